@@ -24,6 +24,21 @@ impl BunyanClient {
         handle_response(resp)
     }
 
+    pub fn get_with_query<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<T, String> {
+        let url = format!("{}{}", self.base_url, path);
+        let resp = self
+            .client
+            .get(&url)
+            .query(query)
+            .send()
+            .map_err(|e| format!("Request failed: {}", e))?;
+        handle_response(resp)
+    }
+
     pub fn post<B: serde::Serialize, T: DeserializeOwned>(
         &self,
         path: &str,
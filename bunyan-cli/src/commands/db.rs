@@ -0,0 +1,53 @@
+use clap::Subcommand;
+
+use bunyan_core::models::MigrationStatus;
+
+use crate::client::BunyanClient;
+use crate::output::{self, OutputMode};
+
+#[derive(Subcommand)]
+pub enum DbCommand {
+    /// Apply pending schema migrations
+    Migrate {
+        /// Only show which migrations are applied or pending
+        #[arg(long)]
+        status: bool,
+    },
+}
+
+pub fn run(client: &BunyanClient, cmd: DbCommand, mode: OutputMode) {
+    match cmd {
+        DbCommand::Migrate { status } => {
+            let migrations: Vec<MigrationStatus> = if status {
+                client.get("/db/migrations")
+            } else {
+                client.post_empty("/db/migrate")
+            }
+            .unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            });
+            match mode {
+                OutputMode::Quiet => {
+                    for m in migrations.iter().filter(|m| m.applied_at.is_none()) {
+                        println!("{}", m.version);
+                    }
+                }
+                OutputMode::Json => output::print_value(mode, &migrations),
+                OutputMode::Table => {
+                    let rows: Vec<Vec<String>> = migrations
+                        .iter()
+                        .map(|m| {
+                            vec![
+                                m.version.to_string(),
+                                m.name.clone(),
+                                m.applied_at.clone().unwrap_or_else(|| "pending".to_string()),
+                            ]
+                        })
+                        .collect();
+                    output::print_table(&["VERSION", "NAME", "APPLIED"], &rows);
+                }
+            }
+        }
+    }
+}
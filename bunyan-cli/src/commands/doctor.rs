@@ -0,0 +1,68 @@
+use clap::Args;
+
+use bunyan_core::models::DoctorReport;
+
+use crate::client::BunyanClient;
+use crate::output::{self, OutputMode};
+
+#[derive(Args)]
+pub struct DoctorArgs {
+    /// Repair every issue that has a safe fix
+    #[arg(long)]
+    fix: bool,
+}
+
+pub fn run(client: &BunyanClient, args: DoctorArgs, mode: OutputMode) {
+    let result = if args.fix {
+        client.post_empty::<DoctorReport>("/doctor/fix")
+    } else {
+        client.get::<DoctorReport>("/doctor")
+    };
+    let report = result.unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+
+    match mode {
+        OutputMode::Json => output::print_value(mode, &report),
+        OutputMode::Quiet => {
+            for i in &report.issues {
+                println!("{}", i.subject);
+            }
+        }
+        OutputMode::Table => {
+            if report.issues.is_empty() {
+                println!("No issues found");
+            }
+            let rows: Vec<Vec<String>> = report
+                .issues
+                .iter()
+                .map(|i| {
+                    let fix = if i.fixed {
+                        "fixed".to_string()
+                    } else if let Some(e) = &i.fix_error {
+                        format!("failed: {}", e)
+                    } else if i.fixable {
+                        "fixable".to_string()
+                    } else {
+                        "manual".to_string()
+                    };
+                    vec![
+                        i.kind.as_str().to_string(),
+                        i.subject.clone(),
+                        i.message.clone(),
+                        fix,
+                    ]
+                })
+                .collect();
+            output::print_table(&["KIND", "SUBJECT", "ISSUE", "FIX"], &rows);
+            for s in &report.skipped {
+                eprintln!("Skipped {}", s);
+            }
+        }
+    }
+
+    if report.issues.iter().any(|i| !i.fixed) {
+        std::process::exit(1);
+    }
+}
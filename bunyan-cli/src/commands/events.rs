@@ -0,0 +1,73 @@
+use clap::Args;
+
+use bunyan_core::models::Event;
+
+use crate::client::BunyanClient;
+use crate::output::{self, OutputMode};
+
+#[derive(Args)]
+pub struct EventsArgs {
+    /// Filter by repository ID
+    #[arg(long)]
+    repo_id: Option<String>,
+    /// Filter by workspace ID
+    #[arg(long)]
+    workspace_id: Option<String>,
+    /// Only show events at or after this RFC 3339 timestamp
+    #[arg(long)]
+    since: Option<String>,
+    /// Filter by event kind (e.g. workspace_created)
+    #[arg(long)]
+    kind: Option<String>,
+    /// Maximum number of events (default: 100)
+    #[arg(long)]
+    limit: Option<u32>,
+}
+
+pub fn run(client: &BunyanClient, args: EventsArgs, mode: OutputMode) {
+    let mut query: Vec<(&str, String)> = Vec::new();
+    if let Some(v) = args.repo_id {
+        query.push(("repo_id", v));
+    }
+    if let Some(v) = args.workspace_id {
+        query.push(("workspace_id", v));
+    }
+    if let Some(v) = args.since {
+        query.push(("since", v));
+    }
+    if let Some(v) = args.kind {
+        query.push(("kind", v));
+    }
+    if let Some(v) = args.limit {
+        query.push(("limit", v.to_string()));
+    }
+
+    let events: Vec<Event> = client
+        .get_with_query("/events", &query)
+        .unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        });
+    match mode {
+        OutputMode::Quiet => {
+            for e in &events {
+                println!("{}", e.id);
+            }
+        }
+        OutputMode::Json => output::print_value(mode, &events),
+        OutputMode::Table => {
+            let rows: Vec<Vec<String>> = events
+                .iter()
+                .map(|e| {
+                    vec![
+                        e.created_at.clone(),
+                        e.kind.as_str().to_string(),
+                        e.workspace_id.clone().unwrap_or_default(),
+                        e.message.clone().unwrap_or_default(),
+                    ]
+                })
+                .collect();
+            output::print_table(&["AT", "KIND", "WORKSPACE", "MESSAGE"], &rows);
+        }
+    }
+}
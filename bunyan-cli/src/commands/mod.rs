@@ -1,4 +1,7 @@
+pub mod db;
 pub mod docker;
+pub mod doctor;
+pub mod events;
 pub mod pane;
 pub mod repo;
 pub mod session;
@@ -1,6 +1,9 @@
 use clap::Subcommand;
 
-use bunyan_core::models::{CreateRepoInput, Repo, UpdateRepoInput};
+use bunyan_core::models::{
+    ConfigValidation, CreateRepoInput, ReorderReposInput, Repo, UpdateRepoInput,
+    ValidateConfigInput,
+};
 
 use crate::client::BunyanClient;
 use crate::output::{self, OutputMode};
@@ -20,8 +23,8 @@ pub enum RepoCommand {
         #[arg(long)]
         name: String,
         /// Git remote URL
-        #[arg(long)]
-        remote_url: String,
+        #[arg(long, required_unless_present = "adopt")]
+        remote_url: Option<String>,
         /// Local path for the repo root
         #[arg(long)]
         root_path: String,
@@ -37,6 +40,9 @@ pub enum RepoCommand {
         /// JSON config blob
         #[arg(long)]
         config: Option<String>,
+        /// Register the clone already at --root-path instead of cloning it
+        #[arg(long, conflicts_with = "remote_url")]
+        adopt: bool,
     },
     /// Update a repository
     Update {
@@ -55,11 +61,30 @@ pub enum RepoCommand {
         #[arg(long)]
         config: Option<String>,
     },
+    /// Move a repository before or after another one in the display order
+    Move {
+        /// Repository ID to move
+        id: String,
+        /// Place it immediately before this repository
+        #[arg(long, conflicts_with = "after", required_unless_present = "after")]
+        before: Option<String>,
+        /// Place it immediately after this repository
+        #[arg(long)]
+        after: Option<String>,
+    },
     /// Delete a repository
     Delete {
         /// Repository ID
         id: String,
     },
+    /// Check a repository config for errors
+    ValidateConfig {
+        /// Repository ID
+        id: String,
+        /// JSON config to check (default: the repo's stored config)
+        #[arg(long)]
+        config: Option<String>,
+    },
 }
 
 pub fn run(client: &BunyanClient, cmd: RepoCommand, mode: OutputMode) {
@@ -103,6 +128,7 @@ pub fn run(client: &BunyanClient, cmd: RepoCommand, mode: OutputMode) {
             remote,
             display_order,
             config,
+            adopt,
         } => {
             let config_val = config.map(|c| {
                 serde_json::from_str::<serde_json::Value>(&c).unwrap_or_else(|e| {
@@ -112,12 +138,13 @@ pub fn run(client: &BunyanClient, cmd: RepoCommand, mode: OutputMode) {
             });
             let input = CreateRepoInput {
                 name,
-                remote_url,
+                remote_url: remote_url.unwrap_or_default(),
                 root_path,
                 default_branch,
                 remote,
                 display_order,
                 config: config_val,
+                adopt,
             };
             let repo: Repo = client.post("/repos", &input).unwrap_or_else(|e| {
                 eprintln!("Error: {}", e);
@@ -170,5 +197,119 @@ pub fn run(client: &BunyanClient, cmd: RepoCommand, mode: OutputMode) {
                 println!("Deleted repo {}", id);
             }
         }
+        RepoCommand::Move { id, before, after } => {
+            let repos: Vec<Repo> = client.get("/repos").unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            });
+            let ids: Vec<String> = repos.into_iter().map(|r| r.id).collect();
+            let ids = match (before, after) {
+                (Some(target), _) => move_relative(&ids, &id, &target, false),
+                (None, Some(target)) => move_relative(&ids, &id, &target, true),
+                (None, None) => unreachable!("clap requires --before or --after"),
+            }
+            .unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            });
+            let repos: Vec<Repo> = client
+                .post("/repos/reorder", &ReorderReposInput { ids })
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            match mode {
+                OutputMode::Quiet => {
+                    for r in &repos {
+                        println!("{}", r.id);
+                    }
+                }
+                OutputMode::Json => output::print_value(mode, &repos),
+                OutputMode::Table => {
+                    let rows: Vec<Vec<String>> = repos
+                        .iter()
+                        .map(|r| vec![r.id.clone(), r.name.clone(), r.display_order.to_string()])
+                        .collect();
+                    output::print_table(&["ID", "NAME", "ORDER"], &rows);
+                }
+            }
+        }
+        RepoCommand::ValidateConfig { id, config } => {
+            let config_val = config.map(|c| {
+                serde_json::from_str::<serde_json::Value>(&c).unwrap_or_else(|e| {
+                    eprintln!("Invalid JSON config: {}", e);
+                    std::process::exit(1);
+                })
+            });
+            let input = ValidateConfigInput { config: config_val };
+            let result: ConfigValidation = client
+                .post(&format!("/repos/{}/config/validate", id), &input)
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            match mode {
+                OutputMode::Json => output::print_value(mode, &result),
+                OutputMode::Quiet => {}
+                OutputMode::Table => {
+                    if result.valid {
+                        println!("Config is valid");
+                    } else {
+                        let rows: Vec<Vec<String>> = result
+                            .errors
+                            .iter()
+                            .map(|e| vec![e.path.clone(), e.message.clone()])
+                            .collect();
+                        output::print_table(&["FIELD", "ERROR"], &rows);
+                    }
+                }
+            }
+            if !result.valid {
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+/// Return `ids` with `id` moved next to `target`: immediately after it when
+/// `after` is set, otherwise immediately before.
+fn move_relative(ids: &[String], id: &str, target: &str, after: bool) -> Result<Vec<String>, String> {
+    if id == target {
+        return Err("Cannot move a repository relative to itself".to_string());
+    }
+    if !ids.iter().any(|i| i == id) {
+        return Err(format!("Repository not found: {}", id));
+    }
+    let mut rest: Vec<String> = ids.iter().filter(|i| *i != id).cloned().collect();
+    let pos = rest
+        .iter()
+        .position(|i| i == target)
+        .ok_or_else(|| format!("Repository not found: {}", target))?;
+    rest.insert(if after { pos + 1 } else { pos }, id.to_string());
+    Ok(rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn move_before_and_after() {
+        let order = ids(&["a", "b", "c", "d"]);
+        assert_eq!(move_relative(&order, "d", "b", false).unwrap(), ids(&["a", "d", "b", "c"]));
+        assert_eq!(move_relative(&order, "a", "c", true).unwrap(), ids(&["b", "c", "a", "d"]));
+        assert_eq!(move_relative(&order, "a", "d", true).unwrap(), ids(&["b", "c", "d", "a"]));
+    }
+
+    #[test]
+    fn move_rejects_unknown_or_self() {
+        let order = ids(&["a", "b"]);
+        assert!(move_relative(&order, "x", "a", false).is_err());
+        assert!(move_relative(&order, "a", "x", false).is_err());
+        assert!(move_relative(&order, "a", "a", true).is_err());
     }
 }
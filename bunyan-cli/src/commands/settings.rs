@@ -1,15 +1,17 @@
 use clap::Subcommand;
 use serde::Serialize;
 
-use bunyan_core::models::Setting;
+use bunyan_core::models::{EffectiveSetting, Setting, SettingDefinition};
 
 use crate::client::BunyanClient;
 use crate::output::{self, OutputMode};
 
 #[derive(Subcommand)]
 pub enum SettingsCommand {
-    /// List all settings
+    /// List all settings, including defaults
     List,
+    /// List the known settings with their types and defaults
+    Keys,
     /// Get a setting by key
     Get {
         /// Setting key
@@ -21,6 +23,9 @@ pub enum SettingsCommand {
         key: String,
         /// Setting value
         value: String,
+        /// Store the value even if the key is unknown or the value is invalid
+        #[arg(long)]
+        force: bool,
     },
 }
 
@@ -32,7 +37,7 @@ struct SetBody {
 pub fn run(client: &BunyanClient, cmd: SettingsCommand, mode: OutputMode) {
     match cmd {
         SettingsCommand::List => {
-            let settings: Vec<Setting> = client.get("/settings").unwrap_or_else(|e| {
+            let settings: Vec<EffectiveSetting> = client.get("/settings").unwrap_or_else(|e| {
                 eprintln!("Error: {}", e);
                 std::process::exit(1);
             });
@@ -46,14 +51,58 @@ pub fn run(client: &BunyanClient, cmd: SettingsCommand, mode: OutputMode) {
                 OutputMode::Table => {
                     let rows: Vec<Vec<String>> = settings
                         .iter()
-                        .map(|s| vec![s.key.clone(), s.value.clone()])
+                        .map(|s| {
+                            let source = if s.is_default {
+                                "default"
+                            } else if s.known {
+                                "set"
+                            } else {
+                                "unknown"
+                            };
+                            vec![s.key.clone(), s.value.clone(), source.to_string()]
+                        })
                         .collect();
-                    output::print_table(&["KEY", "VALUE"], &rows);
+                    output::print_table(&["KEY", "VALUE", "SOURCE"], &rows);
+                }
+            }
+        }
+        SettingsCommand::Keys => {
+            let definitions: Vec<SettingDefinition> = client
+                .get("/settings/definitions")
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            match mode {
+                OutputMode::Quiet => {
+                    for d in &definitions {
+                        println!("{}", d.key);
+                    }
+                }
+                OutputMode::Json => output::print_value(mode, &definitions),
+                OutputMode::Table => {
+                    let rows: Vec<Vec<String>> = definitions
+                        .iter()
+                        .map(|d| {
+                            let value_type = if d.allowed_values.is_empty() {
+                                d.value_type.as_str().to_string()
+                            } else {
+                                d.allowed_values.join("|")
+                            };
+                            vec![
+                                d.key.clone(),
+                                value_type,
+                                d.default_value.clone().unwrap_or_default(),
+                                d.description.clone(),
+                            ]
+                        })
+                        .collect();
+                    output::print_table(&["KEY", "TYPE", "DEFAULT", "DESCRIPTION"], &rows);
                 }
             }
         }
         SettingsCommand::Get { key } => {
-            let setting: Setting =
+            let setting: EffectiveSetting =
                 client.get(&format!("/settings/{}", key)).unwrap_or_else(|e| {
                     eprintln!("Error: {}", e);
                     std::process::exit(1);
@@ -63,10 +112,15 @@ pub fn run(client: &BunyanClient, cmd: SettingsCommand, mode: OutputMode) {
                 _ => output::print_value(mode, &setting),
             }
         }
-        SettingsCommand::Set { key, value } => {
+        SettingsCommand::Set { key, value, force } => {
             let body = SetBody { value };
+            let path = if force {
+                format!("/settings/{}?force=true", key)
+            } else {
+                format!("/settings/{}", key)
+            };
             let setting: Setting = client
-                .put(&format!("/settings/{}", key), &body)
+                .put(&path, &body)
                 .unwrap_or_else(|e| {
                     eprintln!("Error: {}", e);
                     std::process::exit(1);
@@ -1,22 +1,41 @@
 use clap::Subcommand;
 
-use bunyan_core::models::{ClaudeSessionEntry, ContainerMode, CreateWorkspaceInput, TmuxPane, Workspace};
+use bunyan_core::models::{
+    ArchiveInput, BranchMode, Checkpoint, ClaudeSessionEntry, CommitInput, CommitResult,
+    CommitStatus, ContainerMode, CreateCheckpointInput, CreatePullRequestInput,
+    CreateWorkspaceInput, EffectiveWorkspaceConfig, GitStatus, HookRun, PullRequest,
+    PurgeWorkspacesInput, PurgeWorkspacesResult, PushResult, PushStatus, RestoreCheckpointResult,
+    SparseCheckout, SparseInput, SyncInput, SyncResult, SyncStatus, SyncStrategy, TmuxPane,
+    UpdateWorkspaceInput, WarningKind, Workspace, WorkspaceDiff, WorkspaceTransition,
+};
 
 use crate::client::BunyanClient;
 use crate::output::{self, OutputMode};
 
 #[derive(Subcommand)]
 pub enum WorkspaceCommand {
-    /// List workspaces (optionally filter by repo)
+    /// List workspaces (optionally filter by repo, label, state or mode)
     List {
         /// Filter by repository ID
         #[arg(long)]
         repo_id: Option<String>,
+        /// Only workspaces carrying this label (repeatable; all must match)
+        #[arg(long)]
+        label: Vec<String>,
+        /// Filter by state (creating, ready, failed, suspended, archiving, archived)
+        #[arg(long)]
+        state: Option<String>,
+        /// Filter by container mode (local, container)
+        #[arg(long)]
+        mode: Option<String>,
     },
     /// Get a workspace by ID
     Get {
         /// Workspace ID
         id: String,
+        /// Refresh the pull request from GitHub first
+        #[arg(long)]
+        refresh: bool,
     },
     /// Create a new workspace (worktree)
     Create {
@@ -32,11 +51,207 @@ pub enum WorkspaceCommand {
         /// Use container mode
         #[arg(long)]
         container: bool,
+        /// Check out an existing local branch instead of creating one
+        #[arg(long, conflicts_with_all = ["remote", "base"])]
+        existing: bool,
+        /// Track a remote branch (e.g. a teammate's `origin/feature`)
+        #[arg(long)]
+        remote: bool,
+        /// Ref to start a new branch from, or the remote-tracking ref with --remote
+        #[arg(long)]
+        base: Option<String>,
+        /// JSON config overrides, e.g. '{"container": {"image": "python:3.12"}}'
+        #[arg(long)]
+        config: Option<String>,
+    },
+    /// Show git status (changes, ahead/behind, last commit) for ready workspaces
+    Status {
+        /// Workspace ID (default: every ready workspace)
+        id: Option<String>,
+        /// Only workspaces of this repository
+        #[arg(long, conflicts_with = "id")]
+        repo_id: Option<String>,
+    },
+    /// Show a workspace's changes since it diverged from the default branch
+    Diff {
+        /// Workspace ID
+        id: String,
+        /// Ref to compare against (default: <remote>/<default_branch>)
+        #[arg(long)]
+        base: Option<String>,
+        /// Only show per-file line counts
+        #[arg(long)]
+        stat: bool,
+        /// Limit to this path (repeatable)
+        #[arg(long)]
+        path: Vec<String>,
+        /// Patch size cap in bytes
+        #[arg(long)]
+        max_bytes: Option<usize>,
+    },
+    /// Commit changes in a workspace's worktree
+    Commit {
+        /// Workspace ID
+        id: String,
+        /// Commit message
+        #[arg(short, long)]
+        message: String,
+        /// Stage and commit only this path (repeatable)
+        #[arg(long)]
+        path: Vec<String>,
+        /// Stage every modified or deleted tracked file first
+        #[arg(short, long, conflicts_with = "path")]
+        all: bool,
+    },
+    /// Push a workspace's branch to the repo's remote, setting it as upstream
+    Push {
+        /// Workspace ID
+        id: String,
+    },
+    /// Fetch the default branch and rebase a workspace onto it (or merge it in)
+    Sync {
+        /// Workspace ID
+        #[arg(required_unless_present = "all")]
+        id: Option<String>,
+        /// Sync every ready workspace of --repo-id instead
+        #[arg(long, conflicts_with = "id", requires = "repo_id")]
+        all: bool,
+        /// Repository whose workspaces --all syncs
+        #[arg(long, conflicts_with = "id")]
+        repo_id: Option<String>,
+        /// Merge the default branch in instead of rebasing
+        #[arg(long)]
+        merge: bool,
+        /// On conflict, leave the rebase or merge in progress to resolve by hand
+        #[arg(long)]
+        stop_on_conflict: bool,
+    },
+    /// Open a pull request for a workspace's pushed branch (or show the open one)
+    Pr {
+        /// Workspace ID
+        id: String,
+        /// Title (default: subject of the latest commit)
+        #[arg(long)]
+        title: Option<String>,
+        /// Description
+        #[arg(long)]
+        body: Option<String>,
+        /// Target branch (default: the repo's default branch)
+        #[arg(long)]
+        base: Option<String>,
+        /// Open as a draft
+        #[arg(long)]
+        draft: bool,
+    },
+    /// Show a workspace's sparse-checkout directories, or widen them
+    Sparse {
+        /// Workspace ID
+        id: String,
+        /// Also check out this directory (repeatable)
+        #[arg(long)]
+        add: Vec<String>,
+        /// Check out the whole tree
+        #[arg(long, conflicts_with = "add")]
+        all: bool,
+    },
+    /// Snapshot a workspace's working tree without touching its index or HEAD
+    Checkpoint {
+        /// Workspace ID
+        id: String,
+        /// Describe the checkpoint
+        #[arg(short, long)]
+        message: Option<String>,
+    },
+    /// List a workspace's checkpoints, newest first
+    Checkpoints {
+        /// Workspace ID
+        id: String,
+    },
+    /// Restore a workspace to a checkpoint (the current state is checkpointed first)
+    Restore {
+        /// Workspace ID
+        id: String,
+        /// Checkpoint ID
+        checkpoint_id: String,
+    },
+    /// List a workspace's setup, teardown and post-checkout runs, or show one's output
+    Hooks {
+        /// Workspace ID
+        id: String,
+        /// Hook run ID
+        run: Option<i64>,
+    },
+    /// Show a workspace's effective config (repo config plus overrides)
+    Config {
+        /// Workspace ID
+        id: String,
+    },
+    /// Update a workspace's description, notes, labels or config overrides
+    Update {
+        /// Workspace ID
+        id: String,
+        /// New description (empty string clears it)
+        #[arg(long)]
+        description: Option<String>,
+        /// New notes (empty string clears them)
+        #[arg(long)]
+        notes: Option<String>,
+        /// Label to set (repeatable; replaces existing labels)
+        #[arg(long)]
+        label: Vec<String>,
+        /// Remove all labels
+        #[arg(long, conflicts_with = "label")]
+        clear_labels: bool,
+        /// JSON config overrides (`{}` clears them)
+        #[arg(long)]
+        config: Option<String>,
     },
     /// Archive a workspace
     Archive {
         /// Workspace ID
         id: String,
+        /// Archive even with uncommitted files or unpushed commits, discarding them;
+        /// with --delete-branch, delete the branch even if unmerged
+        #[arg(long)]
+        force: bool,
+        /// Delete the local branch if it is merged into the default branch
+        #[arg(long)]
+        delete_branch: bool,
+        /// Delete the remote branch if it is merged into the default branch
+        #[arg(long)]
+        delete_remote_branch: bool,
+    },
+    /// Permanently delete an archived workspace
+    Delete {
+        /// Workspace ID
+        id: String,
+    },
+    /// Delete archived workspaces in bulk
+    Purge {
+        /// Only purge workspaces archived longer ago than this (e.g. 30d, 12h, 2w)
+        #[arg(long)]
+        older_than: Option<String>,
+        /// Only purge workspaces of this repository
+        #[arg(long)]
+        repo_id: Option<String>,
+        /// List what would be removed without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Suspend a workspace (closes panes, stops its container)
+    Suspend {
+        /// Workspace ID
+        id: String,
+    },
+    /// Resume a suspended workspace
+    Resume {
+        /// Workspace ID
+        id: String,
+    },
+    /// Show the lifecycle state history of a workspace
+    History {
+        /// Workspace ID
+        id: String,
     },
     /// View workspace in iTerm
     View {
@@ -57,15 +272,31 @@ pub enum WorkspaceCommand {
 
 pub fn run(client: &BunyanClient, cmd: WorkspaceCommand, mode: OutputMode) {
     match cmd {
-        WorkspaceCommand::List { repo_id } => {
-            let path = match &repo_id {
-                Some(id) => format!("/workspaces?repo_id={}", id),
-                None => "/workspaces".to_string(),
-            };
-            let workspaces: Vec<Workspace> = client.get(&path).unwrap_or_else(|e| {
-                eprintln!("Error: {}", e);
-                std::process::exit(1);
-            });
+        WorkspaceCommand::List {
+            repo_id,
+            label,
+            state,
+            mode: container_mode,
+        } => {
+            let mut query: Vec<(&str, String)> = Vec::new();
+            if let Some(id) = repo_id {
+                query.push(("repo_id", id));
+            }
+            if !label.is_empty() {
+                query.push(("label", label.join(",")));
+            }
+            if let Some(s) = state {
+                query.push(("state", s));
+            }
+            if let Some(m) = container_mode {
+                query.push(("container_mode", m));
+            }
+            let workspaces: Vec<Workspace> = client
+                .get_with_query("/workspaces", &query)
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
             match mode {
                 OutputMode::Quiet => {
                     for w in &workspaces {
@@ -83,16 +314,25 @@ pub fn run(client: &BunyanClient, cmd: WorkspaceCommand, mode: OutputMode) {
                                 w.branch.clone(),
                                 w.state.as_str().to_string(),
                                 w.container_mode.as_str().to_string(),
+                                w.labels.join(","),
                             ]
                         })
                         .collect();
-                    output::print_table(&["ID", "NAME", "BRANCH", "STATE", "MODE"], &rows);
+                    output::print_table(
+                        &["ID", "NAME", "BRANCH", "STATE", "MODE", "LABELS"],
+                        &rows,
+                    );
                 }
             }
         }
-        WorkspaceCommand::Get { id } => {
+        WorkspaceCommand::Get { id, refresh } => {
+            let path = if refresh {
+                format!("/workspaces/{}?refresh=true", id)
+            } else {
+                format!("/workspaces/{}", id)
+            };
             let ws: Workspace = client
-                .get(&format!("/workspaces/{}", id))
+                .get(&path)
                 .unwrap_or_else(|e| {
                     eprintln!("Error: {}", e);
                     std::process::exit(1);
@@ -107,7 +347,17 @@ pub fn run(client: &BunyanClient, cmd: WorkspaceCommand, mode: OutputMode) {
             name,
             branch,
             container,
+            existing,
+            remote,
+            base,
+            config,
         } => {
+            let config = config.map(|c| {
+                serde_json::from_str::<serde_json::Value>(&c).unwrap_or_else(|e| {
+                    eprintln!("Invalid JSON config: {}", e);
+                    std::process::exit(1);
+                })
+            });
             let input = CreateWorkspaceInput {
                 repository_id: repo,
                 directory_name: name,
@@ -117,6 +367,15 @@ pub fn run(client: &BunyanClient, cmd: WorkspaceCommand, mode: OutputMode) {
                 } else {
                     ContainerMode::Local
                 },
+                branch_mode: if existing {
+                    BranchMode::Existing
+                } else if remote {
+                    BranchMode::Remote
+                } else {
+                    BranchMode::New
+                },
+                base_ref: base,
+                config,
             };
             let ws: Workspace = client.post("/workspaces", &input).unwrap_or_else(|e| {
                 eprintln!("Error: {}", e);
@@ -126,10 +385,477 @@ pub fn run(client: &BunyanClient, cmd: WorkspaceCommand, mode: OutputMode) {
                 OutputMode::Quiet => println!("{}", ws.id),
                 _ => output::print_value(mode, &ws),
             }
+            for warning in &ws.warnings {
+                let step = match warning.kind {
+                    WarningKind::Submodules => "submodules",
+                    WarningKind::Lfs => "lfs",
+                };
+                eprintln!("Warning ({}): {}", step, warning.message);
+            }
+        }
+        WorkspaceCommand::Status { id, repo_id } => {
+            let workspaces: Vec<Workspace> = match id {
+                Some(id) => vec![client.get(&format!("/workspaces/{}", id)).unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                })],
+                None => {
+                    let mut query: Vec<(&str, String)> = vec![("state", "ready".to_string())];
+                    if let Some(id) = repo_id {
+                        query.push(("repo_id", id));
+                    }
+                    client
+                        .get_with_query("/workspaces", &query)
+                        .unwrap_or_else(|e| {
+                            eprintln!("Error: {}", e);
+                            std::process::exit(1);
+                        })
+                }
+            };
+            // One unreachable worktree shouldn't hide the rest
+            let statuses: Vec<(Workspace, GitStatus)> = workspaces
+                .into_iter()
+                .filter_map(|w| {
+                    match client.get::<GitStatus>(&format!("/workspaces/{}/git/status", w.id)) {
+                        Ok(s) => Some((w, s)),
+                        Err(e) => {
+                            eprintln!("Warning: {}: {}", w.directory_name, e);
+                            None
+                        }
+                    }
+                })
+                .collect();
+            match mode {
+                OutputMode::Quiet => {
+                    for (w, _) in &statuses {
+                        println!("{}", w.id);
+                    }
+                }
+                OutputMode::Json => {
+                    let values: Vec<&GitStatus> = statuses.iter().map(|(_, s)| s).collect();
+                    output::print_value(mode, &values);
+                }
+                OutputMode::Table => {
+                    let count = |n: Option<u32>| n.map(|n| n.to_string()).unwrap_or_else(|| "-".to_string());
+                    let rows: Vec<Vec<String>> = statuses
+                        .iter()
+                        .map(|(w, s)| {
+                            vec![
+                                w.id.clone(),
+                                w.directory_name.clone(),
+                                s.branch.clone().unwrap_or_else(|| "(detached)".to_string()),
+                                s.staged.to_string(),
+                                s.dirty.to_string(),
+                                s.untracked.to_string(),
+                                count(s.ahead),
+                                count(s.behind),
+                                s.last_commit
+                                    .as_ref()
+                                    .map(|c| c.summary.clone())
+                                    .unwrap_or_default(),
+                            ]
+                        })
+                        .collect();
+                    output::print_table(
+                        &[
+                            "ID", "NAME", "BRANCH", "STAGED", "DIRTY", "UNTRACKED", "AHEAD",
+                            "BEHIND", "LAST COMMIT",
+                        ],
+                        &rows,
+                    );
+                }
+            }
+        }
+        WorkspaceCommand::Diff {
+            id,
+            base,
+            stat,
+            path,
+            max_bytes,
+        } => {
+            let mut query: Vec<(&str, String)> = Vec::new();
+            if let Some(b) = base {
+                query.push(("base", b));
+            }
+            if stat {
+                query.push(("stat", "true".to_string()));
+            }
+            if !path.is_empty() {
+                query.push(("paths", path.join(",")));
+            }
+            if let Some(n) = max_bytes {
+                query.push(("max_bytes", n.to_string()));
+            }
+            let diff: WorkspaceDiff = client
+                .get_with_query(&format!("/workspaces/{}/diff", id), &query)
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            match mode {
+                OutputMode::Json => output::print_value(mode, &diff),
+                OutputMode::Quiet => {
+                    for f in &diff.files {
+                        println!("{}", f.path);
+                    }
+                }
+                OutputMode::Table => match &diff.patch {
+                    Some(patch) => {
+                        output::page(patch);
+                        if diff.truncated {
+                            eprintln!("(diff truncated; raise --max-bytes or narrow with --path)");
+                        }
+                    }
+                    None => {
+                        let rows: Vec<Vec<String>> = diff
+                            .files
+                            .iter()
+                            .map(|f| {
+                                if f.binary {
+                                    vec![f.path.clone(), "-".to_string(), "-".to_string()]
+                                } else {
+                                    vec![f.path.clone(), f.additions.to_string(), f.deletions.to_string()]
+                                }
+                            })
+                            .collect();
+                        output::print_table(&["PATH", "ADDED", "DELETED"], &rows);
+                    }
+                },
+            }
         }
-        WorkspaceCommand::Archive { id } => {
+        WorkspaceCommand::Commit {
+            id,
+            message,
+            path,
+            all,
+        } => {
+            let input = CommitInput {
+                message,
+                paths: path,
+                all,
+            };
+            let result: CommitResult = client
+                .post(&format!("/workspaces/{}/commit", id), &input)
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            match mode {
+                OutputMode::Quiet => {
+                    if let Some(c) = &result.commit {
+                        println!("{}", c.sha);
+                    }
+                }
+                OutputMode::Json => output::print_value(mode, &result),
+                OutputMode::Table => match (&result.status, &result.commit) {
+                    (CommitStatus::Committed, Some(c)) => println!("{} {}", &c.sha[..c.sha.len().min(7)], c.summary),
+                    (CommitStatus::NothingToCommit, _) => println!("Nothing to commit"),
+                    _ => {}
+                },
+            }
+            if result.status == CommitStatus::HookFailed {
+                eprintln!("Commit hook failed:\n{}", result.output);
+                std::process::exit(1);
+            }
+        }
+        WorkspaceCommand::Push { id } => {
+            let result: PushResult = client
+                .post_empty(&format!("/workspaces/{}/push", id))
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            match mode {
+                OutputMode::Quiet => {}
+                OutputMode::Json => output::print_value(mode, &result),
+                OutputMode::Table => match result.status {
+                    PushStatus::Pushed => println!("Pushed {} to {}", result.branch, result.remote),
+                    PushStatus::UpToDate => println!("{} is up to date on {}", result.branch, result.remote),
+                    _ => {}
+                },
+            }
+            match result.status {
+                PushStatus::Rejected => {
+                    eprintln!("Push rejected:\n{}", result.output);
+                    std::process::exit(1);
+                }
+                PushStatus::HookFailed => {
+                    eprintln!("Pre-push hook failed:\n{}", result.output);
+                    std::process::exit(1);
+                }
+                _ => {}
+            }
+        }
+        WorkspaceCommand::Sync {
+            id,
+            all,
+            repo_id,
+            merge,
+            stop_on_conflict,
+        } => {
+            let targets: Vec<(String, String)> = match id {
+                Some(id) => vec![(id.clone(), id)],
+                None => {
+                    let query = vec![
+                        ("state", "ready".to_string()),
+                        ("repo_id", repo_id.unwrap_or_default()),
+                    ];
+                    let workspaces: Vec<Workspace> = client
+                        .get_with_query("/workspaces", &query)
+                        .unwrap_or_else(|e| {
+                            eprintln!("Error: {}", e);
+                            std::process::exit(1);
+                        });
+                    workspaces.into_iter().map(|w| (w.id, w.directory_name)).collect()
+                }
+            };
+            let input = SyncInput {
+                strategy: if merge { SyncStrategy::Merge } else { SyncStrategy::Rebase },
+                stop_on_conflict,
+            };
+            // Keep going after a conflict so one stuck workspace doesn't block the rest
+            let mut failed = false;
+            let mut results: Vec<(String, SyncResult)> = Vec::new();
+            for (id, name) in targets {
+                match client.post::<_, SyncResult>(&format!("/workspaces/{}/sync", id), &input) {
+                    Ok(result) => {
+                        failed |= result.status == SyncStatus::Conflicted;
+                        results.push((name, result));
+                    }
+                    Err(e) => {
+                        eprintln!("Error: {}: {}", name, e);
+                        failed = true;
+                    }
+                }
+            }
+            match mode {
+                OutputMode::Quiet => {}
+                OutputMode::Json if all => {
+                    let values: Vec<&SyncResult> = results.iter().map(|(_, r)| r).collect();
+                    output::print_value(mode, &values);
+                }
+                OutputMode::Json => {
+                    if let Some((_, result)) = results.first() {
+                        output::print_value(mode, result);
+                    }
+                }
+                OutputMode::Table => {
+                    for (name, r) in &results {
+                        let line = match r.status {
+                            SyncStatus::UpToDate => format!("up to date with {}", r.base),
+                            SyncStatus::Synced if r.strategy == SyncStrategy::Merge => format!("merged {}", r.base),
+                            SyncStatus::Synced => format!("rebased onto {}", r.base),
+                            SyncStatus::Conflicted => format!(
+                                "conflicts with {} in {}{}",
+                                r.base,
+                                r.conflicts.join(", "),
+                                if r.aborted { " (aborted)" } else { " (left in progress)" }
+                            ),
+                        };
+                        if all {
+                            println!("{}: {}", name, line);
+                        } else {
+                            println!("{}", line);
+                        }
+                    }
+                }
+            }
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        WorkspaceCommand::Pr {
+            id,
+            title,
+            body,
+            base,
+            draft,
+        } => {
+            let input = CreatePullRequestInput {
+                title,
+                body,
+                base,
+                draft,
+            };
+            let pr: PullRequest = client
+                .post(&format!("/workspaces/{}/pull-request", id), &input)
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            match mode {
+                OutputMode::Quiet => println!("{}", pr.url),
+                OutputMode::Json => output::print_value(mode, &pr),
+                OutputMode::Table => println!("#{} {} ({})", pr.number, pr.title, pr.url),
+            }
+        }
+        WorkspaceCommand::Sparse { id, add, all } => {
+            let path = format!("/workspaces/{}/sparse", id);
+            let result: Result<SparseCheckout, String> = if add.is_empty() && !all {
+                client.get(&path)
+            } else {
+                client.post(&path, &SparseInput { paths: add, all })
+            };
+            let sparse = result.unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            });
+            match mode {
+                OutputMode::Json => output::print_value(mode, &sparse),
+                OutputMode::Table if !sparse.sparse => println!("Full checkout"),
+                _ => {
+                    for path in &sparse.paths {
+                        println!("{}", path);
+                    }
+                }
+            }
+        }
+        WorkspaceCommand::Checkpoint { id, message } => {
+            let checkpoint: Checkpoint = client
+                .post(
+                    &format!("/workspaces/{}/checkpoints", id),
+                    &CreateCheckpointInput { message },
+                )
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            match mode {
+                OutputMode::Quiet => println!("{}", checkpoint.id),
+                OutputMode::Json => output::print_value(mode, &checkpoint),
+                OutputMode::Table => println!("Checkpoint {} created", checkpoint.id),
+            }
+        }
+        WorkspaceCommand::Checkpoints { id } => {
+            let checkpoints: Vec<Checkpoint> = client
+                .get(&format!("/workspaces/{}/checkpoints", id))
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            match mode {
+                OutputMode::Quiet => {
+                    for c in &checkpoints {
+                        println!("{}", c.id);
+                    }
+                }
+                OutputMode::Json => output::print_value(mode, &checkpoints),
+                OutputMode::Table => {
+                    let rows: Vec<Vec<String>> = checkpoints
+                        .iter()
+                        .map(|c| {
+                            vec![
+                                c.id.clone(),
+                                c.head.chars().take(8).collect(),
+                                c.created_at.clone(),
+                                c.message.clone(),
+                            ]
+                        })
+                        .collect();
+                    output::print_table(&["ID", "HEAD", "CREATED", "MESSAGE"], &rows);
+                }
+            }
+        }
+        WorkspaceCommand::Restore { id, checkpoint_id } => {
+            let result: RestoreCheckpointResult = client
+                .post_empty(&format!(
+                    "/workspaces/{}/checkpoints/{}/restore",
+                    id, checkpoint_id
+                ))
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            match mode {
+                OutputMode::Quiet => println!("{}", result.backup.id),
+                OutputMode::Json => output::print_value(mode, &result),
+                OutputMode::Table => println!(
+                    "Restored {} (previous state saved as {})",
+                    result.restored.id, result.backup.id
+                ),
+            }
+        }
+        WorkspaceCommand::Hooks { id, run: Some(run) } => {
+            let run: HookRun = client
+                .get(&format!("/workspaces/{}/hooks/{}", id, run))
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            match mode {
+                OutputMode::Json => output::print_value(mode, &run),
+                _ => print!("{}", run.output),
+            }
+        }
+        WorkspaceCommand::Hooks { id, run: None } => {
+            let runs: Vec<HookRun> = client
+                .get(&format!("/workspaces/{}/hooks", id))
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            match mode {
+                OutputMode::Quiet => {
+                    for r in &runs {
+                        println!("{}", r.id);
+                    }
+                }
+                OutputMode::Json => output::print_value(mode, &runs),
+                OutputMode::Table => {
+                    let rows: Vec<Vec<String>> = runs
+                        .iter()
+                        .map(|r| {
+                            vec![
+                                r.id.to_string(),
+                                r.hook.as_str().to_string(),
+                                r.exit_code.map_or("-".to_string(), |c| c.to_string()),
+                                r.finished_at.clone(),
+                                r.command.clone(),
+                            ]
+                        })
+                        .collect();
+                    output::print_table(&["ID", "HOOK", "EXIT", "FINISHED", "COMMAND"], &rows);
+                }
+            }
+        }
+        WorkspaceCommand::Config { id } => {
+            let config: EffectiveWorkspaceConfig = client
+                .get(&format!("/workspaces/{}/config", id))
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            output::print_value(mode, &config);
+        }
+        WorkspaceCommand::Update {
+            id,
+            description,
+            notes,
+            label,
+            clear_labels,
+            config,
+        } => {
+            let labels = if clear_labels {
+                Some(Vec::new())
+            } else if label.is_empty() {
+                None
+            } else {
+                Some(label)
+            };
+            let config_val = config.map(|c| {
+                serde_json::from_str::<serde_json::Value>(&c).unwrap_or_else(|e| {
+                    eprintln!("Invalid JSON config: {}", e);
+                    std::process::exit(1);
+                })
+            });
+            let input = UpdateWorkspaceInput {
+                description,
+                notes,
+                labels,
+                config: config_val,
+            };
             let ws: Workspace = client
-                .post_empty(&format!("/workspaces/{}/archive", id))
+                .put(&format!("/workspaces/{}", id), &input)
                 .unwrap_or_else(|e| {
                     eprintln!("Error: {}", e);
                     std::process::exit(1);
@@ -139,6 +865,140 @@ pub fn run(client: &BunyanClient, cmd: WorkspaceCommand, mode: OutputMode) {
                 _ => output::print_value(mode, &ws),
             }
         }
+        WorkspaceCommand::Archive {
+            id,
+            force,
+            delete_branch,
+            delete_remote_branch,
+        } => {
+            let input = ArchiveInput {
+                force,
+                delete_branch,
+                delete_remote_branch,
+            };
+            let ws: Workspace = client
+                .post(&format!("/workspaces/{}/archive", id), &input)
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            match mode {
+                OutputMode::Quiet => println!("{}", ws.id),
+                _ => output::print_value(mode, &ws),
+            }
+        }
+        WorkspaceCommand::Delete { id } => {
+            let ws: Workspace = client
+                .delete(&format!("/workspaces/{}", id))
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            match mode {
+                OutputMode::Quiet => println!("{}", ws.id),
+                OutputMode::Json => output::print_value(mode, &ws),
+                OutputMode::Table => println!("Deleted workspace {}", ws.id),
+            }
+        }
+        WorkspaceCommand::Purge {
+            older_than,
+            repo_id,
+            dry_run,
+        } => {
+            let input = PurgeWorkspacesInput {
+                older_than,
+                repository_id: repo_id,
+                dry_run,
+            };
+            let result: PurgeWorkspacesResult = client
+                .post("/workspaces/purge", &input)
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            match mode {
+                OutputMode::Quiet => {
+                    for w in &result.workspaces {
+                        println!("{}", w.id);
+                    }
+                }
+                OutputMode::Json => output::print_value(mode, &result),
+                OutputMode::Table => {
+                    let rows: Vec<Vec<String>> = result
+                        .workspaces
+                        .iter()
+                        .map(|w| {
+                            vec![
+                                w.id.clone(),
+                                w.directory_name.clone(),
+                                w.branch.clone(),
+                                w.archived_at.clone().unwrap_or_default(),
+                            ]
+                        })
+                        .collect();
+                    output::print_table(&["ID", "NAME", "BRANCH", "ARCHIVED"], &rows);
+                    let verb = if result.dry_run { "Would purge" } else { "Purged" };
+                    println!("{} {} workspace(s)", verb, result.workspaces.len());
+                }
+            }
+        }
+        WorkspaceCommand::Suspend { id } => {
+            let ws: Workspace = client
+                .post_empty(&format!("/workspaces/{}/suspend", id))
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            match mode {
+                OutputMode::Quiet => println!("{}", ws.id),
+                _ => output::print_value(mode, &ws),
+            }
+        }
+        WorkspaceCommand::Resume { id } => {
+            let ws: Workspace = client
+                .post_empty(&format!("/workspaces/{}/resume", id))
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            match mode {
+                OutputMode::Quiet => println!("{}", ws.id),
+                _ => output::print_value(mode, &ws),
+            }
+        }
+        WorkspaceCommand::History { id } => {
+            let history: Vec<WorkspaceTransition> = client
+                .get(&format!("/workspaces/{}/transitions", id))
+                .unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+            match mode {
+                OutputMode::Quiet => {
+                    for t in &history {
+                        println!("{}", t.to_state.as_str());
+                    }
+                }
+                OutputMode::Json => output::print_value(mode, &history),
+                OutputMode::Table => {
+                    let rows: Vec<Vec<String>> = history
+                        .iter()
+                        .map(|t| {
+                            vec![
+                                t.created_at.clone(),
+                                t.from_state
+                                    .as_ref()
+                                    .map(|s| s.as_str().to_string())
+                                    .unwrap_or_default(),
+                                t.to_state.as_str().to_string(),
+                                t.reason.clone().unwrap_or_default(),
+                            ]
+                        })
+                        .collect();
+                    output::print_table(&["AT", "FROM", "TO", "REASON"], &rows);
+                }
+            }
+        }
         WorkspaceCommand::View { id } => {
             let result: serde_json::Value = client
                 .post_empty(&format!("/workspaces/{}/view", id))
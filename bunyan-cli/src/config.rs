@@ -9,7 +9,7 @@ pub fn discover_server_url(port_override: Option<u16>) -> String {
                 .ok()
                 .and_then(|p| p.parse().ok())
         })
-        .or_else(|| read_port_file())
+        .or_else(read_port_file)
         .unwrap_or(3333);
 
     format!("http://127.0.0.1:{}", port)
@@ -60,6 +60,15 @@ enum Command {
         #[command(subcommand)]
         cmd: commands::settings::SettingsCommand,
     },
+    /// Show the audit log of actions Bunyan has taken
+    Events(commands::events::EventsArgs),
+    /// Find drift between the database, worktrees, tmux and containers
+    Doctor(commands::doctor::DoctorArgs),
+    /// Database maintenance
+    Db {
+        #[command(subcommand)]
+        cmd: commands::db::DbCommand,
+    },
     /// Check server health and Docker availability
     Status,
     /// Start the bunyan server in the foreground
@@ -113,6 +122,9 @@ fn main() {
                 Command::Pane { cmd: sub } => commands::pane::run(&client, sub, mode),
                 Command::Docker { cmd: sub } => commands::docker::run(&client, sub, mode),
                 Command::Settings { cmd: sub } => commands::settings::run(&client, sub, mode),
+                Command::Events(args) => commands::events::run(&client, args, mode),
+                Command::Doctor(args) => commands::doctor::run(&client, args, mode),
+                Command::Db { cmd: sub } => commands::db::run(&client, sub, mode),
                 Command::Status => run_status(&client, mode),
                 Command::Serve { .. } | Command::Up { .. } | Command::Down => unreachable!(),
             }
@@ -58,3 +58,27 @@ pub fn print_table(headers: &[&str], rows: &[Vec<String>]) {
         println!("{}", line.join("  "));
     }
 }
+
+/// Show long text through `$PAGER` (default `less -FRX`) when stdout is a
+/// terminal; otherwise, or if the pager can't start, print it directly.
+pub fn page(text: &str) {
+    use std::io::{IsTerminal, Write};
+    use std::process::{Command, Stdio};
+
+    if std::io::stdout().is_terminal() {
+        let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -FRX".to_string());
+        if let Ok(mut child) = Command::new("sh")
+            .args(["-c", &pager])
+            .stdin(Stdio::piped())
+            .spawn()
+        {
+            if let Some(mut stdin) = child.stdin.take() {
+                // The user may quit the pager early; a broken pipe is fine
+                let _ = stdin.write_all(text.as_bytes());
+            }
+            let _ = child.wait();
+            return;
+        }
+    }
+    print!("{}", text);
+}
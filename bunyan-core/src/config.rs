@@ -0,0 +1,431 @@
+use std::collections::HashMap;
+
+use rusqlite::Connection;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::docker;
+use crate::editor::Editor;
+use crate::error::{BunyanError, Result};
+use crate::git;
+use crate::models::{
+    CheckoutConfig, ConfigFieldError, ContainerConfig, ContainerOverrides, EffectiveWorkspaceConfig,
+    Repo, RepoConfig, Workspace, WorkspaceConfig,
+};
+use crate::settings;
+
+struct Errors(Vec<ConfigFieldError>);
+
+impl Errors {
+    fn push(&mut self, path: &str, message: impl Into<String>) {
+        self.0.push(ConfigFieldError {
+            path: path.to_string(),
+            message: message.into(),
+        });
+    }
+}
+
+/// The docker validators report `Docker` errors; keep only their message.
+fn message(err: BunyanError) -> String {
+    match err {
+        BunyanError::Docker(msg) => msg,
+        other => other.to_string(),
+    }
+}
+
+/// Deserialize a raw config into its serde type. Unknown fields, missing
+/// fields and wrong types are reported by serde, at the path of the field.
+fn deserialize<T: DeserializeOwned + Default>(value: &Value) -> std::result::Result<T, ConfigFieldError> {
+    if value.is_null() {
+        return Ok(T::default());
+    }
+    serde_path_to_error::deserialize(value).map_err(|e| {
+        let path = e.path().to_string();
+        ConfigFieldError {
+            path: if path == "." { String::new() } else { path },
+            message: e.into_inner().to_string(),
+        }
+    })
+}
+
+/// Check the values a repo's container config and a workspace's container
+/// overrides have in common.
+fn check_container(
+    image: Option<&str>,
+    ports: Option<&[String]>,
+    env: Option<&HashMap<String, String>>,
+    shell: Option<&str>,
+    errors: &mut Errors,
+) {
+    if let Some(image) = image {
+        if let Err(e) = docker::validate_image(image) {
+            errors.push("container.image", message(e));
+        }
+    }
+    for (i, spec) in ports.unwrap_or_default().iter().enumerate() {
+        if let Err(e) = docker::parse_port_spec(spec) {
+            errors.push(&format!("container.ports[{}]", i), message(e));
+        }
+    }
+    if let Some(env) = env {
+        let mut names: Vec<&String> = env.keys().collect();
+        names.sort();
+        for name in names {
+            let path = format!("container.env.{}", name);
+            if name.is_empty() || name.contains('=') {
+                errors.push(&path, "invalid variable name");
+            } else if let Err(e) = docker::validate_env(std::slice::from_ref(name)) {
+                errors.push(&path, message(e));
+            }
+        }
+    }
+    if let Some(shell) = shell {
+        if !shell.starts_with('/') {
+            errors.push("container.shell", "expected an absolute path");
+        }
+    }
+}
+
+fn check_checkout(checkout: &CheckoutConfig, errors: &mut Errors) {
+    if let Some(filter) = &checkout.filter {
+        if !git::is_clone_filter(filter) {
+            errors.push("checkout.filter", "expected blob:none, blob:limit=<size> or tree:<depth>");
+        }
+    }
+    if checkout.depth == Some(0) {
+        errors.push("checkout.depth", "expected a positive integer");
+    }
+    for (i, dir) in checkout.sparse.iter().enumerate() {
+        if let Err(e) = git::check_sparse_dir(dir) {
+            errors.push(&format!("checkout.sparse[{}]", i), e);
+        }
+    }
+}
+
+/// Check the values of a repo config that its types alone don't constrain.
+fn check_repo(config: &RepoConfig) -> Vec<ConfigFieldError> {
+    let mut errors = Errors(Vec::new());
+    if let Some(c) = &config.container {
+        check_container(c.image.as_deref(), c.ports.as_deref(), c.env.as_ref(), c.shell.as_deref(), &mut errors);
+    }
+    if let Some(checkout) = &config.checkout {
+        check_checkout(checkout, &mut errors);
+    }
+    errors.0
+}
+
+fn check_workspace(config: &WorkspaceConfig) -> Vec<ConfigFieldError> {
+    let mut errors = Errors(Vec::new());
+    if let Some(c) = &config.container {
+        check_container(c.image.as_deref(), c.ports.as_deref(), c.env.as_ref(), c.shell.as_deref(), &mut errors);
+    }
+    if let Some(id) = &config.editor {
+        if Editor::from_id(id).is_none() {
+            errors.push("editor", format!("unknown editor '{}'", id));
+        }
+    }
+    errors.0
+}
+
+/// Check a raw repo config against `RepoConfig`. A malformed config reports
+/// the first field serde rejects; a well-formed one reports every bad value.
+pub fn validate(value: &Value) -> Vec<ConfigFieldError> {
+    match deserialize::<RepoConfig>(value) {
+        Ok(config) => check_repo(&config),
+        Err(e) => vec![e],
+    }
+}
+
+/// Check a raw workspace config against `WorkspaceConfig`.
+pub fn validate_workspace(value: &Value) -> Vec<ConfigFieldError> {
+    match deserialize::<WorkspaceConfig>(value) {
+        Ok(config) => check_workspace(&config),
+        Err(e) => vec![e],
+    }
+}
+
+fn invalid(kind: &str, errors: &[ConfigFieldError]) -> BunyanError {
+    let details: Vec<String> = errors
+        .iter()
+        .map(|e| format!("{}: {}", e.path, e.message))
+        .collect();
+    BunyanError::InvalidInput(format!(
+        "Invalid {} config: {}",
+        kind,
+        details.join("; ")
+    ))
+}
+
+fn check(kind: &str, errors: Vec<ConfigFieldError>) -> Result<()> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(invalid(kind, &errors))
+    }
+}
+
+/// Validate and convert a raw repo config, failing with every field error in the message.
+pub fn parse(value: &Value) -> Result<RepoConfig> {
+    let config = deserialize(value).map_err(|e| invalid("repo", &[e]))?;
+    check("repo", check_repo(&config))?;
+    Ok(config)
+}
+
+/// Validate and convert a raw workspace config.
+pub fn parse_workspace(value: &Value) -> Result<WorkspaceConfig> {
+    let config = deserialize(value).map_err(|e| invalid("workspace", &[e]))?;
+    check("workspace", check_workspace(&config))?;
+    Ok(config)
+}
+
+/// Apply a workspace's container overrides to the repo's container config.
+pub fn merge_container(
+    base: Option<&ContainerConfig>,
+    overrides: Option<&ContainerOverrides>,
+) -> Option<ContainerConfig> {
+    let Some(overrides) = overrides else {
+        return base.cloned();
+    };
+    let mut merged = base.cloned().unwrap_or(ContainerConfig {
+        enabled: false,
+        image: None,
+        ports: None,
+        env: None,
+        shell: None,
+        dangerously_skip_permissions: false,
+    });
+
+    if let Some(image) = &overrides.image {
+        merged.image = Some(image.clone());
+    }
+    if let Some(shell) = &overrides.shell {
+        merged.shell = Some(shell.clone());
+    }
+    if let Some(skip) = overrides.dangerously_skip_permissions {
+        merged.dangerously_skip_permissions = skip;
+    }
+    if let Some(extra) = &overrides.ports {
+        let ports = merged.ports.get_or_insert_with(Vec::new);
+        for port in extra {
+            if !ports.contains(port) {
+                ports.push(port.clone());
+            }
+        }
+    }
+    if let Some(extra) = &overrides.env {
+        merged
+            .env
+            .get_or_insert_with(Default::default)
+            .extend(extra.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+    Some(merged)
+}
+
+/// The repo config with the workspace's overrides applied. The editor falls
+/// back to `default_editor` when the workspace does not set one.
+pub fn effective(repo: &Repo, ws: &Workspace, default_editor: &str) -> EffectiveWorkspaceConfig {
+    let repo_config = repo.config.clone().unwrap_or_default();
+    let ws_config = ws.config.clone().unwrap_or_default();
+    EffectiveWorkspaceConfig {
+        container: merge_container(repo_config.container.as_ref(), ws_config.container.as_ref()),
+        scripts: repo_config.scripts,
+        editor: ws_config
+            .editor
+            .unwrap_or_else(|| default_editor.to_string()),
+    }
+}
+
+/// Like `effective`, reading the default editor from the `preferred_editor` setting.
+pub fn resolve(conn: &Connection, repo: &Repo, ws: &Workspace) -> Result<EffectiveWorkspaceConfig> {
+    let editor = settings::get_effective(conn, settings::PREFERRED_EDITOR)?;
+    Ok(effective(repo, ws, &editor.value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn paths(value: Value) -> Vec<String> {
+        validate(&value).into_iter().map(|e| e.path).collect()
+    }
+
+    #[test]
+    fn accepts_full_config() {
+        let value = json!({
+            "container": {
+                "enabled": true,
+                "image": "node:22",
+                "ports": ["3000:3000"],
+                "env": {"NODE_ENV": "development"},
+                "shell": "/bin/bash",
+                "dangerously_skip_permissions": false
+            },
+            "scripts": {
+                "setup": "make setup",
+                "run": "npm start",
+                "teardown": "make clean",
+                "post_checkout": "cp ../.env ."
+            },
+            "runScriptMode": "concurrent"
+        });
+        assert!(validate(&value).is_empty());
+
+        let config = parse(&value).unwrap();
+        assert_eq!(config.container.unwrap().image.as_deref(), Some("node:22"));
+        let scripts = config.scripts.unwrap();
+        assert_eq!(scripts.setup.as_deref(), Some("make setup"));
+        assert_eq!(scripts.teardown.as_deref(), Some("make clean"));
+        assert_eq!(scripts.post_checkout.as_deref(), Some("cp ../.env ."));
+    }
+
+    #[test]
+    fn accepts_null_and_empty_config() {
+        assert!(validate(&Value::Null).is_empty());
+        assert_eq!(parse(&json!({})).unwrap(), RepoConfig::default());
+    }
+
+    #[test]
+    fn reports_misspelled_field() {
+        let value = json!({"container": {"enabled": true, "imgae": "node:22"}});
+        assert_eq!(paths(value), vec!["container.imgae"]);
+    }
+
+    #[test]
+    fn reports_every_bad_value() {
+        let value = json!({
+            "container": {
+                "enabled": true,
+                "image": "evil/image",
+                "ports": ["3000:3000", "80:80", "nonsense"],
+                "env": {"PATH": "/tmp"},
+                "shell": "bash"
+            }
+        });
+        let mut found = paths(value);
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                "container.env.PATH",
+                "container.image",
+                "container.ports[1]",
+                "container.ports[2]",
+                "container.shell",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_wrong_types_at_their_path() {
+        assert_eq!(paths(json!({"container": {"enabled": "yes"}})), vec!["container.enabled"]);
+        assert_eq!(paths(json!({"scripts": "make setup"})), vec!["scripts"]);
+        assert_eq!(paths(json!({"extra": 1})), vec!["extra"]);
+        assert_eq!(paths(json!({"checkout": {"depth": -1}})), vec!["checkout.depth"]);
+        assert_eq!(paths(json!({"container": {"enabled": true, "ports": ["1:1", 2]}})), vec!["container.ports[1]"]);
+    }
+
+    #[test]
+    fn validates_checkout() {
+        let value = json!({
+            "checkout": {
+                "filter": "blob:none",
+                "depth": 50,
+                "sparse": ["services/api", "libs"],
+                "submodules": true,
+                "lfs": false
+            }
+        });
+        assert!(validate(&value).is_empty());
+        let checkout = parse(&value).unwrap().checkout.unwrap();
+        assert_eq!(checkout.depth, Some(50));
+        assert_eq!(checkout.sparse, vec!["services/api", "libs"]);
+        assert!(checkout.submodules && !checkout.lfs);
+
+        let value = json!({
+            "checkout": {"filter": "everything", "depth": 0, "sparse": ["../up", "src/*", "ok"]}
+        });
+        let mut found = paths(value);
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                "checkout.depth",
+                "checkout.filter",
+                "checkout.sparse[0]",
+                "checkout.sparse[1]",
+            ]
+        );
+        assert_eq!(paths(json!({"checkout": {"lfs": "yes"}})), vec!["checkout.lfs"]);
+    }
+
+    #[test]
+    fn requires_container_enabled() {
+        let errors = validate(&json!({"container": {"image": "node:22"}}));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "container");
+        assert!(errors[0].message.contains("missing field `enabled`"));
+    }
+
+    #[test]
+    fn parse_rejects_with_field_paths_in_message() {
+        let err = parse(&json!({"container": "not an object"})).unwrap_err();
+        match err {
+            BunyanError::InvalidInput(msg) => {
+                assert!(msg.starts_with("Invalid repo config: container: invalid type: string"), "{}", msg)
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn workspace_overrides_reject_enabled_and_unknown_editor() {
+        let enabled = json!({"container": {"enabled": true, "image": "python:3.12"}});
+        let found: Vec<String> = validate_workspace(&enabled).into_iter().map(|e| e.path).collect();
+        assert_eq!(found, vec!["container.enabled"]);
+
+        let editor = json!({"container": {"image": "python:3.12"}, "editor": "notepad"});
+        let found: Vec<String> = validate_workspace(&editor).into_iter().map(|e| e.path).collect();
+        assert_eq!(found, vec!["editor"]);
+    }
+
+    #[test]
+    fn merge_container_applies_overrides() {
+        let base = ContainerConfig {
+            enabled: true,
+            image: Some("node:22".to_string()),
+            ports: Some(vec!["3000:3000".to_string()]),
+            env: Some([("NODE_ENV".to_string(), "development".to_string())].into()),
+            shell: None,
+            dangerously_skip_permissions: false,
+        };
+        let overrides = ContainerOverrides {
+            image: Some("node:20".to_string()),
+            ports: Some(vec!["3000:3000".to_string(), "9229:9229".to_string()]),
+            env: Some([("DEBUG".to_string(), "1".to_string())].into()),
+            shell: None,
+            dangerously_skip_permissions: Some(true),
+        };
+
+        let merged = merge_container(Some(&base), Some(&overrides)).unwrap();
+        assert!(merged.enabled);
+        assert_eq!(merged.image.as_deref(), Some("node:20"));
+        assert_eq!(merged.ports.unwrap(), vec!["3000:3000", "9229:9229"]);
+        let env = merged.env.unwrap();
+        assert_eq!(env.get("NODE_ENV").map(String::as_str), Some("development"));
+        assert_eq!(env.get("DEBUG").map(String::as_str), Some("1"));
+        assert!(merged.dangerously_skip_permissions);
+    }
+
+    #[test]
+    fn merge_container_without_overrides_keeps_repo_config() {
+        assert!(merge_container(None, None).is_none());
+        let overrides = ContainerOverrides {
+            image: Some("python:3.12".to_string()),
+            ..Default::default()
+        };
+        let merged = merge_container(None, Some(&overrides)).unwrap();
+        assert_eq!(merged.image.as_deref(), Some("python:3.12"));
+        assert!(!merged.enabled);
+    }
+}
@@ -0,0 +1,250 @@
+use chrono::SecondsFormat;
+use rusqlite::{params, Connection};
+
+use crate::error::{BunyanError, Result};
+use crate::models::{Event, EventKind};
+
+const DEFAULT_LIMIT: u32 = 100;
+
+/// Timestamps are stored in a fixed-width UTC format so they compare correctly as strings.
+fn format_ts(ts: chrono::DateTime<chrono::Utc>) -> String {
+    ts.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+fn row_to_event(row: &rusqlite::Row) -> rusqlite::Result<Event> {
+    let kind_str: String = row.get(1)?;
+    Ok(Event {
+        id: row.get(0)?,
+        kind: EventKind::from_db(&kind_str).map_err(|_| rusqlite::Error::InvalidQuery)?,
+        repo_id: row.get(2)?,
+        workspace_id: row.get(3)?,
+        message: row.get(4)?,
+        created_at: row.get(5)?,
+    })
+}
+
+#[derive(Debug, Default)]
+pub struct EventFilter {
+    pub repo_id: Option<String>,
+    pub workspace_id: Option<String>,
+    /// RFC 3339 timestamp; only events at or after this time are returned
+    pub since: Option<String>,
+    pub kind: Option<EventKind>,
+    pub limit: Option<u32>,
+}
+
+pub fn record(
+    conn: &Connection,
+    kind: EventKind,
+    repo_id: Option<&str>,
+    workspace_id: Option<&str>,
+    message: Option<&str>,
+) -> Result<Event> {
+    conn.execute(
+        "INSERT INTO events (kind, repo_id, workspace_id, message, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            kind.as_str(),
+            repo_id,
+            workspace_id,
+            message,
+            format_ts(chrono::Utc::now())
+        ],
+    )?;
+    let id = conn.last_insert_rowid();
+    conn.query_row(
+        "SELECT id, kind, repo_id, workspace_id, message, created_at FROM events WHERE id = ?1",
+        [id],
+        row_to_event,
+    )
+    .map_err(BunyanError::from)
+}
+
+/// Record an event without failing the caller. The action being audited has
+/// already happened, so a write failure is logged rather than returned.
+pub fn audit(
+    conn: &Connection,
+    kind: EventKind,
+    repo_id: Option<&str>,
+    workspace_id: Option<&str>,
+    message: Option<&str>,
+) {
+    if let Err(e) = record(conn, kind.clone(), repo_id, workspace_id, message) {
+        eprintln!("Warning: could not record {} event: {}", kind.as_str(), e);
+    }
+}
+
+/// `audit` for async handlers: the write runs on the blocking pool.
+#[cfg(feature = "server")]
+pub async fn audit_async(
+    db: &crate::db::Database,
+    kind: EventKind,
+    repo_id: Option<&str>,
+    workspace_id: Option<&str>,
+    message: Option<&str>,
+) {
+    let repo_id = repo_id.map(str::to_string);
+    let workspace_id = workspace_id.map(str::to_string);
+    let message = message.map(str::to_string);
+    let name = kind.as_str();
+    let result = db
+        .call(move |conn| {
+            audit(conn, kind, repo_id.as_deref(), workspace_id.as_deref(), message.as_deref());
+            Ok(())
+        })
+        .await;
+    if let Err(e) = result {
+        eprintln!("Warning: could not record {} event: {}", name, e);
+    }
+}
+
+/// List events matching the filter, newest first.
+pub fn list(conn: &Connection, filter: &EventFilter) -> Result<Vec<Event>> {
+    let mut clauses: Vec<String> = Vec::new();
+    let mut values: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+    if let Some(repo_id) = &filter.repo_id {
+        values.push(Box::new(repo_id.clone()));
+        clauses.push(format!("repo_id = ?{}", values.len()));
+    }
+    if let Some(workspace_id) = &filter.workspace_id {
+        values.push(Box::new(workspace_id.clone()));
+        clauses.push(format!("workspace_id = ?{}", values.len()));
+    }
+    if let Some(since) = &filter.since {
+        let parsed = chrono::DateTime::parse_from_rfc3339(since).map_err(|e| {
+            BunyanError::InvalidInput(format!("Invalid since timestamp '{}': {}", since, e))
+        })?;
+        values.push(Box::new(format_ts(parsed.with_timezone(&chrono::Utc))));
+        clauses.push(format!("created_at >= ?{}", values.len()));
+    }
+    if let Some(kind) = &filter.kind {
+        values.push(Box::new(kind.as_str()));
+        clauses.push(format!("kind = ?{}", values.len()));
+    }
+
+    let where_sql = if clauses.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", clauses.join(" AND "))
+    };
+    values.push(Box::new(filter.limit.unwrap_or(DEFAULT_LIMIT)));
+    let sql = format!(
+        "SELECT id, kind, repo_id, workspace_id, message, created_at FROM events {} ORDER BY id DESC LIMIT ?{}",
+        where_sql,
+        values.len()
+    );
+
+    let refs: Vec<&dyn rusqlite::ToSql> = values.iter().map(|b| b.as_ref()).collect();
+    let mut stmt = conn.prepare(&sql)?;
+    let events = stmt
+        .query_map(refs.as_slice(), row_to_event)?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::schema::initialize_database;
+
+    fn test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn record_and_list_returns_newest_first() {
+        let conn = test_db();
+        record(&conn, EventKind::RepoCreated, Some("r1"), None, Some("cloned")).unwrap();
+        record(&conn, EventKind::WorkspaceCreated, Some("r1"), Some("w1"), None).unwrap();
+
+        let events = list(&conn, &EventFilter::default()).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind, EventKind::WorkspaceCreated);
+        assert_eq!(events[1].kind, EventKind::RepoCreated);
+        assert_eq!(events[1].message.as_deref(), Some("cloned"));
+    }
+
+    #[test]
+    fn list_filters_by_repo_and_workspace() {
+        let conn = test_db();
+        record(&conn, EventKind::RepoCreated, Some("r1"), None, None).unwrap();
+        record(&conn, EventKind::WorkspaceCreated, Some("r1"), Some("w1"), None).unwrap();
+        record(&conn, EventKind::WorkspaceCreated, Some("r2"), Some("w2"), None).unwrap();
+
+        let by_repo = list(
+            &conn,
+            &EventFilter {
+                repo_id: Some("r1".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(by_repo.len(), 2);
+
+        let by_ws = list(
+            &conn,
+            &EventFilter {
+                workspace_id: Some("w2".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(by_ws.len(), 1);
+        assert_eq!(by_ws[0].repo_id.as_deref(), Some("r2"));
+    }
+
+    #[test]
+    fn list_filters_by_since() {
+        let conn = test_db();
+        conn.execute(
+            "INSERT INTO events (kind, created_at) VALUES ('repo_created', '2020-01-01T00:00:00.000000Z')",
+            [],
+        )
+        .unwrap();
+        record(&conn, EventKind::RepoDeleted, None, None, None).unwrap();
+
+        let recent = list(
+            &conn,
+            &EventFilter {
+                since: Some("2024-01-01T00:00:00+00:00".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].kind, EventKind::RepoDeleted);
+    }
+
+    #[test]
+    fn list_rejects_malformed_since() {
+        let conn = test_db();
+        let result = list(
+            &conn,
+            &EventFilter {
+                since: Some("yesterday".to_string()),
+                ..Default::default()
+            },
+        );
+        assert!(matches!(result, Err(BunyanError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn list_respects_limit() {
+        let conn = test_db();
+        for _ in 0..5 {
+            record(&conn, EventKind::PaneKilled, None, Some("w1"), None).unwrap();
+        }
+        let events = list(
+            &conn,
+            &EventFilter {
+                limit: Some(3),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(events.len(), 3);
+    }
+}
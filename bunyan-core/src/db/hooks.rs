@@ -0,0 +1,155 @@
+use rusqlite::{params, Connection};
+
+use crate::error::{BunyanError, Result};
+use crate::models::{HookKind, HookRun};
+
+/// A finished hook run, before it is stored.
+#[derive(Debug, Clone)]
+pub struct NewHookRun {
+    pub workspace_id: String,
+    pub hook: HookKind,
+    pub command: String,
+    pub exit_code: Option<i32>,
+    pub output: String,
+    pub started_at: String,
+    pub finished_at: String,
+}
+
+fn row_to_run(row: &rusqlite::Row) -> rusqlite::Result<HookRun> {
+    let hook_str: String = row.get(2)?;
+    Ok(HookRun {
+        id: row.get(0)?,
+        workspace_id: row.get(1)?,
+        hook: HookKind::from_db(&hook_str).map_err(|_| rusqlite::Error::InvalidQuery)?,
+        command: row.get(3)?,
+        exit_code: row.get(4)?,
+        success: row.get(5)?,
+        output: row.get(6)?,
+        started_at: row.get(7)?,
+        finished_at: row.get(8)?,
+    })
+}
+
+const COLUMNS: &str =
+    "id, workspace_id, hook, command, exit_code, success, output, started_at, finished_at";
+
+pub fn record(conn: &Connection, run: &NewHookRun) -> Result<HookRun> {
+    conn.execute(
+        "INSERT INTO hook_runs
+            (workspace_id, hook, command, exit_code, success, output, started_at, finished_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            run.workspace_id,
+            run.hook.as_str(),
+            run.command,
+            run.exit_code,
+            run.exit_code == Some(0),
+            run.output,
+            run.started_at,
+            run.finished_at
+        ],
+    )?;
+    get(conn, &run.workspace_id, conn.last_insert_rowid())
+}
+
+pub fn get(conn: &Connection, workspace_id: &str, id: i64) -> Result<HookRun> {
+    conn.query_row(
+        &format!("SELECT {} FROM hook_runs WHERE id = ?1 AND workspace_id = ?2", COLUMNS),
+        params![id, workspace_id],
+        row_to_run,
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => {
+            BunyanError::NotFound(format!("Hook run not found: {}", id))
+        }
+        _ => BunyanError::Database(e),
+    })
+}
+
+/// List a workspace's hook runs, newest first.
+pub fn list(conn: &Connection, workspace_id: &str) -> Result<Vec<HookRun>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM hook_runs WHERE workspace_id = ?1 ORDER BY id DESC",
+        COLUMNS
+    ))?;
+    let rows = stmt
+        .query_map([workspace_id], row_to_run)?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::schema::initialize_database;
+    use crate::db::{repos, workspaces};
+    use crate::models::{BranchMode, ContainerMode, CreateRepoInput, CreateWorkspaceInput};
+
+    fn test_db() -> (Connection, String) {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        let repo = repos::create(
+            &conn,
+            CreateRepoInput {
+                name: "app".to_string(),
+                remote_url: "git@github.com:org/app.git".to_string(),
+                root_path: "/repos/app".to_string(),
+                default_branch: "main".to_string(),
+                remote: "origin".to_string(),
+                display_order: 0,
+                config: None,
+                adopt: false,
+            },
+        )
+        .unwrap();
+        let ws = workspaces::create(
+            &conn,
+            CreateWorkspaceInput {
+                repository_id: repo.id,
+                directory_name: "feature".to_string(),
+                branch: "feature".to_string(),
+                container_mode: ContainerMode::Local,
+                branch_mode: BranchMode::New,
+                base_ref: None,
+                config: None,
+            },
+        )
+        .unwrap();
+        (conn, ws.id)
+    }
+
+    fn run(workspace_id: &str, hook: HookKind, exit_code: Option<i32>) -> NewHookRun {
+        NewHookRun {
+            workspace_id: workspace_id.to_string(),
+            hook,
+            command: "make setup".to_string(),
+            exit_code,
+            output: "done\n".to_string(),
+            started_at: "2026-01-01T00:00:00Z".to_string(),
+            finished_at: "2026-01-01T00:00:01Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn record_and_list_newest_first() {
+        let (conn, ws_id) = test_db();
+        let setup = record(&conn, &run(&ws_id, HookKind::Setup, Some(0))).unwrap();
+        assert!(setup.success);
+        let teardown = record(&conn, &run(&ws_id, HookKind::Teardown, None)).unwrap();
+        assert!(!teardown.success);
+        assert_eq!(teardown.exit_code, None);
+
+        let runs = list(&conn, &ws_id).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].hook, HookKind::Teardown);
+        assert_eq!(runs[1].output, "done\n");
+    }
+
+    #[test]
+    fn get_is_scoped_to_the_workspace() {
+        let (conn, ws_id) = test_db();
+        let setup = record(&conn, &run(&ws_id, HookKind::Setup, Some(1))).unwrap();
+        assert_eq!(get(&conn, &ws_id, setup.id).unwrap().exit_code, Some(1));
+        assert!(matches!(get(&conn, "other", setup.id), Err(BunyanError::NotFound(_))));
+    }
+}
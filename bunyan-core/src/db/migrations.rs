@@ -0,0 +1,364 @@
+use rusqlite::{params, Connection};
+
+use crate::error::{BunyanError, Result};
+use crate::models::MigrationStatus;
+
+/// A single step of a migration: either a raw SQL batch or a function for
+/// changes that need to inspect the existing schema first.
+pub enum Step {
+    Sql(&'static str),
+    Func(fn(&Connection) -> Result<()>),
+}
+
+/// A numbered schema migration. Versions must be strictly increasing.
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub step: Step,
+}
+
+/// All known migrations, in the order they are applied.
+/// Append new migrations to the end; never edit or reorder applied ones.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial_schema",
+        step: Step::Sql(
+            "CREATE TABLE IF NOT EXISTS repos (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                remote_url TEXT NOT NULL,
+                default_branch TEXT NOT NULL DEFAULT 'main',
+                root_path TEXT NOT NULL,
+                remote TEXT NOT NULL DEFAULT 'origin',
+                display_order INTEGER NOT NULL DEFAULT 0,
+                config TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_repos_display_order ON repos(display_order);
+            CREATE TABLE IF NOT EXISTS workspaces (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                directory_name TEXT NOT NULL,
+                branch TEXT NOT NULL,
+                state TEXT NOT NULL DEFAULT 'ready',
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY(repository_id) REFERENCES repos(id) ON DELETE CASCADE
+            );
+            CREATE INDEX IF NOT EXISTS idx_workspaces_repository_id ON workspaces(repository_id);
+            CREATE INDEX IF NOT EXISTS idx_workspaces_state ON workspaces(state);
+            CREATE TABLE IF NOT EXISTS settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );",
+        ),
+    },
+    Migration {
+        version: 2,
+        name: "workspace_container_columns",
+        step: Step::Func(add_workspace_container_columns),
+    },
+    Migration {
+        version: 3,
+        name: "workspace_transitions",
+        step: Step::Sql(
+            "ALTER TABLE workspaces ADD COLUMN state_reason TEXT;
+            CREATE TABLE workspace_transitions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                workspace_id TEXT NOT NULL,
+                from_state TEXT,
+                to_state TEXT NOT NULL,
+                reason TEXT,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+            );
+            CREATE INDEX idx_workspace_transitions_workspace_id
+                ON workspace_transitions(workspace_id);",
+        ),
+    },
+    Migration {
+        version: 4,
+        name: "events",
+        // No foreign keys: events must outlive the repos and workspaces they describe
+        step: Step::Sql(
+            "CREATE TABLE events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                kind TEXT NOT NULL,
+                repo_id TEXT,
+                workspace_id TEXT,
+                message TEXT,
+                created_at TEXT NOT NULL
+            );
+            CREATE INDEX idx_events_repo_id ON events(repo_id);
+            CREATE INDEX idx_events_workspace_id ON events(workspace_id);
+            CREATE INDEX idx_events_created_at ON events(created_at);",
+        ),
+    },
+    Migration {
+        version: 5,
+        name: "workspace_metadata_and_labels",
+        step: Step::Sql(
+            "CREATE TABLE workspace_metadata (
+                workspace_id TEXT PRIMARY KEY,
+                description TEXT,
+                notes TEXT,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+            );
+            CREATE TABLE workspace_labels (
+                workspace_id TEXT NOT NULL,
+                label TEXT NOT NULL,
+                PRIMARY KEY(workspace_id, label),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+            );
+            CREATE INDEX idx_workspace_labels_label ON workspace_labels(label);",
+        ),
+    },
+    Migration {
+        version: 6,
+        name: "workspace_config",
+        step: Step::Sql("ALTER TABLE workspace_metadata ADD COLUMN config TEXT"),
+    },
+    Migration {
+        version: 7,
+        name: "workspace_branch_cleanup",
+        step: Step::Sql("ALTER TABLE workspaces ADD COLUMN branch_cleanup TEXT"),
+    },
+    Migration {
+        version: 8,
+        name: "workspace_pull_request",
+        step: Step::Sql("ALTER TABLE workspaces ADD COLUMN pull_request TEXT"),
+    },
+    Migration {
+        version: 9,
+        name: "workspace_warnings",
+        step: Step::Sql("ALTER TABLE workspaces ADD COLUMN warnings TEXT"),
+    },
+    Migration {
+        version: 10,
+        name: "hook_runs",
+        step: Step::Sql(
+            "CREATE TABLE hook_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                workspace_id TEXT NOT NULL,
+                hook TEXT NOT NULL,
+                command TEXT NOT NULL,
+                exit_code INTEGER,
+                success INTEGER NOT NULL,
+                output TEXT NOT NULL,
+                started_at TEXT NOT NULL,
+                finished_at TEXT NOT NULL,
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+            );
+            CREATE INDEX idx_hook_runs_workspace_id ON hook_runs(workspace_id);",
+        ),
+    },
+];
+
+/// Databases created before versioned migrations may already have these
+/// columns, so only add the ones that are missing.
+fn add_workspace_container_columns(conn: &Connection) -> Result<()> {
+    if !column_exists(conn, "workspaces", "container_mode")? {
+        conn.execute_batch(
+            "ALTER TABLE workspaces ADD COLUMN container_mode TEXT NOT NULL DEFAULT 'local'",
+        )?;
+    }
+    if !column_exists(conn, "workspaces", "container_id")? {
+        conn.execute_batch("ALTER TABLE workspaces ADD COLUMN container_id TEXT")?;
+    }
+    Ok(())
+}
+
+/// Check whether a table has a column with the given name.
+pub fn column_exists(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let names = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(names.iter().any(|n| n == column))
+}
+
+fn ensure_migrations_table(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        )",
+    )?;
+    Ok(())
+}
+
+fn applied_versions(conn: &Connection) -> Result<Vec<(i64, String)>> {
+    let mut stmt =
+        conn.prepare("SELECT version, applied_at FROM schema_migrations ORDER BY version ASC")?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Apply all pending migrations. Returns the versions that were applied.
+pub fn run(conn: &Connection) -> Result<Vec<i64>> {
+    run_migrations(conn, MIGRATIONS)
+}
+
+fn run_migrations(conn: &Connection, migrations: &[Migration]) -> Result<Vec<i64>> {
+    ensure_migrations_table(conn)?;
+
+    let applied = applied_versions(conn)?;
+    let latest_known = migrations.last().map(|m| m.version).unwrap_or(0);
+    if let Some((newest, _)) = applied.last() {
+        if *newest > latest_known {
+            return Err(BunyanError::Migration(format!(
+                "Database schema version {} is newer than this build supports ({})",
+                newest, latest_known
+            )));
+        }
+    }
+
+    let mut ran = Vec::new();
+    for migration in migrations {
+        if applied.iter().any(|(v, _)| *v == migration.version) {
+            continue;
+        }
+
+        let tx = conn.unchecked_transaction()?;
+        let result = match &migration.step {
+            Step::Sql(sql) => tx.execute_batch(sql).map_err(BunyanError::from),
+            Step::Func(f) => f(&tx),
+        };
+        result.map_err(|e| {
+            BunyanError::Migration(format!(
+                "Migration {} ({}) failed: {}",
+                migration.version, migration.name, e
+            ))
+        })?;
+        tx.execute(
+            "INSERT INTO schema_migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+            params![
+                migration.version,
+                migration.name,
+                chrono::Utc::now().to_rfc3339()
+            ],
+        )?;
+        tx.commit()?;
+
+        ran.push(migration.version);
+    }
+
+    Ok(ran)
+}
+
+/// Report every known migration and whether it has been applied.
+pub fn status(conn: &Connection) -> Result<Vec<MigrationStatus>> {
+    ensure_migrations_table(conn)?;
+    let applied = applied_versions(conn)?;
+
+    Ok(MIGRATIONS
+        .iter()
+        .map(|m| MigrationStatus {
+            version: m.version,
+            name: m.name.to_string(),
+            applied_at: applied
+                .iter()
+                .find(|(v, _)| *v == m.version)
+                .map(|(_, at)| at.clone()),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_are_strictly_increasing() {
+        for pair in MIGRATIONS.windows(2) {
+            assert!(pair[0].version < pair[1].version);
+        }
+    }
+
+    #[test]
+    fn run_applies_all_migrations_once() {
+        let conn = Connection::open_in_memory().unwrap();
+        let ran = run(&conn).unwrap();
+        assert_eq!(ran.len(), MIGRATIONS.len());
+
+        let second = run(&conn).unwrap();
+        assert!(second.is_empty());
+    }
+
+    #[test]
+    fn status_reports_pending_then_applied() {
+        let conn = Connection::open_in_memory().unwrap();
+        let before = status(&conn).unwrap();
+        assert!(before.iter().all(|s| s.applied_at.is_none()));
+
+        run(&conn).unwrap();
+        let after = status(&conn).unwrap();
+        assert_eq!(after.len(), MIGRATIONS.len());
+        assert!(after.iter().all(|s| s.applied_at.is_some()));
+    }
+
+    #[test]
+    fn legacy_database_with_container_columns_upgrades_cleanly() {
+        let conn = Connection::open_in_memory().unwrap();
+        // Schema as written by the pre-migration initializer
+        conn.execute_batch(
+            "CREATE TABLE repos (id TEXT PRIMARY KEY, name TEXT NOT NULL, remote_url TEXT NOT NULL,
+                default_branch TEXT NOT NULL DEFAULT 'main', root_path TEXT NOT NULL,
+                remote TEXT NOT NULL DEFAULT 'origin', display_order INTEGER NOT NULL DEFAULT 0,
+                config TEXT, created_at TEXT NOT NULL, updated_at TEXT NOT NULL);
+             CREATE TABLE workspaces (id TEXT PRIMARY KEY, repository_id TEXT NOT NULL,
+                directory_name TEXT NOT NULL, branch TEXT NOT NULL,
+                state TEXT NOT NULL DEFAULT 'ready', created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                container_mode TEXT NOT NULL DEFAULT 'local', container_id TEXT);",
+        )
+        .unwrap();
+
+        run(&conn).unwrap();
+        assert!(column_exists(&conn, "workspaces", "container_id").unwrap());
+    }
+
+    #[test]
+    fn failed_migration_rolls_back_and_is_not_recorded() {
+        let conn = Connection::open_in_memory().unwrap();
+        let migrations = [Migration {
+            version: 1,
+            name: "broken",
+            step: Step::Sql("CREATE TABLE half_done (id TEXT); NOT VALID SQL"),
+        }];
+
+        let result = run_migrations(&conn, &migrations);
+        assert!(matches!(result, Err(BunyanError::Migration(_))));
+
+        let tables: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE name = 'half_done'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(tables, 0);
+        assert!(applied_versions(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn newer_database_version_is_rejected() {
+        let conn = Connection::open_in_memory().unwrap();
+        run(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO schema_migrations (version, name, applied_at) VALUES (9999, 'future', 'now')",
+            [],
+        )
+        .unwrap();
+
+        assert!(matches!(run(&conn), Err(BunyanError::Migration(_))));
+    }
+}
@@ -1,6 +1,11 @@
 pub mod schema;
+pub mod migrations;
 pub mod repos;
 pub mod settings;
 pub mod workspaces;
+pub mod events;
+pub mod hooks;
+pub mod pool;
 
+pub use pool::{Database, DbConnection};
 pub use schema::initialize_database;
@@ -0,0 +1,209 @@
+use std::path::Path;
+use std::time::Duration;
+
+use r2d2::{Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::Connection;
+
+use crate::db::schema;
+use crate::error::{BunyanError, Result};
+
+pub type DbConnection = PooledConnection<SqliteConnectionManager>;
+
+const MAX_CONNECTIONS: u32 = 8;
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A pool of SQLite connections. With the database in WAL mode, readers run
+/// concurrently with a writer, and writers queue on SQLite's busy timeout
+/// instead of a process-wide lock. A panic while a connection is checked out
+/// only loses that connection's open transaction; it cannot poison the pool.
+#[derive(Clone)]
+pub struct Database {
+    pool: Pool<SqliteConnectionManager>,
+}
+
+fn configure(conn: &mut Connection) -> rusqlite::Result<()> {
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    conn.execute_batch("PRAGMA foreign_keys = ON")
+}
+
+fn pool_error(err: r2d2::Error) -> BunyanError {
+    BunyanError::DatabaseUnavailable(err.to_string())
+}
+
+impl Database {
+    /// Open (creating if needed) the database file at `path` and apply pending migrations.
+    pub fn open(path: &Path) -> Result<Self> {
+        let manager = SqliteConnectionManager::file(path).with_init(configure);
+        Self::build(manager, Pool::builder().max_size(MAX_CONNECTIONS))
+    }
+
+    /// Open a private in-memory database shared by every connection in the pool.
+    pub fn open_in_memory() -> Result<Self> {
+        let uri = format!(
+            "file:bunyan-{}?mode=memory&cache=shared",
+            uuid::Uuid::new_v4()
+        );
+        let manager = SqliteConnectionManager::file(uri).with_init(configure);
+        // An in-memory database is dropped with its last connection, so never reap idle ones
+        Self::build(
+            manager,
+            Pool::builder()
+                .max_size(MAX_CONNECTIONS)
+                .min_idle(Some(1))
+                .idle_timeout(None)
+                .max_lifetime(None),
+        )
+    }
+
+    fn build(
+        manager: SqliteConnectionManager,
+        builder: r2d2::Builder<SqliteConnectionManager>,
+    ) -> Result<Self> {
+        let pool = builder.build(manager).map_err(pool_error)?;
+        let db = Self { pool };
+        let conn = db.get()?;
+        schema::initialize_database(&conn)?;
+        drop(conn);
+        Ok(db)
+    }
+
+    /// Check out a connection, waiting for one to free up if the pool is exhausted.
+    pub fn get(&self) -> Result<DbConnection> {
+        self.pool.get().map_err(pool_error)
+    }
+
+    /// Run `f` with a pooled connection on the blocking thread pool, so async
+    /// handlers never block the runtime on SQLite.
+    #[cfg(feature = "server")]
+    pub async fn call<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&Connection) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.call_mut(move |conn| f(conn)).await
+    }
+
+    /// Like [`Database::call`], but hands `f` a mutable connection so it can
+    /// open a transaction with an explicit behavior.
+    #[cfg(feature = "server")]
+    pub async fn call_mut<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let db = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = db.get()?;
+            f(&mut conn)
+        })
+        .await
+        .map_err(|e| BunyanError::Process(format!("Database task failed: {}", e)))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_memory_database_is_shared_between_connections() {
+        let db = Database::open_in_memory().unwrap();
+        db.get()
+            .unwrap()
+            .execute(
+                "INSERT INTO settings (key, value, created_at, updated_at) VALUES ('k', 'v', 'now', 'now')",
+                [],
+            )
+            .unwrap();
+
+        let a = db.get().unwrap();
+        let b = db.get().unwrap();
+        for conn in [&a, &b] {
+            let value: String = conn
+                .query_row("SELECT value FROM settings WHERE key = 'k'", [], |row| row.get(0))
+                .unwrap();
+            assert_eq!(value, "v");
+        }
+    }
+
+    #[test]
+    fn in_memory_databases_are_isolated() {
+        let first = Database::open_in_memory().unwrap();
+        let second = Database::open_in_memory().unwrap();
+        first
+            .get()
+            .unwrap()
+            .execute(
+                "INSERT INTO settings (key, value, created_at, updated_at) VALUES ('k', 'v', 'now', 'now')",
+                [],
+            )
+            .unwrap();
+
+        let count: i64 = second
+            .get()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM settings", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn panic_while_holding_a_connection_does_not_poison_the_pool() {
+        let db = Database::open_in_memory().unwrap();
+        let cloned = db.clone();
+        let result = std::thread::spawn(move || {
+            let conn = cloned.get().unwrap();
+            let tx = conn.unchecked_transaction().unwrap();
+            tx.execute(
+                "INSERT INTO settings (key, value, created_at, updated_at) VALUES ('k', 'v', 'now', 'now')",
+                [],
+            )
+            .unwrap();
+            panic!("handler panicked mid-transaction");
+        })
+        .join();
+        assert!(result.is_err());
+
+        let count: i64 = db
+            .get()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM settings", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn file_database_allows_concurrent_readers() {
+        let dir = std::env::temp_dir().join(format!("bunyan-pool-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let db = Database::open(&dir.join("bunyan.db")).unwrap();
+
+        let mode: String = db
+            .get()
+            .unwrap()
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(mode, "wal");
+
+        let writer = db.get().unwrap();
+        let tx = writer.unchecked_transaction().unwrap();
+        tx.execute(
+            "INSERT INTO settings (key, value, created_at, updated_at) VALUES ('k', 'v', 'now', 'now')",
+            [],
+        )
+        .unwrap();
+
+        // A reader sees the last committed state while the write is still open
+        let count: i64 = db
+            .get()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM settings", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0);
+
+        tx.commit().unwrap();
+        drop(writer);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
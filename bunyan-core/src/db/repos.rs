@@ -1,14 +1,21 @@
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, TransactionBehavior};
 use uuid::Uuid;
 
 use crate::error::{BunyanError, Result};
-use crate::models::{CreateRepoInput, Repo, UpdateRepoInput};
+use crate::models::{CreateRepoInput, Repo, RepoConfig, UpdateRepoInput};
 
 fn now() -> String {
     chrono::Utc::now().to_rfc3339()
 }
 
 fn row_to_repo(row: &rusqlite::Row) -> rusqlite::Result<Repo> {
+    let (config, config_error) = match row.get::<_, Option<String>>(7)? {
+        Some(raw) => match parse_stored_config(&raw) {
+            Ok(config) => (Some(config), None),
+            Err(e) => (None, Some(e)),
+        },
+        None => (None, None),
+    };
     Ok(Repo {
         id: row.get(0)?,
         name: row.get(1)?,
@@ -17,14 +24,25 @@ fn row_to_repo(row: &rusqlite::Row) -> rusqlite::Result<Repo> {
         root_path: row.get(4)?,
         remote: row.get(5)?,
         display_order: row.get(6)?,
-        config: row
-            .get::<_, Option<String>>(7)?
-            .and_then(|s| serde_json::from_str(&s).ok()),
+        config,
+        config_error,
         created_at: row.get(8)?,
         updated_at: row.get(9)?,
     })
 }
 
+/// Configs are validated on write, so a stored config that no longer parses
+/// predates validation or a schema change. It is left in place for
+/// `get_raw_config`, and the reason is reported as `Repo.config_error` so
+/// the repo doesn't silently lose its container settings.
+fn parse_stored_config(raw: &str) -> std::result::Result<RepoConfig, String> {
+    let value: serde_json::Value = serde_json::from_str(raw).map_err(|e| format!("Invalid repo config: {}", e))?;
+    crate::config::parse(&value).map_err(|e| match e {
+        BunyanError::InvalidInput(msg) => msg,
+        other => other.to_string(),
+    })
+}
+
 const SELECT_COLS: &str =
     "id, name, remote_url, default_branch, root_path, remote, display_order, config, created_at, updated_at";
 
@@ -35,7 +53,7 @@ pub fn list(conn: &Connection) -> Result<Vec<Repo>> {
     );
     let mut stmt = conn.prepare(&sql)?;
     let repos = stmt
-        .query_map([], |row| row_to_repo(row))?
+        .query_map([], row_to_repo)?
         .collect::<std::result::Result<Vec<_>, _>>()?;
     Ok(repos)
 }
@@ -43,7 +61,7 @@ pub fn list(conn: &Connection) -> Result<Vec<Repo>> {
 pub fn get(conn: &Connection, id: &str) -> Result<Repo> {
     let sql = format!("SELECT {} FROM repos WHERE id = ?1", SELECT_COLS);
     let mut stmt = conn.prepare(&sql)?;
-    stmt.query_row([id], |row| row_to_repo(row))
+    stmt.query_row([id], row_to_repo)
         .map_err(|e| match e {
             rusqlite::Error::QueryReturnedNoRows => {
                 BunyanError::NotFound(format!("Repository not found: {}", id))
@@ -52,13 +70,26 @@ pub fn get(conn: &Connection, id: &str) -> Result<Repo> {
         })
 }
 
+/// The repo's config exactly as stored, without converting it to `RepoConfig`.
+pub fn get_raw_config(conn: &Connection, id: &str) -> Result<Option<serde_json::Value>> {
+    let raw: Option<String> = conn
+        .query_row("SELECT config FROM repos WHERE id = ?1", [id], |row| row.get(0))
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => {
+                BunyanError::NotFound(format!("Repository not found: {}", id))
+            }
+            _ => BunyanError::Database(e),
+        })?;
+    Ok(raw.and_then(|s| serde_json::from_str(&s).ok()))
+}
+
 pub fn create(conn: &Connection, input: CreateRepoInput) -> Result<Repo> {
     let id = Uuid::new_v4().to_string();
     let ts = now();
     let config_json = input
         .config
         .as_ref()
-        .map(|v| serde_json::to_string(v))
+        .map(serde_json::to_string)
         .transpose()?;
 
     conn.execute(
@@ -124,6 +155,58 @@ pub fn update(conn: &Connection, input: UpdateRepoInput) -> Result<Repo> {
     get(conn, &input.id)
 }
 
+/// Rewrite `display_order` so repos appear in the order of `ids`. The list
+/// must name every repo exactly once; a repo missing from it means the
+/// caller's view is stale, which is reported as a conflict.
+pub fn reorder(conn: &mut Connection, ids: &[String]) -> Result<Vec<Repo>> {
+    let mut seen = std::collections::HashSet::new();
+    if let Some(dup) = ids.iter().find(|id| !seen.insert(id.as_str())) {
+        return Err(BunyanError::InvalidInput(format!(
+            "Repository listed more than once: {}",
+            dup
+        )));
+    }
+
+    // Take the write lock before reading, so a concurrent writer cannot slip in
+    // between the SELECT and the UPDATEs and fail the upgrade with SQLITE_BUSY
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let existing: Vec<String> = {
+        let mut stmt = tx.prepare("SELECT id FROM repos")?;
+        let rows = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        rows
+    };
+    if let Some(unknown) = ids.iter().find(|id| !existing.contains(id)) {
+        return Err(BunyanError::NotFound(format!(
+            "Repository not found: {}",
+            unknown
+        )));
+    }
+    let missing: Vec<&str> = existing
+        .iter()
+        .filter(|id| !seen.contains(id.as_str()))
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        return Err(BunyanError::Conflict(format!(
+            "Reorder must include every repository; missing: {}",
+            missing.join(", ")
+        )));
+    }
+
+    let ts = now();
+    for (order, id) in ids.iter().enumerate() {
+        tx.execute(
+            "UPDATE repos SET display_order = ?1, updated_at = ?2 WHERE id = ?3",
+            params![order as i32, ts, id],
+        )?;
+    }
+    tx.commit()?;
+
+    list(conn)
+}
+
 pub fn delete(conn: &Connection, id: &str) -> Result<()> {
     let affected = conn.execute("DELETE FROM repos WHERE id = ?1", [id])?;
     if affected == 0 {
@@ -155,6 +238,7 @@ mod tests {
             remote: "origin".to_string(),
             display_order: 0,
             config: None,
+            adopt: false,
         }
     }
 
@@ -253,10 +337,31 @@ mod tests {
         input.config = Some(config.clone());
         let created = create(&conn, input).unwrap();
 
-        assert_eq!(created.config.unwrap(), config);
+        assert_eq!(serde_json::to_value(created.config.unwrap()).unwrap(), config);
 
         let fetched = get(&conn, &created.id).unwrap();
-        assert_eq!(fetched.config.unwrap(), config);
+        assert_eq!(serde_json::to_value(fetched.config.unwrap()).unwrap(), config);
+        assert_eq!(get_raw_config(&conn, &created.id).unwrap(), Some(config));
+    }
+
+    #[test]
+    fn invalid_stored_config_is_reported_and_kept() {
+        let conn = test_db();
+        let repo = create(&conn, sample_input("legacy")).unwrap();
+        conn.execute(
+            "UPDATE repos SET config = '{\"container\": \"not an object\"}' WHERE id = ?1",
+            [&repo.id],
+        )
+        .unwrap();
+
+        let fetched = get(&conn, &repo.id).unwrap();
+        assert!(fetched.config.is_none());
+        let error = fetched.config_error.unwrap();
+        assert!(error.starts_with("Invalid repo config: container: invalid type"), "{}", error);
+        assert_eq!(
+            get_raw_config(&conn, &repo.id).unwrap(),
+            Some(serde_json::json!({"container": "not an object"}))
+        );
     }
 
     #[test]
@@ -283,4 +388,38 @@ mod tests {
             .unwrap();
         assert_eq!(count, 0);
     }
+
+    #[test]
+    fn reorder_rewrites_display_order() {
+        let mut conn = test_db();
+        let a = create(&conn, sample_input("alpha")).unwrap();
+        let b = create(&conn, sample_input("beta")).unwrap();
+        let c = create(&conn, sample_input("gamma")).unwrap();
+
+        let ids = vec![c.id.clone(), a.id.clone(), b.id.clone()];
+        let repos = reorder(&mut conn, &ids).unwrap();
+        let names: Vec<&str> = repos.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["gamma", "alpha", "beta"]);
+        let orders: Vec<i32> = repos.iter().map(|r| r.display_order).collect();
+        assert_eq!(orders, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn reorder_rejects_duplicates_unknown_and_missing_ids() {
+        let mut conn = test_db();
+        let a = create(&conn, sample_input("alpha")).unwrap();
+        let b = create(&conn, sample_input("beta")).unwrap();
+
+        let dup = reorder(&mut conn, &[a.id.clone(), a.id.clone(), b.id.clone()]);
+        assert!(matches!(dup, Err(BunyanError::InvalidInput(_))));
+
+        let unknown = reorder(&mut conn, &[a.id.clone(), b.id.clone(), "nope".to_string()]);
+        assert!(matches!(unknown, Err(BunyanError::NotFound(_))));
+
+        let missing = reorder(&mut conn, std::slice::from_ref(&b.id));
+        assert!(matches!(missing, Err(BunyanError::Conflict(_))));
+
+        // Nothing was written by the failed attempts
+        assert_eq!(get(&conn, &b.id).unwrap().display_order, 0);
+    }
 }
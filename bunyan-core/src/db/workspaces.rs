@@ -1,81 +1,334 @@
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension, Transaction, TransactionBehavior};
 use uuid::Uuid;
 
 use crate::error::{BunyanError, Result};
-use crate::models::{ContainerMode, CreateWorkspaceInput, Workspace, WorkspaceState};
+use crate::models::{
+    BranchCleanup, ContainerMode, CreateWorkspaceInput, PullRequest, UpdateWorkspaceInput,
+    Workspace, WorkspaceConfig, WorkspaceState, WorkspaceTransition, WorkspaceWarning,
+};
 
 fn now() -> String {
     chrono::Utc::now().to_rfc3339()
 }
 
+/// Open an IMMEDIATE transaction, taking the write lock up front. A deferred
+/// transaction that reads before writing can fail its lock upgrade with
+/// SQLITE_BUSY in WAL mode instead of waiting on the busy timeout.
+fn write_transaction(conn: &Connection) -> Result<Transaction<'_>> {
+    Ok(Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?)
+}
+
 fn row_to_workspace(row: &rusqlite::Row) -> rusqlite::Result<Workspace> {
     let state_str: String = row.get(4)?;
-    let container_mode_str: String = row.get(5)?;
+    let container_mode_str: String = row.get(6)?;
+    let state = WorkspaceState::from_db(&state_str).map_err(|_| rusqlite::Error::InvalidQuery)?;
+    // Rows archived before transitions were recorded fall back to their last update
+    let archived_at = match state {
+        WorkspaceState::Archived => Some(match row.get::<_, Option<String>>(17)? {
+            Some(at) => at,
+            None => row.get(9)?,
+        }),
+        _ => None,
+    };
     Ok(Workspace {
         id: row.get(0)?,
         repository_id: row.get(1)?,
         directory_name: row.get(2)?,
         branch: row.get(3)?,
-        state: WorkspaceState::from_db(&state_str)
-            .map_err(|_| rusqlite::Error::InvalidQuery)?,
+        state,
+        state_reason: row.get(5)?,
         container_mode: ContainerMode::from_db(&container_mode_str)
             .map_err(|_| rusqlite::Error::InvalidQuery)?,
-        container_id: row.get(6)?,
-        created_at: row.get(7)?,
-        updated_at: row.get(8)?,
+        container_id: row.get(7)?,
+        created_at: row.get(8)?,
+        updated_at: row.get(9)?,
+        archived_at,
+        description: row.get(10)?,
+        notes: row.get(11)?,
+        labels: split_labels(row.get(12)?),
+        config: json_column(row, 13)?,
+        branch_cleanup: json_column(row, 14)?,
+        pull_request: json_column(row, 15)?,
+        warnings: json_column(row, 16)?.unwrap_or_default(),
     })
 }
 
+/// Parse a nullable JSON column. Malformed JSON fails the row rather than
+/// reading as unset; it surfaces as `BunyanError::Serialization`.
+fn json_column<T: serde::de::DeserializeOwned>(row: &rusqlite::Row, idx: usize) -> rusqlite::Result<Option<T>> {
+    row.get::<_, Option<String>>(idx)?
+        .map(|s| {
+            serde_json::from_str(&s).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(idx, rusqlite::types::Type::Text, Box::new(e))
+            })
+        })
+        .transpose()
+}
+
+/// Labels are aggregated with the ASCII unit separator, which cannot appear in a valid label.
+const LABEL_SEP: char = '\u{1f}';
+
+fn split_labels(joined: Option<String>) -> Vec<String> {
+    let mut labels: Vec<String> = joined
+        .map(|s| s.split(LABEL_SEP).map(|l| l.to_string()).collect())
+        .unwrap_or_default();
+    labels.sort();
+    labels
+}
+
 const SELECT_COLS: &str =
-    "id, repository_id, directory_name, branch, state, container_mode, container_id, created_at, updated_at";
+    "w.id, w.repository_id, w.directory_name, w.branch, w.state, w.state_reason, w.container_mode, w.container_id, w.created_at, w.updated_at,
+     m.description, m.notes,
+     (SELECT group_concat(l.label, char(31)) FROM workspace_labels l WHERE l.workspace_id = w.id),
+     m.config, w.branch_cleanup, w.pull_request, w.warnings,
+     (SELECT max(t.created_at) FROM workspace_transitions t WHERE t.workspace_id = w.id AND t.to_state = 'archived')";
+
+const FROM_TABLES: &str =
+    "workspaces w LEFT JOIN workspace_metadata m ON m.workspace_id = w.id";
+
+/// Criteria for `list_filtered`. Every label listed must be present on a workspace.
+#[derive(Debug, Default)]
+pub struct WorkspaceFilter {
+    pub repository_id: Option<String>,
+    pub state: Option<WorkspaceState>,
+    pub container_mode: Option<ContainerMode>,
+    pub labels: Vec<String>,
+}
+
+fn row_to_transition(row: &rusqlite::Row) -> rusqlite::Result<WorkspaceTransition> {
+    let from_str: Option<String> = row.get(2)?;
+    let to_str: String = row.get(3)?;
+    Ok(WorkspaceTransition {
+        id: row.get(0)?,
+        workspace_id: row.get(1)?,
+        from_state: from_str
+            .map(|s| WorkspaceState::from_db(&s))
+            .transpose()
+            .map_err(|_| rusqlite::Error::InvalidQuery)?,
+        to_state: WorkspaceState::from_db(&to_str).map_err(|_| rusqlite::Error::InvalidQuery)?,
+        reason: row.get(4)?,
+        created_at: row.get(5)?,
+    })
+}
+
+fn record_transition(
+    conn: &Connection,
+    id: &str,
+    from: Option<&WorkspaceState>,
+    to: &WorkspaceState,
+    reason: Option<&str>,
+    ts: &str,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO workspace_transitions (workspace_id, from_state, to_state, reason, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![id, from.map(|s| s.as_str()), to.as_str(), reason, ts],
+    )?;
+    Ok(())
+}
 
 pub fn list(conn: &Connection, repository_id: Option<&str>) -> Result<Vec<Workspace>> {
-    match repository_id {
-        Some(repo_id) => {
-            let sql = format!(
-                "SELECT {} FROM workspaces WHERE repository_id = ?1 ORDER BY created_at DESC",
-                SELECT_COLS
-            );
-            let mut stmt = conn.prepare(&sql)?;
-            let rows = stmt
-                .query_map([repo_id], |row| row_to_workspace(row))?
-                .collect::<std::result::Result<Vec<_>, _>>()?;
-            Ok(rows)
-        }
-        None => {
-            let sql = format!(
-                "SELECT {} FROM workspaces ORDER BY created_at DESC",
-                SELECT_COLS
-            );
-            let mut stmt = conn.prepare(&sql)?;
-            let rows = stmt
-                .query_map([], |row| row_to_workspace(row))?
-                .collect::<std::result::Result<Vec<_>, _>>()?;
-            Ok(rows)
-        }
+    list_filtered(
+        conn,
+        &WorkspaceFilter {
+            repository_id: repository_id.map(|s| s.to_string()),
+            ..Default::default()
+        },
+    )
+}
+
+pub fn list_filtered(conn: &Connection, filter: &WorkspaceFilter) -> Result<Vec<Workspace>> {
+    let mut clauses: Vec<String> = Vec::new();
+    let mut values: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+    if let Some(repo_id) = &filter.repository_id {
+        values.push(Box::new(repo_id.clone()));
+        clauses.push(format!("w.repository_id = ?{}", values.len()));
+    }
+    if let Some(state) = &filter.state {
+        values.push(Box::new(state.as_str()));
+        clauses.push(format!("w.state = ?{}", values.len()));
     }
+    if let Some(mode) = &filter.container_mode {
+        values.push(Box::new(mode.as_str()));
+        clauses.push(format!("w.container_mode = ?{}", values.len()));
+    }
+    for label in &filter.labels {
+        values.push(Box::new(label.clone()));
+        clauses.push(format!(
+            "EXISTS (SELECT 1 FROM workspace_labels l WHERE l.workspace_id = w.id AND l.label = ?{})",
+            values.len()
+        ));
+    }
+
+    let where_sql = if clauses.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", clauses.join(" AND "))
+    };
+    let sql = format!(
+        "SELECT {} FROM {} {} ORDER BY w.created_at DESC",
+        SELECT_COLS, FROM_TABLES, where_sql
+    );
+
+    let refs: Vec<&dyn rusqlite::ToSql> = values.iter().map(|b| b.as_ref()).collect();
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map(refs.as_slice(), row_to_workspace)?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
 }
 
 pub fn get(conn: &Connection, id: &str) -> Result<Workspace> {
-    let sql = format!("SELECT {} FROM workspaces WHERE id = ?1", SELECT_COLS);
+    let sql = format!("SELECT {} FROM {} WHERE w.id = ?1", SELECT_COLS, FROM_TABLES);
     let mut stmt = conn.prepare(&sql)?;
-    stmt.query_row([id], |row| row_to_workspace(row))
+    stmt.query_row([id], row_to_workspace)
         .map_err(|e| match e {
             rusqlite::Error::QueryReturnedNoRows => {
                 BunyanError::NotFound(format!("Workspace not found: {}", id))
             }
-            _ => BunyanError::Database(e),
+            _ => e.into(),
         })
 }
 
+/// Validate a workspace label: non-empty, at most 64 characters, and limited
+/// to letters, digits, `-`, `_`, `.`, `:` and `/`.
+pub fn validate_label(label: &str) -> Result<()> {
+    let valid = !label.is_empty()
+        && label.len() <= 64
+        && label
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | ':' | '/'));
+    if !valid {
+        return Err(BunyanError::InvalidInput(format!("Invalid label: '{}'", label)));
+    }
+    Ok(())
+}
+
+/// The JSON stored for a workspace's config overrides; None when there are none.
+fn stored_config(config: &WorkspaceConfig) -> Result<Option<String>> {
+    if *config == WorkspaceConfig::default() {
+        Ok(None)
+    } else {
+        Ok(Some(serde_json::to_string(config)?))
+    }
+}
+
+/// Update a workspace's description, notes, labels and config overrides.
+/// Only fields that are `Some` change; an empty description or notes string
+/// clears it, `labels` replaces the full label set, and an empty config
+/// clears the overrides. Container overrides are fixed once the workspace has
+/// a container, since they only apply when it is created.
+pub fn update(conn: &Connection, id: &str, input: UpdateWorkspaceInput) -> Result<Workspace> {
+    let ws = get(conn, id)?;
+    if let Some(labels) = &input.labels {
+        for label in labels {
+            validate_label(label)?;
+        }
+    }
+    let config_json = match &input.config {
+        Some(value) => {
+            let parsed = crate::config::parse_workspace(value)?;
+            let current = ws.config.as_ref().and_then(|c| c.container.as_ref());
+            if ws.container_id.is_some() && parsed.container.as_ref() != current {
+                return Err(BunyanError::Conflict(format!(
+                    "Workspace {} already has a container; its container overrides can't change",
+                    ws.directory_name
+                )));
+            }
+            Some(stored_config(&parsed)?)
+        }
+        None => None,
+    };
+
+    let ts = now();
+    let tx = write_transaction(conn)?;
+
+    if input.description.is_some() || input.notes.is_some() || config_json.is_some() {
+        tx.execute(
+            "INSERT INTO workspace_metadata (workspace_id, updated_at) VALUES (?1, ?2)
+             ON CONFLICT(workspace_id) DO NOTHING",
+            params![id, ts],
+        )?;
+        if let Some(description) = &input.description {
+            let value = Some(description.as_str()).filter(|d| !d.is_empty());
+            tx.execute(
+                "UPDATE workspace_metadata SET description = ?1, updated_at = ?2 WHERE workspace_id = ?3",
+                params![value, ts, id],
+            )?;
+        }
+        if let Some(notes) = &input.notes {
+            let value = Some(notes.as_str()).filter(|n| !n.is_empty());
+            tx.execute(
+                "UPDATE workspace_metadata SET notes = ?1, updated_at = ?2 WHERE workspace_id = ?3",
+                params![value, ts, id],
+            )?;
+        }
+        if let Some(config) = &config_json {
+            tx.execute(
+                "UPDATE workspace_metadata SET config = ?1, updated_at = ?2 WHERE workspace_id = ?3",
+                params![config, ts, id],
+            )?;
+        }
+    }
+
+    if let Some(labels) = &input.labels {
+        tx.execute("DELETE FROM workspace_labels WHERE workspace_id = ?1", [id])?;
+        for label in labels {
+            tx.execute(
+                "INSERT OR IGNORE INTO workspace_labels (workspace_id, label) VALUES (?1, ?2)",
+                params![id, label],
+            )?;
+        }
+    }
+
+    tx.execute(
+        "UPDATE workspaces SET updated_at = ?1 WHERE id = ?2",
+        params![ts, id],
+    )?;
+    tx.commit()?;
+
+    get(conn, id)
+}
+
 pub fn create(conn: &Connection, input: CreateWorkspaceInput) -> Result<Workspace> {
+    insert(conn, input, WorkspaceState::Ready)
+}
+
+/// Insert a workspace in the `creating` state, before its worktree exists.
+/// Callers move it to `ready` or `failed` with `transition` once setup finishes.
+pub fn create_pending(conn: &Connection, input: CreateWorkspaceInput) -> Result<Workspace> {
+    insert(conn, input, WorkspaceState::Creating)
+}
+
+fn insert(conn: &Connection, input: CreateWorkspaceInput, state: WorkspaceState) -> Result<Workspace> {
     // Verify the repo exists
     crate::db::repos::get(conn, &input.repository_id)?;
+    let config_json = match &input.config {
+        Some(value) => stored_config(&crate::config::parse_workspace(value)?)?,
+        None => None,
+    };
 
     let id = Uuid::new_v4().to_string();
     let ts = now();
 
-    conn.execute(
+    let tx = write_transaction(conn)?;
+    // A failed workspace keeps its directory until it is archived, so a retry
+    // under the same name would collide with what it left behind
+    let existing: Option<String> = tx
+        .query_row(
+            "SELECT state FROM workspaces WHERE repository_id = ?1 AND directory_name = ?2 AND state != 'archived'",
+            params![input.repository_id, input.directory_name],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if let Some(existing) = existing {
+        return Err(BunyanError::Conflict(format!(
+            "Workspace '{}' already exists ({}); archive it before reusing the name",
+            input.directory_name, existing
+        )));
+    }
+    tx.execute(
         "INSERT INTO workspaces (id, repository_id, directory_name, branch, state, container_mode, created_at, updated_at)
          VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
         params![
@@ -83,41 +336,139 @@ pub fn create(conn: &Connection, input: CreateWorkspaceInput) -> Result<Workspac
             input.repository_id,
             input.directory_name,
             input.branch,
-            WorkspaceState::Ready.as_str(),
+            state.as_str(),
             input.container_mode.as_str(),
             ts,
             ts,
         ],
     )?;
+    if let Some(config) = &config_json {
+        tx.execute(
+            "INSERT INTO workspace_metadata (workspace_id, config, updated_at) VALUES (?1, ?2, ?3)",
+            params![id, config, ts],
+        )?;
+    }
+    record_transition(&tx, &id, None, &state, None, &ts)?;
+    tx.commit()?;
 
     get(conn, &id)
 }
 
-pub fn archive(conn: &Connection, id: &str) -> Result<Workspace> {
+/// Move a workspace to a new lifecycle state, recording the transition.
+/// Returns `Conflict` if the move is not allowed from the current state.
+pub fn transition(
+    conn: &Connection,
+    id: &str,
+    to: WorkspaceState,
+    reason: Option<&str>,
+) -> Result<Workspace> {
+    let ws = get(conn, id)?;
+    if !ws.state.can_transition_to(&to) {
+        return Err(BunyanError::Conflict(format!(
+            "Workspace {} cannot move from {} to {}",
+            id,
+            ws.state.as_str(),
+            to.as_str()
+        )));
+    }
+
     let ts = now();
-    let affected = conn.execute(
-        "UPDATE workspaces SET state = ?1, updated_at = ?2 WHERE id = ?3 AND state = ?4",
-        params![
-            WorkspaceState::Archived.as_str(),
-            ts,
+    let tx = write_transaction(conn)?;
+    move_state(&tx, id, &ws.state, &to, reason, &ts)?;
+    tx.commit()?;
+
+    get(conn, id)
+}
+
+/// Change a workspace's state only if it is still `from`, so two requests
+/// that both saw the same state can't both move it. The loser gets `Conflict`.
+fn move_state(
+    conn: &Connection,
+    id: &str,
+    from: &WorkspaceState,
+    to: &WorkspaceState,
+    reason: Option<&str>,
+    ts: &str,
+) -> Result<()> {
+    let changed = conn.execute(
+        "UPDATE workspaces SET state = ?1, state_reason = ?2, updated_at = ?3 WHERE id = ?4 AND state = ?5",
+        params![to.as_str(), reason, ts, id, from.as_str()],
+    )?;
+    if changed == 0 {
+        return Err(BunyanError::Conflict(format!(
+            "Workspace {} is no longer {}; it changed state while moving to {}",
             id,
-            WorkspaceState::Ready.as_str(),
-        ],
+            from.as_str(),
+            to.as_str()
+        )));
+    }
+    record_transition(conn, id, Some(from), to, reason, ts)
+}
+
+/// List the recorded state transitions for a workspace, oldest first.
+pub fn list_transitions(conn: &Connection, id: &str) -> Result<Vec<WorkspaceTransition>> {
+    get(conn, id)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, workspace_id, from_state, to_state, reason, created_at
+         FROM workspace_transitions WHERE workspace_id = ?1 ORDER BY id ASC",
     )?;
+    let rows = stmt
+        .query_map([id], row_to_transition)?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
 
-    if affected == 0 {
-        // Check if it exists at all vs already archived
-        let ws = get(conn, id)?;
-        if ws.state == WorkspaceState::Archived {
-            return Ok(ws);
+/// Mark a workspace archived. Already-archived workspaces are returned unchanged;
+/// workspaces not yet in `archiving` pass through it so the history stays complete.
+pub fn archive(conn: &Connection, id: &str) -> Result<Workspace> {
+    let ws = get(conn, id)?;
+    match ws.state {
+        WorkspaceState::Archived => Ok(ws),
+        WorkspaceState::Archiving => transition(conn, id, WorkspaceState::Archived, None),
+        _ => {
+            transition(conn, id, WorkspaceState::Archiving, None)?;
+            transition(conn, id, WorkspaceState::Archived, None)
         }
-        return Err(BunyanError::NotFound(format!(
-            "Workspace not found: {}",
-            id
-        )));
     }
+}
 
-    get(conn, id)
+/// Record what archiving did with the workspace's branch.
+pub fn set_branch_cleanup(conn: &Connection, id: &str, cleanup: &BranchCleanup) -> Result<()> {
+    let ts = now();
+    let changed = conn.execute(
+        "UPDATE workspaces SET branch_cleanup = ?1, updated_at = ?2 WHERE id = ?3",
+        params![serde_json::to_string(cleanup)?, ts, id],
+    )?;
+    if changed == 0 {
+        return Err(BunyanError::NotFound(format!("Workspace not found: {}", id)));
+    }
+    Ok(())
+}
+
+/// Record the pull request opened for the workspace's branch.
+pub fn set_pull_request(conn: &Connection, id: &str, pr: &PullRequest) -> Result<()> {
+    let ts = now();
+    let changed = conn.execute(
+        "UPDATE workspaces SET pull_request = ?1, updated_at = ?2 WHERE id = ?3",
+        params![serde_json::to_string(pr)?, ts, id],
+    )?;
+    if changed == 0 {
+        return Err(BunyanError::NotFound(format!("Workspace not found: {}", id)));
+    }
+    Ok(())
+}
+
+/// Record the problems hit while creating the workspace, replacing any earlier ones.
+pub fn set_warnings(conn: &Connection, id: &str, warnings: &[WorkspaceWarning]) -> Result<()> {
+    let ts = now();
+    let changed = conn.execute(
+        "UPDATE workspaces SET warnings = ?1, updated_at = ?2 WHERE id = ?3",
+        params![serde_json::to_string(warnings)?, ts, id],
+    )?;
+    if changed == 0 {
+        return Err(BunyanError::NotFound(format!("Workspace not found: {}", id)));
+    }
+    Ok(())
 }
 
 pub fn set_container_id(conn: &Connection, id: &str, container_id: &str) -> Result<()> {
@@ -131,7 +482,7 @@ pub fn set_container_id(conn: &Connection, id: &str, container_id: &str) -> Resu
 
 pub fn count_container_workspaces(conn: &Connection, repo_id: &str) -> Result<i64> {
     let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM workspaces WHERE repository_id = ?1 AND container_mode = 'container' AND state = 'ready'",
+        "SELECT COUNT(*) FROM workspaces WHERE repository_id = ?1 AND container_mode = 'container' AND state != 'archived'",
         params![repo_id],
         |row| row.get(0),
     )?;
@@ -148,7 +499,6 @@ pub fn clear_container_id(conn: &Connection, id: &str) -> Result<()> {
     Ok(())
 }
 
-#[allow(dead_code)]
 pub fn delete(conn: &Connection, id: &str) -> Result<()> {
     let affected = conn.execute("DELETE FROM workspaces WHERE id = ?1", [id])?;
     if affected == 0 {
@@ -160,12 +510,64 @@ pub fn delete(conn: &Connection, id: &str) -> Result<()> {
     Ok(())
 }
 
+/// Delete an archived workspace row. Live workspaces must be archived first.
+pub fn delete_archived(conn: &Connection, id: &str) -> Result<Workspace> {
+    let ws = get(conn, id)?;
+    if ws.state != WorkspaceState::Archived {
+        return Err(BunyanError::Conflict(format!(
+            "Workspace {} is {}; only archived workspaces can be deleted",
+            id,
+            ws.state.as_str()
+        )));
+    }
+    delete(conn, id)?;
+    Ok(ws)
+}
+
+/// Delete workspaces archived before `cutoff` (all archived workspaces when
+/// `None`). Age counts from the archive transition, so edits to an archived
+/// workspace's notes or labels don't postpone its purge. With `dry_run`,
+/// nothing is deleted.
+/// Returns the workspaces that were (or would be) removed.
+pub fn purge_archived(
+    conn: &Connection,
+    cutoff: Option<chrono::DateTime<chrono::Utc>>,
+    repository_id: Option<&str>,
+    dry_run: bool,
+) -> Result<Vec<Workspace>> {
+    let candidates: Vec<Workspace> = list(conn, repository_id)?
+        .into_iter()
+        .filter(|ws| ws.state == WorkspaceState::Archived)
+        .filter(|ws| match cutoff {
+            None => true,
+            Some(cutoff) => ws
+                .archived_at
+                .as_deref()
+                .and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok())
+                .is_some_and(|archived_at| archived_at < cutoff),
+        })
+        .collect();
+
+    if !dry_run {
+        let tx = write_transaction(conn)?;
+        for ws in &candidates {
+            delete(&tx, &ws.id)?;
+        }
+        tx.commit()?;
+    }
+
+    Ok(candidates)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::db::repos;
     use crate::db::schema::initialize_database;
-    use crate::models::{ContainerMode, CreateRepoInput};
+    use crate::models::{
+        BranchMode, BranchOutcome, ChecksState, ContainerMode, CreateRepoInput, PullRequestState,
+        WarningKind,
+    };
 
     fn test_db() -> Connection {
         let conn = Connection::open_in_memory().unwrap();
@@ -184,6 +586,7 @@ mod tests {
                 remote: "origin".to_string(),
                 display_order: 0,
                 config: None,
+                adopt: false,
             },
         )
         .unwrap()
@@ -201,6 +604,9 @@ mod tests {
                 directory_name: "lisbon".to_string(),
                 branch: "bkegley/lisbon".to_string(),
                 container_mode: ContainerMode::Local,
+                branch_mode: BranchMode::New,
+                base_ref: None,
+                config: None,
             },
         )
         .unwrap();
@@ -220,6 +626,9 @@ mod tests {
                 directory_name: "lisbon".to_string(),
                 branch: "main".to_string(),
                 container_mode: ContainerMode::Local,
+                branch_mode: BranchMode::New,
+                base_ref: None,
+                config: None,
             },
         );
         assert!(matches!(result, Err(BunyanError::NotFound(_))));
@@ -237,6 +646,9 @@ mod tests {
                 directory_name: "chicago".to_string(),
                 branch: "main".to_string(),
                 container_mode: ContainerMode::Local,
+                branch_mode: BranchMode::New,
+                base_ref: None,
+                config: None,
             },
         )
         .unwrap();
@@ -256,6 +668,9 @@ mod tests {
                 directory_name: "boston".to_string(),
                 branch: "main".to_string(),
                 container_mode: ContainerMode::Local,
+                branch_mode: BranchMode::New,
+                base_ref: None,
+                config: None,
             },
         )
         .unwrap();
@@ -268,6 +683,72 @@ mod tests {
         assert_eq!(fetched.state, WorkspaceState::Archived);
     }
 
+    #[test]
+    fn branch_cleanup_is_stored_on_the_workspace() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let ws = create_local(&conn, &repo.id, "cleanup");
+        assert_eq!(ws.branch_cleanup, None);
+
+        let cleanup = BranchCleanup {
+            local: Some(BranchOutcome::Deleted),
+            remote: Some(BranchOutcome::Failed),
+            detail: Some("remote rejected".to_string()),
+        };
+        set_branch_cleanup(&conn, &ws.id, &cleanup).unwrap();
+        assert_eq!(get(&conn, &ws.id).unwrap().branch_cleanup, Some(cleanup.clone()));
+
+        let missing = set_branch_cleanup(&conn, "nope", &cleanup);
+        assert!(matches!(missing, Err(BunyanError::NotFound(_))));
+    }
+
+    #[test]
+    fn pull_request_is_stored_on_the_workspace() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let ws = create_local(&conn, &repo.id, "pr");
+        assert_eq!(ws.pull_request, None);
+
+        let pr = PullRequest {
+            number: 42,
+            url: "https://github.com/org/frontend/pull/42".to_string(),
+            title: "Fix login".to_string(),
+            state: PullRequestState::Open,
+            head: "pr".to_string(),
+            base: "main".to_string(),
+            checks: Some(ChecksState::Pending),
+        };
+        set_pull_request(&conn, &ws.id, &pr).unwrap();
+        assert_eq!(get(&conn, &ws.id).unwrap().pull_request, Some(pr));
+    }
+
+    #[test]
+    fn malformed_stored_json_is_a_serialization_error() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let ws = create_local(&conn, &repo.id, "corrupt");
+        conn.execute("UPDATE workspaces SET pull_request = '{\"number\":' WHERE id = ?1", [&ws.id])
+            .unwrap();
+
+        assert!(matches!(get(&conn, &ws.id), Err(BunyanError::Serialization(_))));
+        assert!(matches!(list(&conn, None), Err(BunyanError::Serialization(_))));
+    }
+
+    #[test]
+    fn warnings_are_stored_on_the_workspace() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let ws = create_local(&conn, &repo.id, "lfs");
+        assert!(ws.warnings.is_empty());
+
+        let warnings = vec![WorkspaceWarning {
+            kind: WarningKind::Lfs,
+            message: "git-lfs is not installed".to_string(),
+        }];
+        set_warnings(&conn, &ws.id, &warnings).unwrap();
+        assert_eq!(get(&conn, &ws.id).unwrap().warnings, warnings);
+    }
+
     #[test]
     fn list_with_repo_filter_returns_only_that_repos_workspaces() {
         let conn = test_db();
@@ -281,6 +762,9 @@ mod tests {
                 directory_name: "ws1".to_string(),
                 branch: "main".to_string(),
                 container_mode: ContainerMode::Local,
+                branch_mode: BranchMode::New,
+                base_ref: None,
+                config: None,
             },
         )
         .unwrap();
@@ -292,6 +776,9 @@ mod tests {
                 directory_name: "ws2".to_string(),
                 branch: "main".to_string(),
                 container_mode: ContainerMode::Local,
+                branch_mode: BranchMode::New,
+                base_ref: None,
+                config: None,
             },
         )
         .unwrap();
@@ -318,6 +805,9 @@ mod tests {
                 directory_name: "ws1".to_string(),
                 branch: "main".to_string(),
                 container_mode: ContainerMode::Local,
+                branch_mode: BranchMode::New,
+                base_ref: None,
+                config: None,
             },
         )
         .unwrap();
@@ -329,6 +819,9 @@ mod tests {
                 directory_name: "ws2".to_string(),
                 branch: "main".to_string(),
                 container_mode: ContainerMode::Local,
+                branch_mode: BranchMode::New,
+                base_ref: None,
+                config: None,
             },
         )
         .unwrap();
@@ -349,6 +842,9 @@ mod tests {
                 directory_name: "denver".to_string(),
                 branch: "main".to_string(),
                 container_mode: ContainerMode::Local,
+                branch_mode: BranchMode::New,
+                base_ref: None,
+                config: None,
             },
         )
         .unwrap();
@@ -357,4 +853,418 @@ mod tests {
         let second = archive(&conn, &ws.id).unwrap();
         assert_eq!(second.state, WorkspaceState::Archived);
     }
+
+    /// Input for a local workspace on `main`; tests override fields with `..local_input(..)`.
+    fn local_input(repo_id: &str, name: &str) -> CreateWorkspaceInput {
+        CreateWorkspaceInput {
+            repository_id: repo_id.to_string(),
+            directory_name: name.to_string(),
+            branch: "main".to_string(),
+            container_mode: ContainerMode::Local,
+            branch_mode: BranchMode::New,
+            base_ref: None,
+            config: None,
+        }
+    }
+
+    fn create_local(conn: &Connection, repo_id: &str, name: &str) -> Workspace {
+        create(conn, local_input(repo_id, name)).unwrap()
+    }
+
+    #[test]
+    fn create_pending_starts_in_creating_state() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+
+        let ws = create_pending(&conn, local_input(&repo.id, "austin")).unwrap();
+
+        assert_eq!(ws.state, WorkspaceState::Creating);
+    }
+
+    #[test]
+    fn directory_name_is_taken_until_the_workspace_is_archived() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let other = create_test_repo(&conn, "backend");
+        let ws = create_local(&conn, &repo.id, "dallas");
+        transition(&conn, &ws.id, WorkspaceState::Failed, Some("setup hook exited with status 1")).unwrap();
+
+        let retry = create_pending(&conn, local_input(&repo.id, "dallas"));
+        assert!(matches!(retry, Err(BunyanError::Conflict(_))));
+        // The name is per repo
+        create_local(&conn, &other.id, "dallas");
+
+        archive(&conn, &ws.id).unwrap();
+        let reused = create_local(&conn, &repo.id, "dallas");
+        assert_eq!(reused.state, WorkspaceState::Ready);
+    }
+
+    #[test]
+    fn transition_records_reason_and_history() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let ws = create_local(&conn, &repo.id, "elpaso");
+
+        let failed = transition(&conn, &ws.id, WorkspaceState::Failed, Some("container exited")).unwrap();
+        assert_eq!(failed.state, WorkspaceState::Failed);
+        assert_eq!(failed.state_reason.as_deref(), Some("container exited"));
+
+        let history = list_transitions(&conn, &ws.id).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].from_state, None);
+        assert_eq!(history[0].to_state, WorkspaceState::Ready);
+        assert_eq!(history[1].from_state, Some(WorkspaceState::Ready));
+        assert_eq!(history[1].to_state, WorkspaceState::Failed);
+        assert_eq!(history[1].reason.as_deref(), Some("container exited"));
+    }
+
+    #[test]
+    fn illegal_transition_is_rejected() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let ws = create_local(&conn, &repo.id, "fresno");
+
+        let result = transition(&conn, &ws.id, WorkspaceState::Creating, None);
+        assert!(matches!(result, Err(BunyanError::Conflict(_))));
+
+        // State is unchanged and no transition was recorded
+        assert_eq!(get(&conn, &ws.id).unwrap().state, WorkspaceState::Ready);
+        assert_eq!(list_transitions(&conn, &ws.id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn archived_is_terminal() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let ws = create_local(&conn, &repo.id, "gary");
+
+        archive(&conn, &ws.id).unwrap();
+        let result = transition(&conn, &ws.id, WorkspaceState::Ready, None);
+        assert!(matches!(result, Err(BunyanError::Conflict(_))));
+    }
+
+    #[test]
+    fn archive_passes_through_archiving() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let ws = create_local(&conn, &repo.id, "houston");
+
+        archive(&conn, &ws.id).unwrap();
+
+        let states: Vec<WorkspaceState> = list_transitions(&conn, &ws.id)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.to_state)
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                WorkspaceState::Ready,
+                WorkspaceState::Archiving,
+                WorkspaceState::Archived
+            ]
+        );
+    }
+
+    #[test]
+    fn delete_archived_rejects_live_workspace() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let ws = create_local(&conn, &repo.id, "irvine");
+
+        let result = delete_archived(&conn, &ws.id);
+        assert!(matches!(result, Err(BunyanError::Conflict(_))));
+        assert!(get(&conn, &ws.id).is_ok());
+    }
+
+    #[test]
+    fn delete_archived_removes_row_and_history() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let ws = create_local(&conn, &repo.id, "juneau");
+        archive(&conn, &ws.id).unwrap();
+
+        delete_archived(&conn, &ws.id).unwrap();
+        assert!(matches!(get(&conn, &ws.id), Err(BunyanError::NotFound(_))));
+
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM workspace_transitions WHERE workspace_id = ?1",
+                [&ws.id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn purge_archived_respects_cutoff_and_dry_run() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let old = create_local(&conn, &repo.id, "kent");
+        let recent = create_local(&conn, &repo.id, "lima");
+        let live = create_local(&conn, &repo.id, "macon");
+        archive(&conn, &old.id).unwrap();
+        archive(&conn, &recent.id).unwrap();
+        conn.execute(
+            "UPDATE workspace_transitions SET created_at = '2020-01-01T00:00:00+00:00'
+             WHERE workspace_id = ?1 AND to_state = 'archived'",
+            [&old.id],
+        )
+        .unwrap();
+        // Editing an archived workspace doesn't restart its retention clock
+        set_labels(&conn, &old.id, &["keep"]);
+        assert_eq!(get(&conn, &old.id).unwrap().archived_at.as_deref(), Some("2020-01-01T00:00:00+00:00"));
+
+        let cutoff = Some(chrono::Utc::now() - chrono::Duration::days(7));
+
+        let preview = purge_archived(&conn, cutoff, None, true).unwrap();
+        assert_eq!(preview.len(), 1);
+        assert_eq!(preview[0].id, old.id);
+        assert!(get(&conn, &old.id).is_ok());
+
+        let purged = purge_archived(&conn, cutoff, None, false).unwrap();
+        assert_eq!(purged.len(), 1);
+        assert!(matches!(get(&conn, &old.id), Err(BunyanError::NotFound(_))));
+        assert!(get(&conn, &recent.id).is_ok());
+        assert!(get(&conn, &live.id).is_ok());
+    }
+
+    fn set_labels(conn: &Connection, id: &str, labels: &[&str]) -> Workspace {
+        update(
+            conn,
+            id,
+            UpdateWorkspaceInput {
+                description: None,
+                notes: None,
+                labels: Some(labels.iter().map(|l| l.to_string()).collect()),
+                config: None,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn update_sets_and_clears_description_and_notes() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let ws = create_local(&conn, &repo.id, "naples");
+
+        let updated = update(
+            &conn,
+            &ws.id,
+            UpdateWorkspaceInput {
+                description: Some("flaky-test investigation".to_string()),
+                notes: Some("fails only on CI".to_string()),
+                labels: None,
+                config: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(updated.description.as_deref(), Some("flaky-test investigation"));
+        assert_eq!(updated.notes.as_deref(), Some("fails only on CI"));
+
+        let cleared = update(
+            &conn,
+            &ws.id,
+            UpdateWorkspaceInput {
+                description: Some(String::new()),
+                notes: None,
+                labels: None,
+                config: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(cleared.description, None);
+        assert_eq!(cleared.notes.as_deref(), Some("fails only on CI"));
+    }
+
+    #[test]
+    fn update_replaces_labels() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let ws = create_local(&conn, &repo.id, "omaha");
+
+        let first = set_labels(&conn, &ws.id, &["flaky", "ci"]);
+        assert_eq!(first.labels, vec!["ci", "flaky"]);
+
+        let second = set_labels(&conn, &ws.id, &["review"]);
+        assert_eq!(second.labels, vec!["review"]);
+    }
+
+    #[test]
+    fn update_rejects_invalid_label() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let ws = create_local(&conn, &repo.id, "provo");
+
+        let result = update(
+            &conn,
+            &ws.id,
+            UpdateWorkspaceInput {
+                description: None,
+                notes: None,
+                labels: Some(vec!["has space".to_string()]),
+                config: None,
+            },
+        );
+        assert!(matches!(result, Err(BunyanError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn list_filtered_by_label_state_and_mode() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let a = create_local(&conn, &repo.id, "quincy");
+        let b = create_local(&conn, &repo.id, "reno");
+        create(
+            &conn,
+            CreateWorkspaceInput {
+                container_mode: ContainerMode::Container,
+                ..local_input(&repo.id, "salem")
+            },
+        )
+        .unwrap();
+        set_labels(&conn, &a.id, &["flaky", "ci"]);
+        set_labels(&conn, &b.id, &["flaky"]);
+        archive(&conn, &b.id).unwrap();
+
+        let flaky = list_filtered(
+            &conn,
+            &WorkspaceFilter {
+                labels: vec!["flaky".to_string()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(flaky.len(), 2);
+
+        let flaky_ci = list_filtered(
+            &conn,
+            &WorkspaceFilter {
+                labels: vec!["flaky".to_string(), "ci".to_string()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(flaky_ci.len(), 1);
+        assert_eq!(flaky_ci[0].id, a.id);
+
+        let ready_flaky = list_filtered(
+            &conn,
+            &WorkspaceFilter {
+                state: Some(WorkspaceState::Ready),
+                labels: vec!["flaky".to_string()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(ready_flaky.len(), 1);
+
+        let containers = list_filtered(
+            &conn,
+            &WorkspaceFilter {
+                container_mode: Some(ContainerMode::Container),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(containers.len(), 1);
+        assert_eq!(containers[0].directory_name, "salem");
+    }
+
+    #[test]
+    fn update_sets_and_clears_config_overrides() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let ws = create_local(&conn, &repo.id, "tulsa");
+
+        let updated = update(
+            &conn,
+            &ws.id,
+            UpdateWorkspaceInput {
+                description: None,
+                notes: None,
+                labels: None,
+                config: Some(serde_json::json!({"editor": "zed"})),
+            },
+        )
+        .unwrap();
+        assert_eq!(updated.config.unwrap().editor.as_deref(), Some("zed"));
+
+        let cleared = update(
+            &conn,
+            &ws.id,
+            UpdateWorkspaceInput {
+                description: None,
+                notes: None,
+                labels: None,
+                config: Some(serde_json::json!({})),
+            },
+        )
+        .unwrap();
+        assert!(cleared.config.is_none());
+    }
+
+    #[test]
+    fn update_rejects_invalid_config() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let ws = create_local(&conn, &repo.id, "utica");
+
+        let result = update(
+            &conn,
+            &ws.id,
+            UpdateWorkspaceInput {
+                description: None,
+                notes: None,
+                labels: None,
+                config: Some(serde_json::json!({"container": {"imgae": "node:22"}})),
+            },
+        );
+        assert!(matches!(result, Err(BunyanError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn container_overrides_are_fixed_once_the_container_exists() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let ws = create_pending(
+            &conn,
+            CreateWorkspaceInput {
+                container_mode: ContainerMode::Container,
+                config: Some(serde_json::json!({"container": {"image": "python:3.12"}})),
+                ..local_input(&repo.id, "vienna")
+            },
+        )
+        .unwrap();
+        let overrides = ws.config.as_ref().and_then(|c| c.container.clone()).unwrap();
+        assert_eq!(overrides.image.as_deref(), Some("python:3.12"));
+        set_container_id(&conn, &ws.id, "abc123").unwrap();
+
+        let input = |config: serde_json::Value| UpdateWorkspaceInput {
+            description: None,
+            notes: None,
+            labels: None,
+            config: Some(config),
+        };
+        let changed = update(&conn, &ws.id, input(serde_json::json!({"container": {"image": "node:22"}})));
+        assert!(matches!(changed, Err(BunyanError::Conflict(_))));
+        let editor = serde_json::json!({"container": {"image": "python:3.12"}, "editor": "zed"});
+        assert_eq!(update(&conn, &ws.id, input(editor)).unwrap().config.unwrap().editor.as_deref(), Some("zed"));
+    }
+
+    #[test]
+    fn state_change_from_a_stale_state_conflicts() {
+        let conn = test_db();
+        let repo = create_test_repo(&conn, "frontend");
+        let ws = create_local(&conn, &repo.id, "oslo");
+        // Another request archived it after this one read `ready`
+        transition(&conn, &ws.id, WorkspaceState::Archiving, None).unwrap();
+
+        let result = move_state(&conn, &ws.id, &WorkspaceState::Ready, &WorkspaceState::Suspended, None, &now());
+        assert!(matches!(result, Err(BunyanError::Conflict(_))));
+        assert_eq!(get(&conn, &ws.id).unwrap().state, WorkspaceState::Archiving);
+        assert_eq!(list_transitions(&conn, &ws.id).unwrap().len(), 2);
+    }
 }
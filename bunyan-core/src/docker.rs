@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 
 use bollard::container::{
-    Config, CreateContainerOptions, RemoveContainerOptions, StartContainerOptions,
-    StopContainerOptions,
+    Config, CreateContainerOptions, ListContainersOptions, RemoveContainerOptions,
+    StartContainerOptions, StopContainerOptions,
 };
 use bollard::exec::{CreateExecOptions, StartExecResults};
 use bollard::image::CreateImageOptions;
@@ -12,8 +12,21 @@ use bollard::Docker;
 use futures_util::StreamExt;
 
 use crate::error::{BunyanError, Result};
+#[cfg(feature = "server")]
+use crate::hooks::{self, HookOutput};
 use crate::models::PortMapping;
 
+/// Label set on every container Bunyan creates, so orphans can be found later.
+pub const MANAGED_LABEL: &str = "bunyan.managed";
+
+/// A container created by Bunyan, as seen by `list_managed_containers`.
+#[derive(Debug, Clone)]
+pub struct ManagedContainer {
+    pub id: String,
+    pub name: String,
+    pub running: bool,
+}
+
 /// Allowed base image prefixes. Images must start with one of these.
 /// Covers official Docker Hub images and common trusted registries.
 const ALLOWED_IMAGE_PREFIXES: &[&str] = &[
@@ -36,6 +49,11 @@ const ALLOWED_IMAGE_PREFIXES: &[&str] = &[
     "golang",
 ];
 
+/// Where a workspace's worktree is mounted inside its container.
+pub fn container_workdir(directory_name: &str) -> String {
+    format!("/workspace/{}", directory_name)
+}
+
 /// Validate that a Docker image is from a trusted source.
 pub fn validate_image(image: &str) -> Result<()> {
     if image.is_empty() {
@@ -55,6 +73,32 @@ pub fn validate_image(image: &str) -> Result<()> {
     Ok(())
 }
 
+/// Parse a "host_port:container_port" mapping. Host ports must be unprivileged (>= 1024).
+pub fn parse_port_spec(spec: &str) -> Result<(u16, u16)> {
+    let (host_port, container_port) = spec.split_once(':').ok_or_else(|| {
+        BunyanError::Docker(format!(
+            "Invalid port mapping '{}': expected host_port:container_port",
+            spec
+        ))
+    })?;
+    let hp: u16 = host_port
+        .parse()
+        .map_err(|_| BunyanError::Docker(format!("Invalid host port: {}", host_port)))?;
+    let cp: u16 = container_port
+        .parse()
+        .map_err(|_| BunyanError::Docker(format!("Invalid container port: {}", container_port)))?;
+    if hp < 1024 {
+        return Err(BunyanError::Docker(format!(
+            "Host port {} is privileged (< 1024). Use a port >= 1024.",
+            hp
+        )));
+    }
+    if cp == 0 {
+        return Err(BunyanError::Docker("Container port cannot be 0".to_string()));
+    }
+    Ok((hp, cp))
+}
+
 /// Environment variable names that are blocked from being passed to containers.
 const BLOCKED_ENV_VARS: &[&str] = &[
     "LD_PRELOAD",
@@ -162,7 +206,7 @@ pub async fn create_workspace_container(
 
     // Build mounts
     let home = dirs::home_dir().ok_or_else(|| BunyanError::Docker("Cannot determine home directory".to_string()))?;
-    let mount_target = format!("/workspace/{}", directory_name);
+    let mount_target = container_workdir(directory_name);
     let mut mounts = vec![
         Mount {
             target: Some(mount_target.clone()),
@@ -201,33 +245,16 @@ pub async fn create_workspace_container(
     let mut exposed_ports = HashMap::new();
     let mut port_bindings: HashMap<String, Option<Vec<PortBinding>>> = HashMap::new();
     for port_spec in ports {
-        // Format: "host_port:container_port"
-        if let Some((host_port, container_port)) = port_spec.split_once(':') {
-            let hp: u16 = host_port.parse().map_err(|_| {
-                BunyanError::Docker(format!("Invalid host port: {}", host_port))
-            })?;
-            let cp: u16 = container_port.parse().map_err(|_| {
-                BunyanError::Docker(format!("Invalid container port: {}", container_port))
-            })?;
-            if hp < 1024 {
-                return Err(BunyanError::Docker(format!(
-                    "Host port {} is privileged (< 1024). Use a port >= 1024.",
-                    hp
-                )));
-            }
-            if cp == 0 {
-                return Err(BunyanError::Docker("Container port cannot be 0".to_string()));
-            }
-            let key = format!("{}/tcp", cp);
-            exposed_ports.insert(key.clone(), HashMap::new());
-            port_bindings.insert(
-                key,
-                Some(vec![PortBinding {
-                    host_ip: Some("127.0.0.1".to_string()),
-                    host_port: Some(hp.to_string()),
-                }]),
-            );
-        }
+        let (hp, cp) = parse_port_spec(port_spec)?;
+        let key = format!("{}/tcp", cp);
+        exposed_ports.insert(key.clone(), HashMap::new());
+        port_bindings.insert(
+            key,
+            Some(vec![PortBinding {
+                host_ip: Some("127.0.0.1".to_string()),
+                host_port: Some(hp.to_string()),
+            }]),
+        );
     }
 
     let host_config = HostConfig {
@@ -249,6 +276,10 @@ pub async fn create_workspace_container(
         exposed_ports: Some(exposed_ports),
         host_config: Some(host_config),
         user: Some("1000:1000".to_string()),
+        labels: Some(HashMap::from([
+            (MANAGED_LABEL.to_string(), "true".to_string()),
+            ("bunyan.workspace".to_string(), directory_name.to_string()),
+        ])),
         ..Default::default()
     };
 
@@ -269,6 +300,42 @@ pub async fn create_workspace_container(
     Ok(container.id)
 }
 
+/// List every container Bunyan created, running or not. Containers from
+/// before labelling are recognised by their `bunyan-` name prefix.
+pub async fn list_managed_containers() -> Result<Vec<ManagedContainer>> {
+    let docker = Docker::connect_with_local_defaults()?;
+    let containers = docker
+        .list_containers(Some(ListContainersOptions::<String> {
+            all: true,
+            ..Default::default()
+        }))
+        .await?;
+
+    Ok(containers
+        .into_iter()
+        .filter_map(|c| {
+            let name = c
+                .names
+                .as_ref()
+                .and_then(|n| n.first())
+                .map(|n| n.trim_start_matches('/').to_string())
+                .unwrap_or_default();
+            let labelled = c
+                .labels
+                .as_ref()
+                .is_some_and(|l| l.contains_key(MANAGED_LABEL));
+            if !labelled && !name.starts_with("bunyan-") {
+                return None;
+            }
+            Some(ManagedContainer {
+                id: c.id?,
+                name,
+                running: c.state.as_deref() == Some("running"),
+            })
+        })
+        .collect())
+}
+
 /// Stop and remove a container.
 pub async fn remove_container(container_id: &str) -> Result<()> {
     let docker = Docker::connect_with_local_defaults()?;
@@ -292,6 +359,38 @@ pub async fn remove_container(container_id: &str) -> Result<()> {
     Ok(())
 }
 
+/// Stop a container without removing it. Idempotent — ignores 304 (already stopped).
+pub async fn stop_container(container_id: &str) -> Result<()> {
+    let docker = Docker::connect_with_local_defaults()?;
+
+    match docker
+        .stop_container(container_id, Some(StopContainerOptions { t: 5 }))
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(bollard::errors::Error::DockerResponseServerError {
+            status_code: 304, ..
+        }) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Start a previously stopped container. Idempotent — ignores 304 (already running).
+pub async fn start_container(container_id: &str) -> Result<()> {
+    let docker = Docker::connect_with_local_defaults()?;
+
+    match docker
+        .start_container(container_id, None::<StartContainerOptions<String>>)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(bollard::errors::Error::DockerResponseServerError {
+            status_code: 304, ..
+        }) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
 /// Ensure Claude CLI is available in the container.
 /// Checks for `claude`, installs via npm if not found.
 pub async fn ensure_claude(container_id: &str) -> Result<()> {
@@ -354,6 +453,87 @@ pub async fn ensure_claude(container_id: &str) -> Result<()> {
     Ok(())
 }
 
+/// Environment variable marking every process of one hook run, so a run that
+/// times out can be found and killed inside the container.
+#[cfg(feature = "server")]
+const HOOK_RUN_VAR: &str = "BUNYAN_HOOK_RUN";
+
+/// Run a hook script with `sh` inside a container, in `working_dir`. A script
+/// still running after `timeout` is killed along with everything it started.
+#[cfg(feature = "server")]
+pub async fn exec_script(
+    container_id: &str,
+    command: &str,
+    working_dir: &str,
+    env: &[(&'static str, String)],
+    timeout: std::time::Duration,
+) -> Result<HookOutput> {
+    let docker = Docker::connect_with_local_defaults()?;
+    let run_id = uuid::Uuid::new_v4().to_string();
+    let mut vars: Vec<String> = env.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+    vars.push(format!("{}={}", HOOK_RUN_VAR, run_id));
+    let exec = docker
+        .create_exec(
+            container_id,
+            CreateExecOptions {
+                cmd: Some(vec!["sh".to_string(), "-c".to_string(), hooks::shell_script(command)]),
+                env: Some(vars),
+                working_dir: Some(working_dir.to_string()),
+                attach_stdout: Some(true),
+                attach_stderr: Some(true),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let mut output = String::new();
+    let mut finished = true;
+    if let StartExecResults::Attached { output: mut stream, .. } = docker.start_exec(&exec.id, None).await? {
+        let read = async {
+            while let Some(Ok(chunk)) = stream.next().await {
+                output.push_str(&chunk.to_string());
+            }
+        };
+        finished = tokio::time::timeout(timeout, read).await.is_ok();
+    }
+    if !finished {
+        kill_hook_run(&docker, container_id, &run_id).await?;
+        return Ok(HookOutput::timed_out(&output, timeout));
+    }
+
+    let inspect = docker.inspect_exec(&exec.id).await?;
+    Ok(HookOutput {
+        exit_code: inspect.exit_code.map(|c| c as i32),
+        output: hooks::tail(&output),
+        timed_out: false,
+    })
+}
+
+/// Kill every process in the container carrying a hook run's marker variable.
+/// Docker has no call to stop an exec, so this runs one that walks `/proc`.
+#[cfg(feature = "server")]
+async fn kill_hook_run(docker: &Docker, container_id: &str, run_id: &str) -> Result<()> {
+    let script = format!(
+        "for p in /proc/[0-9]*; do tr '\\0' '\\n' 2>/dev/null < $p/environ | grep -qx '{}={}' && kill -9 ${{p#/proc/}}; done; true",
+        HOOK_RUN_VAR, run_id
+    );
+    let exec = docker
+        .create_exec(
+            container_id,
+            CreateExecOptions {
+                cmd: Some(vec!["sh".to_string(), "-c".to_string(), script]),
+                attach_stdout: Some(true),
+                attach_stderr: Some(true),
+                ..Default::default()
+            },
+        )
+        .await?;
+    if let StartExecResults::Attached { output: mut stream, .. } = docker.start_exec(&exec.id, None).await? {
+        while stream.next().await.is_some() {}
+    }
+    Ok(())
+}
+
 /// Get the status of a container: "running", "stopped", or "none".
 pub async fn get_container_status(container_id: &str) -> Result<String> {
     let docker = Docker::connect_with_local_defaults()?;
@@ -0,0 +1,537 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+#[cfg(feature = "server")]
+use std::sync::Arc;
+
+use crate::docker::ManagedContainer;
+use crate::models::{ContainerMode, DoctorIssue, IssueKind, Repo, Workspace, WorkspaceState};
+use crate::workspace;
+#[cfg(feature = "server")]
+use crate::{
+    db, docker,
+    error::{BunyanError, Result},
+    git,
+    models::{DoctorReport, EventKind},
+    state::AppState,
+    tmux,
+};
+
+/// Everything the doctor compares, gathered up front so the comparison
+/// itself has no side effects.
+#[derive(Debug, Default)]
+pub struct Inventory {
+    pub repos: Vec<Repo>,
+    pub workspaces: Vec<Workspace>,
+    /// `git worktree list` per repo ID; repos whose worktrees couldn't be listed are absent
+    pub worktrees: HashMap<String, Vec<String>>,
+    /// (session, window) pairs on the bunyan tmux server
+    pub windows: Vec<(String, String)>,
+    /// None when Docker is unavailable
+    pub containers: Option<Vec<ManagedContainer>>,
+    pub skipped: Vec<String>,
+}
+
+/// How to repair an issue.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fix {
+    MarkFailed { workspace_id: String, reason: String, clear_container: bool },
+    PruneWorktrees { repo_root: String },
+    RemoveWorktree { repo_root: String, path: String },
+    KillWindow { session: String, window: String },
+    RemoveContainer { container_id: String },
+}
+
+/// Workspaces that still expect their worktree, window and container to exist.
+/// Creating and archiving are in flight, so they are left alone.
+fn is_live(ws: &Workspace) -> bool {
+    matches!(ws.state, WorkspaceState::Ready | WorkspaceState::Suspended)
+}
+
+/// Resolve symlinks where possible so paths from git and from the database compare equal.
+fn normalize(path: &str) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path))
+}
+
+fn issue(
+    kind: IssueKind,
+    repo_id: Option<&str>,
+    workspace_id: Option<&str>,
+    subject: String,
+    message: String,
+    fix: Option<Fix>,
+) -> (DoctorIssue, Option<Fix>) {
+    (
+        DoctorIssue {
+            kind,
+            repo_id: repo_id.map(str::to_string),
+            workspace_id: workspace_id.map(str::to_string),
+            subject,
+            message,
+            fixable: fix.is_some(),
+            fixed: false,
+            fix_error: None,
+        },
+        fix,
+    )
+}
+
+/// Compare the inventory against the database and list every discrepancy
+/// with the fix for it, if there is a safe one.
+pub fn find_issues(inv: &Inventory) -> Vec<(DoctorIssue, Option<Fix>)> {
+    let mut issues = Vec::new();
+
+    // Repo configs: shown, but only the user can say what the config should be
+    for repo in &inv.repos {
+        if let Some(error) = &repo.config_error {
+            issues.push(issue(
+                IssueKind::InvalidRepoConfig,
+                Some(&repo.id),
+                None,
+                repo.name.clone(),
+                error.clone(),
+                None,
+            ));
+        }
+    }
+
+    // Worktrees
+    for repo in &inv.repos {
+        let Some(listed) = inv.worktrees.get(&repo.id) else {
+            continue;
+        };
+        let listed: Vec<PathBuf> = listed.iter().map(|p| normalize(p)).collect();
+        let repo_workspaces: Vec<&Workspace> = inv
+            .workspaces
+            .iter()
+            .filter(|w| w.repository_id == repo.id)
+            .collect();
+
+        let mut known: HashMap<PathBuf, &Workspace> = HashMap::new();
+        for ws in &repo_workspaces {
+            let Ok(path) = workspace::workspace_path(&repo.root_path, &repo.name, &ws.directory_name)
+            else {
+                continue;
+            };
+            let path = normalize(&path);
+            if is_live(ws) && !listed.contains(&path) {
+                issues.push(issue(
+                    IssueKind::MissingWorktree,
+                    Some(&repo.id),
+                    Some(&ws.id),
+                    path.to_string_lossy().into_owned(),
+                    format!("Workspace {} has no worktree", ws.directory_name),
+                    Some(Fix::MarkFailed {
+                        workspace_id: ws.id.clone(),
+                        reason: "Worktree missing".to_string(),
+                        clear_container: false,
+                    }),
+                ));
+            }
+            // A live row wins over an archived one reusing the same directory name
+            if is_live(ws) || !known.contains_key(&path) {
+                known.insert(path, ws);
+            }
+        }
+
+        // Only worktrees in Bunyan's own directory; the user may keep others
+        let Ok(sample) = workspace::workspace_path(&repo.root_path, &repo.name, "_") else {
+            continue;
+        };
+        let Some(ws_dir) = Path::new(&sample).parent().map(|p| normalize(&p.to_string_lossy()))
+        else {
+            continue;
+        };
+        for path in &listed {
+            if !path.starts_with(&ws_dir) {
+                continue;
+            }
+            let shown = path.to_string_lossy().into_owned();
+            match known.get(path) {
+                Some(ws) if ws.state != WorkspaceState::Archived => {}
+                Some(ws) => issues.push(issue(
+                    IssueKind::UntrackedWorktree,
+                    Some(&repo.id),
+                    Some(&ws.id),
+                    shown.clone(),
+                    format!("Worktree of archived workspace {} was not removed", ws.directory_name),
+                    Some(Fix::RemoveWorktree {
+                        repo_root: repo.root_path.clone(),
+                        path: shown,
+                    }),
+                )),
+                None if !path.exists() => issues.push(issue(
+                    IssueKind::UntrackedWorktree,
+                    Some(&repo.id),
+                    None,
+                    shown,
+                    "Git still records a worktree whose directory is gone".to_string(),
+                    Some(Fix::PruneWorktrees {
+                        repo_root: repo.root_path.clone(),
+                    }),
+                )),
+                // Might hold someone's work; leave it for a person to decide
+                None => issues.push(issue(
+                    IssueKind::UntrackedWorktree,
+                    Some(&repo.id),
+                    None,
+                    shown,
+                    "Worktree has no workspace record".to_string(),
+                    None,
+                )),
+            }
+        }
+    }
+
+    // Tmux windows: sessions are named after repos, windows after workspaces
+    for (session, window) in &inv.windows {
+        let repo = inv.repos.iter().find(|r| &r.name == session);
+        let backed = repo.is_some_and(|r| {
+            inv.workspaces.iter().any(|w| {
+                w.repository_id == r.id && &w.directory_name == window && w.state != WorkspaceState::Archived
+            })
+        });
+        if !backed {
+            issues.push(issue(
+                IssueKind::StaleTmuxWindow,
+                repo.map(|r| r.id.as_str()),
+                None,
+                format!("{}:{}", session, window),
+                "Tmux window has no live workspace".to_string(),
+                Some(Fix::KillWindow {
+                    session: session.clone(),
+                    window: window.clone(),
+                }),
+            ));
+        }
+    }
+
+    // Containers
+    if let Some(containers) = &inv.containers {
+        let present: HashSet<&str> = containers.iter().map(|c| c.id.as_str()).collect();
+        for container in containers {
+            let referenced = inv.workspaces.iter().any(|w| {
+                w.state != WorkspaceState::Archived && w.container_id.as_deref() == Some(container.id.as_str())
+            });
+            if !referenced {
+                issues.push(issue(
+                    IssueKind::OrphanedContainer,
+                    None,
+                    None,
+                    container.id.clone(),
+                    format!(
+                        "Container {} ({}) belongs to no live workspace",
+                        container.name,
+                        if container.running { "running" } else { "stopped" }
+                    ),
+                    Some(Fix::RemoveContainer {
+                        container_id: container.id.clone(),
+                    }),
+                ));
+            }
+        }
+        for ws in inv.workspaces.iter().filter(|w| is_live(w)) {
+            let Some(container_id) = &ws.container_id else {
+                continue;
+            };
+            if ws.container_mode == ContainerMode::Container && !present.contains(container_id.as_str()) {
+                issues.push(issue(
+                    IssueKind::MissingContainer,
+                    Some(&ws.repository_id),
+                    Some(&ws.id),
+                    container_id.clone(),
+                    format!("Container for workspace {} no longer exists", ws.directory_name),
+                    Some(Fix::MarkFailed {
+                        workspace_id: ws.id.clone(),
+                        reason: "Container missing".to_string(),
+                        clear_container: true,
+                    }),
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+/// Gather the database rows, worktrees, tmux windows and containers.
+#[cfg(feature = "server")]
+pub async fn collect(state: &Arc<AppState>) -> Result<Inventory> {
+    let (repos, workspaces, git) = state
+        .db
+        .call(|conn| {
+            Ok((
+                db::repos::list(conn)?,
+                db::workspaces::list(conn, None)?,
+                git::backend(conn),
+            ))
+        })
+        .await?;
+
+    let roots: Vec<(String, String)> = repos.iter().map(|r| (r.id.clone(), r.root_path.clone())).collect();
+    let (worktrees, windows, mut skipped) = tokio::task::spawn_blocking(move || {
+        let mut skipped = Vec::new();
+        let mut worktrees = HashMap::new();
+        for (id, root) in roots {
+            match git.worktree_list(&root) {
+                Ok(list) => {
+                    worktrees.insert(id, list.into_iter().map(|w| w.path).collect());
+                }
+                Err(e) => skipped.push(format!("worktrees of {}: {}", root, e)),
+            }
+        }
+        let mut windows: Vec<(String, String)> = match tmux::list_all_panes() {
+            Ok(panes) => panes.into_iter().map(|(s, w, _)| (s, w)).collect(),
+            Err(e) => {
+                skipped.push(format!("tmux: {}", e));
+                Vec::new()
+            }
+        };
+        windows.dedup();
+        (worktrees, windows, skipped)
+    })
+    .await
+    .map_err(|e| BunyanError::Process(e.to_string()))?;
+
+    let containers = match docker::list_managed_containers().await {
+        Ok(list) => Some(list),
+        Err(e) => {
+            skipped.push(format!("containers: {}", e));
+            None
+        }
+    };
+
+    Ok(Inventory {
+        repos,
+        workspaces,
+        worktrees,
+        windows,
+        containers,
+        skipped,
+    })
+}
+
+#[cfg(feature = "server")]
+async fn apply(state: &Arc<AppState>, fix: Fix) -> Result<()> {
+    match fix {
+        Fix::MarkFailed {
+            workspace_id,
+            reason,
+            clear_container,
+        } => {
+            state
+                .db
+                .call(move |conn| {
+                    if clear_container {
+                        db::workspaces::clear_container_id(conn, &workspace_id)?;
+                    }
+                    let ws = db::workspaces::transition(
+                        conn,
+                        &workspace_id,
+                        WorkspaceState::Failed,
+                        Some(&reason),
+                    )?;
+                    db::events::audit(
+                        conn,
+                        EventKind::WorkspaceFailed,
+                        Some(&ws.repository_id),
+                        Some(&ws.id),
+                        Some(&reason),
+                    );
+                    Ok(())
+                })
+                .await
+        }
+        Fix::PruneWorktrees { repo_root } => {
+            let git = state.db.call(|conn| Ok(git::backend(conn))).await?;
+            tokio::task::spawn_blocking(move || git.worktree_prune(&repo_root))
+                .await
+                .map_err(|e| BunyanError::Process(e.to_string()))?
+        }
+        Fix::RemoveWorktree { repo_root, path } => {
+            // Not forced: git refuses if the worktree has uncommitted changes
+            let git = state.db.call(|conn| Ok(git::backend(conn))).await?;
+            tokio::task::spawn_blocking(move || git.worktree_remove(&repo_root, &path, false))
+                .await
+                .map_err(|e| BunyanError::Process(e.to_string()))?
+        }
+        Fix::KillWindow { session, window } => {
+            tokio::task::spawn_blocking(move || tmux::kill_window(&session, &window))
+                .await
+                .map_err(|e| BunyanError::Process(e.to_string()))?
+        }
+        Fix::RemoveContainer { container_id } => docker::remove_container(&container_id).await,
+    }
+}
+
+/// Check for drift and, when `fix` is set, repair every fixable issue.
+/// A failed repair is recorded on its issue rather than stopping the rest.
+#[cfg(feature = "server")]
+pub async fn run(state: &Arc<AppState>, fix: bool) -> Result<DoctorReport> {
+    let inventory = collect(state).await?;
+    let mut issues = Vec::new();
+    for (mut found, repair) in find_issues(&inventory) {
+        if let (true, Some(repair)) = (fix, repair) {
+            match apply(state, repair).await {
+                Ok(()) => found.fixed = true,
+                Err(e) => found.fix_error = Some(e.to_string()),
+            }
+        }
+        issues.push(found);
+    }
+    Ok(DoctorReport {
+        issues,
+        skipped: inventory.skipped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo() -> Repo {
+        Repo {
+            id: "r1".to_string(),
+            name: "app".to_string(),
+            remote_url: "url".to_string(),
+            default_branch: "main".to_string(),
+            root_path: "/nonexistent/bunyan/repos/app".to_string(),
+            remote: "origin".to_string(),
+            display_order: 0,
+            config: None,
+            config_error: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    fn ws(id: &str, dir: &str, state: WorkspaceState) -> Workspace {
+        Workspace {
+            id: id.to_string(),
+            repository_id: "r1".to_string(),
+            directory_name: dir.to_string(),
+            branch: dir.to_string(),
+            state,
+            state_reason: None,
+            container_mode: ContainerMode::Local,
+            container_id: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+            archived_at: None,
+            description: None,
+            notes: None,
+            labels: Vec::new(),
+            config: None,
+            branch_cleanup: None,
+            pull_request: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    fn inventory(workspaces: Vec<Workspace>, worktrees: &[&str]) -> Inventory {
+        Inventory {
+            repos: vec![repo()],
+            workspaces,
+            worktrees: HashMap::from([(
+                "r1".to_string(),
+                worktrees.iter().map(|p| p.to_string()).collect(),
+            )]),
+            ..Default::default()
+        }
+    }
+
+    fn kinds(found: &[(DoctorIssue, Option<Fix>)]) -> Vec<IssueKind> {
+        found.iter().map(|(i, _)| i.kind.clone()).collect()
+    }
+
+    #[test]
+    fn consistent_state_has_no_issues() {
+        let inv = inventory(
+            vec![ws("w1", "fix", WorkspaceState::Ready)],
+            &["/nonexistent/bunyan/repos/app", "/nonexistent/bunyan/workspaces/app/fix"],
+        );
+        assert!(find_issues(&inv).is_empty());
+    }
+
+    #[test]
+    fn invalid_repo_config_is_reported_without_a_fix() {
+        let mut inv = inventory(Vec::new(), &["/nonexistent/bunyan/repos/app"]);
+        inv.repos[0].config_error = Some("Invalid repo config: extra: unknown field".to_string());
+        let found = find_issues(&inv);
+        assert_eq!(kinds(&found), vec![IssueKind::InvalidRepoConfig]);
+        assert!(found[0].1.is_none() && !found[0].0.fixable);
+    }
+
+    #[test]
+    fn live_workspace_without_worktree_is_marked_failed() {
+        let inv = inventory(vec![ws("w1", "fix", WorkspaceState::Ready)], &["/nonexistent/bunyan/repos/app"]);
+        let found = find_issues(&inv);
+        assert_eq!(kinds(&found), vec![IssueKind::MissingWorktree]);
+        assert!(matches!(&found[0].1, Some(Fix::MarkFailed { workspace_id, .. }) if workspace_id == "w1"));
+    }
+
+    #[test]
+    fn untracked_worktrees_are_pruned_or_removed_by_case() {
+        let inv = inventory(
+            vec![ws("w1", "old", WorkspaceState::Archived)],
+            &[
+                "/nonexistent/bunyan/repos/app",
+                "/nonexistent/bunyan/workspaces/app/old",
+                "/nonexistent/bunyan/workspaces/app/stray",
+                "/elsewhere/my-own-worktree",
+            ],
+        );
+        let found = find_issues(&inv);
+        assert_eq!(kinds(&found), vec![IssueKind::UntrackedWorktree, IssueKind::UntrackedWorktree]);
+        let fixes: Vec<&Fix> = found.iter().filter_map(|(_, f)| f.as_ref()).collect();
+        assert!(fixes.iter().any(|f| matches!(f, Fix::RemoveWorktree { .. })));
+        assert!(fixes.iter().any(|f| matches!(f, Fix::PruneWorktrees { .. })));
+    }
+
+    #[test]
+    fn windows_without_live_workspace_are_stale() {
+        let mut inv = inventory(
+            vec![
+                ws("w1", "fix", WorkspaceState::Ready),
+                ws("w2", "done", WorkspaceState::Archived),
+            ],
+            &["/nonexistent/bunyan/repos/app", "/nonexistent/bunyan/workspaces/app/fix"],
+        );
+        inv.windows = vec![
+            ("app".to_string(), "fix".to_string()),
+            ("app".to_string(), "done".to_string()),
+            ("gone".to_string(), "x".to_string()),
+        ];
+        let subjects: Vec<String> = find_issues(&inv).into_iter().map(|(i, _)| i.subject).collect();
+        assert_eq!(subjects, vec!["app:done", "gone:x"]);
+    }
+
+    #[test]
+    fn containers_are_matched_against_workspaces() {
+        let mut live = ws("w1", "fix", WorkspaceState::Ready);
+        live.container_mode = ContainerMode::Container;
+        live.container_id = Some("c-missing".to_string());
+        let mut inv = inventory(
+            vec![live],
+            &["/nonexistent/bunyan/repos/app", "/nonexistent/bunyan/workspaces/app/fix"],
+        );
+        inv.containers = Some(vec![ManagedContainer {
+            id: "c-orphan".to_string(),
+            name: "bunyan-app-old".to_string(),
+            running: true,
+        }]);
+        let found = find_issues(&inv);
+        assert_eq!(kinds(&found), vec![IssueKind::OrphanedContainer, IssueKind::MissingContainer]);
+    }
+
+    #[test]
+    fn container_checks_skip_when_docker_unavailable() {
+        let mut live = ws("w1", "fix", WorkspaceState::Ready);
+        live.container_mode = ContainerMode::Container;
+        live.container_id = Some("c1".to_string());
+        let inv = inventory(
+            vec![live],
+            &["/nonexistent/bunyan/repos/app", "/nonexistent/bunyan/workspaces/app/fix"],
+        );
+        assert!(find_issues(&inv).is_empty());
+    }
+}
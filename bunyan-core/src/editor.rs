@@ -65,9 +65,10 @@ impl Editor {
         }
     }
 
-    /// All non-iTerm editors that can be detected.
-    fn detectable() -> &'static [Editor] {
+    /// Every supported editor, iTerm first.
+    pub fn all() -> &'static [Editor] {
         &[
+            Editor::Iterm,
             Editor::Vscode,
             Editor::Cursor,
             Editor::Zed,
@@ -75,6 +76,11 @@ impl Editor {
             Editor::Antigravity,
         ]
     }
+
+    /// All non-iTerm editors that can be detected.
+    fn detectable() -> &'static [Editor] {
+        &Self::all()[1..]
+    }
 }
 
 /// Check if a CLI binary is available on PATH.
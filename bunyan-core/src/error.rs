@@ -3,22 +3,64 @@ use std::fmt;
 #[derive(Debug)]
 pub enum BunyanError {
     Database(rusqlite::Error),
+    DatabaseUnavailable(String),
     Serialization(serde_json::Error),
     Git(String),
+    /// A branch that was to be created already exists
+    BranchExists(String),
+    /// A file or directory that was to be created already exists
+    PathExists(String),
+    /// A git remote rejected the available credentials
+    GitAuth(String),
     Process(String),
     NotFound(String),
     Docker(String),
+    /// A code forge (e.g. GitHub) API call failed
+    Forge(String),
+    Migration(String),
+    Conflict(String),
+    InvalidInput(String),
 }
 
 impl fmt::Display for BunyanError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             BunyanError::Database(e) => write!(f, "Database error: {}", e),
+            BunyanError::DatabaseUnavailable(msg) => write!(f, "Database unavailable: {}", msg),
             BunyanError::Serialization(e) => write!(f, "Serialization error: {}", e),
             BunyanError::Git(msg) => write!(f, "Git error: {}", msg),
+            BunyanError::BranchExists(msg) => write!(f, "Branch exists: {}", msg),
+            BunyanError::PathExists(msg) => write!(f, "Path exists: {}", msg),
+            BunyanError::GitAuth(msg) => write!(f, "Git authentication failed: {}", msg),
             BunyanError::Process(msg) => write!(f, "Process error: {}", msg),
             BunyanError::NotFound(msg) => write!(f, "Not found: {}", msg),
             BunyanError::Docker(msg) => write!(f, "Docker error: {}", msg),
+            BunyanError::Forge(msg) => write!(f, "Forge error: {}", msg),
+            BunyanError::Migration(msg) => write!(f, "Migration error: {}", msg),
+            BunyanError::Conflict(msg) => write!(f, "Conflict: {}", msg),
+            BunyanError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
+        }
+    }
+}
+
+impl BunyanError {
+    /// Stable machine-readable name of the error kind, sent to API clients.
+    pub fn code(&self) -> &'static str {
+        match self {
+            BunyanError::Database(_) => "database",
+            BunyanError::DatabaseUnavailable(_) => "database_unavailable",
+            BunyanError::Serialization(_) => "serialization",
+            BunyanError::Git(_) => "git",
+            BunyanError::BranchExists(_) => "branch_exists",
+            BunyanError::PathExists(_) => "path_exists",
+            BunyanError::GitAuth(_) => "git_auth",
+            BunyanError::Process(_) => "process",
+            BunyanError::NotFound(_) => "not_found",
+            BunyanError::Docker(_) => "docker",
+            BunyanError::Forge(_) => "forge",
+            BunyanError::Migration(_) => "migration",
+            BunyanError::Conflict(_) => "conflict",
+            BunyanError::InvalidInput(_) => "invalid_input",
         }
     }
 }
@@ -27,7 +69,18 @@ impl std::error::Error for BunyanError {}
 
 impl From<rusqlite::Error> for BunyanError {
     fn from(err: rusqlite::Error) -> Self {
-        BunyanError::Database(err)
+        // A stored JSON column that no longer parses is a serialization error
+        match err {
+            rusqlite::Error::FromSqlConversionFailure(idx, ty, source) => {
+                match source.downcast::<serde_json::Error>() {
+                    Ok(json) => BunyanError::Serialization(*json),
+                    Err(source) => {
+                        BunyanError::Database(rusqlite::Error::FromSqlConversionFailure(idx, ty, source))
+                    }
+                }
+            }
+            err => BunyanError::Database(err),
+        }
     }
 }
 
@@ -0,0 +1,537 @@
+use std::process::Command;
+use std::time::Duration;
+
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use rusqlite::Connection;
+use serde::Deserialize;
+
+use crate::error::{BunyanError, Result};
+use crate::models::{ChecksState, PullRequest, PullRequestState, Repo};
+use crate::settings;
+
+/// Owner and name of a repository on a forge, parsed from its remote URL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteRepo {
+    pub host: String,
+    pub owner: String,
+    pub name: String,
+}
+
+/// Parse `git@host:owner/name.git`, `ssh://git@host/owner/name.git` or
+/// `https://host/owner/name`. Returns None for anything else, including
+/// local paths and URLs nested deeper than owner/name.
+pub fn parse_remote_url(url: &str) -> Option<RemoteRepo> {
+    let url = url.trim();
+    let (authority, path) = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?,
+        // scp-like syntax needs a user@ and a colon before the first slash
+        None => {
+            let (authority, path) = url.split_once(':')?;
+            if !authority.contains('@') || authority.contains('/') {
+                return None;
+            }
+            (authority, path)
+        }
+    };
+    let host = authority.rsplit('@').next()?.split(':').next()?;
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, name) = path.split_once('/')?;
+    if host.is_empty() || owner.is_empty() || name.is_empty() || name.contains('/') {
+        return None;
+    }
+    Some(RemoteRepo {
+        host: host.to_string(),
+        owner: owner.to_string(),
+        name: name.to_string(),
+    })
+}
+
+/// A pull request to open.
+#[derive(Debug, Clone)]
+pub struct NewPullRequest {
+    pub head: String,
+    pub base: String,
+    pub title: String,
+    pub body: Option<String>,
+    pub draft: bool,
+}
+
+/// A code forge hosting a repository's pull requests.
+pub trait Forge: Send + Sync {
+    fn create_pull_request(&self, pr: &NewPullRequest) -> Result<PullRequest>;
+    /// A pull request with the checks of its head commit.
+    fn pull_request(&self, number: u64) -> Result<PullRequest>;
+    /// Pull requests in any state whose head is `branch`, most recently
+    /// updated first. Checks are not fetched.
+    fn pull_requests_for_branch(&self, branch: &str) -> Result<Vec<PullRequest>>;
+}
+
+/// GitHub Enterprise Server hosts from the `github_enterprise_hosts` setting.
+pub fn enterprise_hosts(conn: &Connection) -> Vec<String> {
+    settings::get_effective(conn, settings::GITHUB_ENTERPRISE_HOSTS)
+        .map(|s| {
+            s.value
+                .split(',')
+                .map(|h| h.trim().to_ascii_lowercase())
+                .filter(|h| !h.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The GitHub API root for `host`: github.com, or one of the configured
+/// Enterprise hosts. Any other host gets None, so no token is sent to it.
+fn github_api_base(host: &str, enterprise_hosts: &[String]) -> Option<String> {
+    let host = host.to_ascii_lowercase();
+    if host == "github.com" {
+        Some("https://api.github.com".to_string())
+    } else if enterprise_hosts.contains(&host) {
+        Some(format!("https://{}/api/v3", host))
+    } else {
+        None
+    }
+}
+
+/// The forge for a repo, chosen from its remote URL. GitHub Enterprise
+/// hosts are only recognized when listed in `enterprise_hosts`.
+pub fn for_repo(repo: &Repo, enterprise_hosts: &[String]) -> Result<Box<dyn Forge>> {
+    let remote = parse_remote_url(&repo.remote_url).ok_or_else(|| {
+        BunyanError::InvalidInput(format!(
+            "Cannot tell which forge hosts remote '{}'",
+            repo.remote_url
+        ))
+    })?;
+    let api_base = github_api_base(&remote.host, enterprise_hosts).ok_or_else(|| {
+        BunyanError::InvalidInput(format!(
+            "No forge integration for {}; only github.com and hosts in the {} setting are supported",
+            remote.host,
+            settings::GITHUB_ENTERPRISE_HOSTS
+        ))
+    })?;
+    let token = github_token(&remote.host);
+    Ok(Box::new(GitHub::new(&api_base, &remote.owner, &remote.name, token)?))
+}
+
+/// A token from the environment, or from the `gh` CLI's login.
+fn github_token(host: &str) -> Option<String> {
+    let vars: &[&str] = if host == "github.com" {
+        &["GH_TOKEN", "GITHUB_TOKEN"]
+    } else {
+        &["GH_ENTERPRISE_TOKEN", "GITHUB_ENTERPRISE_TOKEN"]
+    };
+    vars.iter()
+        .filter_map(|v| std::env::var(v).ok())
+        .find(|t| !t.trim().is_empty())
+        .or_else(|| {
+            let output = Command::new("gh")
+                .args(["auth", "token", "--hostname", host])
+                .output()
+                .ok()?;
+            let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            (output.status.success() && !token.is_empty()).then_some(token)
+        })
+}
+
+/// GitHub REST API client for one repository.
+pub struct GitHub {
+    api_base: String,
+    owner: String,
+    name: String,
+    token: Option<String>,
+    client: Client,
+}
+
+#[derive(Deserialize)]
+struct GhRef {
+    #[serde(rename = "ref")]
+    name: String,
+    sha: String,
+}
+
+#[derive(Deserialize)]
+struct GhPull {
+    number: u64,
+    html_url: String,
+    title: String,
+    state: String,
+    #[serde(default)]
+    draft: bool,
+    merged_at: Option<String>,
+    head: GhRef,
+    base: GhRef,
+}
+
+#[derive(Deserialize)]
+struct GhCheckRuns {
+    check_runs: Vec<GhCheckRun>,
+}
+
+#[derive(Deserialize)]
+struct GhCheckRun {
+    status: String,
+    conclusion: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GhCombinedStatus {
+    statuses: Vec<GhStatus>,
+}
+
+#[derive(Deserialize)]
+struct GhStatus {
+    state: String,
+}
+
+#[derive(Deserialize)]
+struct GhError {
+    message: String,
+    #[serde(default)]
+    errors: Vec<serde_json::Value>,
+}
+
+impl GhPull {
+    fn into_pull_request(self) -> PullRequest {
+        let state = if self.merged_at.is_some() {
+            PullRequestState::Merged
+        } else if self.state == "closed" {
+            PullRequestState::Closed
+        } else if self.draft {
+            PullRequestState::Draft
+        } else {
+            PullRequestState::Open
+        };
+        PullRequest {
+            number: self.number,
+            url: self.html_url,
+            title: self.title,
+            state,
+            head: self.head.name,
+            base: self.base.name,
+            checks: None,
+        }
+    }
+}
+
+/// Fold check runs (`status`, `conclusion`) and commit status states into one
+/// verdict: any failure wins, then anything still running, then success.
+pub fn summarize_checks(runs: &[(&str, Option<&str>)], statuses: &[&str]) -> Option<ChecksState> {
+    if runs.is_empty() && statuses.is_empty() {
+        return None;
+    }
+    let failed = runs.iter().any(|(_, conclusion)| {
+        matches!(
+            conclusion,
+            Some("failure" | "timed_out" | "cancelled" | "action_required" | "startup_failure")
+        )
+    }) || statuses.iter().any(|s| matches!(*s, "failure" | "error"));
+    let pending = runs.iter().any(|(status, _)| *status != "completed")
+        || statuses.contains(&"pending");
+    Some(if failed {
+        ChecksState::Failure
+    } else if pending {
+        ChecksState::Pending
+    } else {
+        ChecksState::Success
+    })
+}
+
+impl GitHub {
+    pub fn new(api_base: &str, owner: &str, name: &str, token: Option<String>) -> Result<Self> {
+        let client = Client::builder()
+            .connect_timeout(Duration::from_secs(5))
+            .timeout(Duration::from_secs(15))
+            .user_agent("bunyan")
+            .build()
+            .map_err(|e| BunyanError::Forge(format!("Failed to build HTTP client: {}", e)))?;
+        Ok(GitHub {
+            api_base: api_base.trim_end_matches('/').to_string(),
+            owner: owner.to_string(),
+            name: name.to_string(),
+            token,
+            client,
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/repos/{}/{}/{}", self.api_base, self.owner, self.name, path)
+    }
+
+    fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let mut request = request
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28");
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request
+            .send()
+            .map_err(|e| BunyanError::Forge(format!("GitHub request failed: {}", e)))?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let message = match response.json::<GhError>() {
+            Ok(err) => {
+                // Validation failures carry the useful detail in `errors`
+                let details: Vec<String> = err
+                    .errors
+                    .iter()
+                    .filter_map(|e| e.get("message").and_then(|m| m.as_str()).map(str::to_string))
+                    .collect();
+                if details.is_empty() {
+                    err.message
+                } else {
+                    format!("{}: {}", err.message, details.join("; "))
+                }
+            }
+            Err(_) => status.to_string(),
+        };
+        Err(match status.as_u16() {
+            404 => BunyanError::NotFound(format!("GitHub: {}", message)),
+            422 if message.contains("already exists") => BunyanError::Conflict(format!("GitHub: {}", message)),
+            422 => BunyanError::InvalidInput(format!("GitHub: {}", message)),
+            _ => BunyanError::Forge(format!("GitHub returned {}: {}", status.as_u16(), message)),
+        })
+    }
+
+    fn json<T: serde::de::DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        self.send(request)?
+            .json()
+            .map_err(|e| BunyanError::Forge(format!("Unexpected GitHub response: {}", e)))
+    }
+
+    fn checks(&self, sha: &str) -> Result<Option<ChecksState>> {
+        let runs: GhCheckRuns = self.json(self.client.get(self.url(&format!("commits/{}/check-runs", sha))))?;
+        let combined: GhCombinedStatus = self.json(self.client.get(self.url(&format!("commits/{}/status", sha))))?;
+        let runs: Vec<(&str, Option<&str>)> = runs
+            .check_runs
+            .iter()
+            .map(|r| (r.status.as_str(), r.conclusion.as_deref()))
+            .collect();
+        let statuses: Vec<&str> = combined.statuses.iter().map(|s| s.state.as_str()).collect();
+        Ok(summarize_checks(&runs, &statuses))
+    }
+}
+
+impl Forge for GitHub {
+    fn create_pull_request(&self, pr: &NewPullRequest) -> Result<PullRequest> {
+        let body = serde_json::json!({
+            "title": pr.title,
+            "head": pr.head,
+            "base": pr.base,
+            "body": pr.body,
+            "draft": pr.draft,
+        });
+        let created: GhPull = self.json(self.client.post(self.url("pulls")).json(&body))?;
+        Ok(created.into_pull_request())
+    }
+
+    fn pull_request(&self, number: u64) -> Result<PullRequest> {
+        let pull: GhPull = self.json(self.client.get(self.url(&format!("pulls/{}", number))))?;
+        let sha = pull.head.sha.clone();
+        let mut pr = pull.into_pull_request();
+        // Tokens without checks access still get the PR itself
+        pr.checks = self.checks(&sha).unwrap_or(None);
+        Ok(pr)
+    }
+
+    fn pull_requests_for_branch(&self, branch: &str) -> Result<Vec<PullRequest>> {
+        let head = format!("{}:{}", self.owner, branch);
+        let request = self.client.get(self.url("pulls")).query(&[
+            ("head", head.as_str()),
+            ("state", "all"),
+            ("sort", "updated"),
+            ("direction", "desc"),
+        ]);
+        let pulls: Vec<GhPull> = self.json(request)?;
+        Ok(pulls.into_iter().map(GhPull::into_pull_request).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    /// Requests seen by the mock server: "METHOD /path?query" and body.
+    type RequestLog = Arc<Mutex<Vec<(String, String)>>>;
+
+    /// Serve `responses` in order, one per connection, recording each request.
+    fn mock_server(responses: Vec<(u16, String)>) -> (String, RequestLog) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = seen.clone();
+        std::thread::spawn(move || {
+            for (status, body) in responses {
+                let Ok((stream, _)) = listener.accept() else { return };
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut request_body = vec![0; length];
+                reader.read_exact(&mut request_body).unwrap();
+                let target = request_line.split(' ').take(2).collect::<Vec<_>>().join(" ");
+                log.lock().unwrap().push((target, String::from_utf8(request_body).unwrap()));
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        (base, seen)
+    }
+
+    fn pull_json(number: u64, state: &str, draft: bool, merged: bool) -> String {
+        serde_json::json!({
+            "number": number,
+            "html_url": format!("https://github.com/org/app/pull/{}", number),
+            "title": "Fix login",
+            "state": state,
+            "draft": draft,
+            "merged_at": if merged { Some("2026-01-01T00:00:00Z") } else { None },
+            "head": {"ref": "fix", "sha": "abc123"},
+            "base": {"ref": "main", "sha": "def456"},
+        })
+        .to_string()
+    }
+
+    fn client(base: &str) -> GitHub {
+        GitHub::new(base, "org", "app", Some("t0ken".to_string())).unwrap()
+    }
+
+    #[test]
+    fn parses_common_remote_url_forms() {
+        let expected = Some(RemoteRepo {
+            host: "github.com".to_string(),
+            owner: "org".to_string(),
+            name: "app".to_string(),
+        });
+        assert_eq!(parse_remote_url("git@github.com:org/app.git"), expected);
+        assert_eq!(parse_remote_url("ssh://git@github.com:22/org/app.git"), expected);
+        assert_eq!(parse_remote_url("https://github.com/org/app"), expected);
+        assert_eq!(parse_remote_url("https://user@github.com/org/app.git/"), expected);
+        assert_eq!(parse_remote_url("/srv/git/app.git"), None);
+        assert_eq!(parse_remote_url("https://gitlab.com/group/sub/app.git"), None);
+    }
+
+    #[test]
+    fn only_github_com_and_configured_enterprise_hosts_are_github() {
+        let enterprise = vec!["github.example.com".to_string()];
+        assert_eq!(github_api_base("github.com", &[]).as_deref(), Some("https://api.github.com"));
+        assert_eq!(
+            github_api_base("GitHub.Example.com", &enterprise).as_deref(),
+            Some("https://github.example.com/api/v3")
+        );
+        assert_eq!(github_api_base("github.example.com", &[]), None);
+        assert_eq!(github_api_base("github.evil.test", &enterprise), None);
+        assert_eq!(github_api_base("notgithub.com", &enterprise), None);
+    }
+
+    #[test]
+    fn checks_summary_prefers_failure_then_pending() {
+        assert_eq!(summarize_checks(&[], &[]), None);
+        assert_eq!(
+            summarize_checks(&[("completed", Some("success")), ("completed", Some("skipped"))], &["success"]),
+            Some(ChecksState::Success)
+        );
+        assert_eq!(
+            summarize_checks(&[("in_progress", None)], &["success"]),
+            Some(ChecksState::Pending)
+        );
+        assert_eq!(
+            summarize_checks(&[("in_progress", None)], &["error"]),
+            Some(ChecksState::Failure)
+        );
+    }
+
+    #[test]
+    fn create_posts_to_pulls() {
+        let (base, seen) = mock_server(vec![(201, pull_json(7, "open", true, false))]);
+        let pr = client(&base)
+            .create_pull_request(&NewPullRequest {
+                head: "fix".to_string(),
+                base: "main".to_string(),
+                title: "Fix login".to_string(),
+                body: None,
+                draft: true,
+            })
+            .unwrap();
+        assert_eq!(pr.number, 7);
+        assert_eq!(pr.state, PullRequestState::Draft);
+        assert_eq!((pr.head.as_str(), pr.base.as_str()), ("fix", "main"));
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen[0].0, "POST /repos/org/app/pulls");
+        let sent: serde_json::Value = serde_json::from_str(&seen[0].1).unwrap();
+        assert_eq!(sent["head"], "fix");
+        assert_eq!(sent["draft"], true);
+    }
+
+    #[test]
+    fn pull_request_includes_checks_of_head_commit() {
+        let (base, seen) = mock_server(vec![
+            (200, pull_json(7, "closed", false, true)),
+            (200, r#"{"total_count": 1, "check_runs": [{"status": "completed", "conclusion": "failure"}]}"#.to_string()),
+            (200, r#"{"state": "success", "statuses": []}"#.to_string()),
+        ]);
+        let pr = client(&base).pull_request(7).unwrap();
+        assert_eq!(pr.state, PullRequestState::Merged);
+        assert_eq!(pr.checks, Some(ChecksState::Failure));
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen[1].0, "GET /repos/org/app/commits/abc123/check-runs");
+    }
+
+    #[test]
+    fn lists_pull_requests_by_head_branch() {
+        let (base, seen) = mock_server(vec![(200, format!("[{}]", pull_json(3, "open", false, false)))]);
+        let prs = client(&base).pull_requests_for_branch("fix").unwrap();
+        assert_eq!(prs.len(), 1);
+        assert_eq!(prs[0].state, PullRequestState::Open);
+        let target = &seen.lock().unwrap()[0].0;
+        assert!(target.starts_with("GET /repos/org/app/pulls?head=org%3Afix&state=all"));
+    }
+
+    #[test]
+    fn maps_error_responses() {
+        let exists = r#"{"message": "Validation Failed", "errors": [{"message": "A pull request already exists for org:fix."}]}"#;
+        let (base, _) = mock_server(vec![
+            (422, exists.to_string()),
+            (404, r#"{"message": "Not Found"}"#.to_string()),
+            (401, r#"{"message": "Bad credentials"}"#.to_string()),
+        ]);
+        let github = client(&base);
+        let new = NewPullRequest {
+            head: "fix".to_string(),
+            base: "main".to_string(),
+            title: "t".to_string(),
+            body: None,
+            draft: false,
+        };
+        assert!(matches!(github.create_pull_request(&new), Err(BunyanError::Conflict(m)) if m.contains("already exists")));
+        assert!(matches!(github.pull_requests_for_branch("fix"), Err(BunyanError::NotFound(_))));
+        assert!(matches!(github.pull_requests_for_branch("fix"), Err(BunyanError::Forge(m)) if m.contains("Bad credentials")));
+    }
+}
@@ -1,50 +1,620 @@
+use std::path::Path;
 use std::process::Command;
+use std::sync::Arc;
+
+use rusqlite::Connection;
 
 use crate::error::{BunyanError, Result};
+use crate::models::{
+    CheckoutConfig, CommitStatus, CommitSummary, DiffFileStat, PushStatus, SyncStatus, SyncStrategy,
+};
+use crate::native_git::NativeGit;
+use crate::settings;
+
+/// Which branch a new worktree checks out.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorktreeSpec {
+    /// Create `branch`, starting from `base` (or the main clone's HEAD)
+    NewBranch { branch: String, base: Option<String> },
+    /// Check out a local branch that already exists
+    Existing { branch: String },
+    /// Create local `branch` tracking the remote-tracking ref `upstream`
+    Track { branch: String, upstream: String },
+}
+
+impl WorktreeSpec {
+    pub fn branch(&self) -> &str {
+        match self {
+            WorktreeSpec::NewBranch { branch, .. }
+            | WorktreeSpec::Existing { branch }
+            | WorktreeSpec::Track { branch, .. } => branch,
+        }
+    }
+}
+
+/// Arguments for `git worktree add` that realize `spec` at `worktree_path`.
+pub fn worktree_add_args(worktree_path: &str, spec: &WorktreeSpec) -> Vec<String> {
+    let mut args = vec!["worktree".to_string(), "add".to_string()];
+    match spec {
+        WorktreeSpec::NewBranch { branch, base } => {
+            // A remote-tracking base must not become the new branch's upstream,
+            // or a plain `git push` would go to the base branch
+            args.extend(["--no-track".to_string(), "-b".to_string(), branch.clone(), worktree_path.to_string()]);
+            if let Some(base) = base {
+                args.push(base.clone());
+            }
+        }
+        WorktreeSpec::Existing { branch } => {
+            args.extend([worktree_path.to_string(), branch.clone()]);
+        }
+        WorktreeSpec::Track { branch, upstream } => {
+            args.extend([
+                "--track".to_string(),
+                "-b".to_string(),
+                branch.clone(),
+                worktree_path.to_string(),
+                upstream.clone(),
+            ]);
+        }
+    }
+    args
+}
+
+/// How much of the remote `clone_repo` downloads.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CloneOptions {
+    /// Partial clone filter, e.g. `blob:none`
+    pub filter: Option<String>,
+    /// Shallow clone depth
+    pub depth: Option<u32>,
+    /// Check out only top-level files in the main clone; worktrees set their own cone
+    pub sparse: bool,
+}
+
+impl CloneOptions {
+    pub fn from_config(checkout: Option<&CheckoutConfig>) -> Self {
+        let Some(checkout) = checkout else {
+            return Self::default();
+        };
+        CloneOptions {
+            filter: checkout.filter.clone(),
+            depth: checkout.depth,
+            sparse: !checkout.sparse.is_empty(),
+        }
+    }
+}
+
+/// Arguments for `git clone` honoring `options`.
+pub fn clone_args(url: &str, path: &str, options: &CloneOptions) -> Vec<String> {
+    let mut args = vec!["clone".to_string()];
+    if let Some(filter) = &options.filter {
+        args.push(format!("--filter={}", filter));
+    }
+    if let Some(depth) = options.depth {
+        args.push(format!("--depth={}", depth));
+    }
+    if options.sparse {
+        args.push("--sparse".to_string());
+    }
+    args.extend(["--".to_string(), url.to_string(), path.to_string()]);
+    args
+}
+
+/// Whether `filter` is a partial clone filter git accepts.
+pub fn is_clone_filter(filter: &str) -> bool {
+    let number = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    if filter == "blob:none" {
+        return true;
+    }
+    if let Some(limit) = filter.strip_prefix("blob:limit=") {
+        let digits = limit.trim_end_matches(['k', 'm', 'g']);
+        return number(digits) && limit.len() - digits.len() <= 1;
+    }
+    filter.strip_prefix("tree:").is_some_and(number)
+}
+
+/// Check one sparse-checkout directory: a plain path relative to the repo
+/// root, since cone mode takes directories rather than patterns.
+pub fn check_sparse_dir(dir: &str) -> std::result::Result<(), String> {
+    let trimmed = dir.trim_end_matches('/');
+    if trimmed.is_empty() {
+        return Err("expected a directory".to_string());
+    }
+    if trimmed.starts_with('/') || trimmed.starts_with('-') {
+        return Err("expected a path relative to the repo root".to_string());
+    }
+    if trimmed.split('/').any(|part| part.is_empty() || part == "." || part == "..") {
+        return Err("must not contain empty, '.' or '..' components".to_string());
+    }
+    if trimmed.contains(['*', '?', '[', '!', '\\', '\n']) {
+        return Err("expected a directory, not a pattern".to_string());
+    }
+    Ok(())
+}
+
+/// One entry of `git worktree list --porcelain`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorktreeEntry {
+    pub path: String,
+    /// Short branch name; None when HEAD is detached
+    pub branch: Option<String>,
+    pub bare: bool,
+}
+
+/// Parse `git worktree list --porcelain`. The first entry is the main worktree.
+pub fn parse_worktree_list(output: &str) -> Vec<WorktreeEntry> {
+    let mut entries = Vec::new();
+    let mut current: Option<WorktreeEntry> = None;
+    for line in output.lines() {
+        if let Some(path) = line.strip_prefix("worktree ") {
+            entries.extend(current.take());
+            current = Some(WorktreeEntry {
+                path: path.to_string(),
+                branch: None,
+                bare: false,
+            });
+        } else if let Some(entry) = current.as_mut() {
+            if let Some(branch) = line.strip_prefix("branch ") {
+                entry.branch = Some(branch.trim_start_matches("refs/heads/").to_string());
+            } else if line == "bare" {
+                entry.bare = true;
+            }
+        }
+    }
+    entries.extend(current);
+    entries
+}
+
+/// What `inspect_clone` learns about an existing checkout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CloneInfo {
+    pub remote_url: String,
+    pub default_branch: String,
+}
+
+/// File counts from `git status`, plus the checked-out branch.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorkingTreeStatus {
+    /// None when HEAD is detached
+    pub branch: Option<String>,
+    pub staged: u32,
+    /// Tracked files with unstaged changes
+    pub dirty: u32,
+    pub untracked: u32,
+    pub conflicted: u32,
+}
+
+/// Parse `git status --porcelain=v2 --branch` output.
+pub fn parse_status_v2(output: &str) -> WorkingTreeStatus {
+    let mut status = WorkingTreeStatus::default();
+    for line in output.lines() {
+        if let Some(head) = line.strip_prefix("# branch.head ") {
+            if head != "(detached)" {
+                status.branch = Some(head.to_string());
+            }
+            continue;
+        }
+        let mut fields = line.splitn(3, ' ');
+        match (fields.next(), fields.next()) {
+            (Some("1") | Some("2"), Some(xy)) => {
+                let mut codes = xy.chars();
+                if codes.next().is_some_and(|x| x != '.') {
+                    status.staged += 1;
+                }
+                if codes.next().is_some_and(|y| y != '.') {
+                    status.dirty += 1;
+                }
+            }
+            (Some("u"), _) => status.conflicted += 1,
+            (Some("?"), _) => status.untracked += 1,
+            _ => {}
+        }
+    }
+    status
+}
+
+/// Work in a worktree that removing it would destroy.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct UnsavedWork {
+    /// Tracked files that are modified, staged or conflicted
+    pub dirty: Vec<String>,
+    pub untracked: Vec<String>,
+    /// `<short sha> <subject>` of commits not on any remote-tracking branch
+    pub unpushed: Vec<String>,
+}
+
+impl UnsavedWork {
+    pub fn is_empty(&self) -> bool {
+        self.dirty.is_empty() && self.untracked.is_empty() && self.unpushed.is_empty()
+    }
+}
+
+/// Split `git status --porcelain=v2` output into changed tracked paths and
+/// untracked paths.
+pub fn parse_status_paths(output: &str) -> (Vec<String>, Vec<String>) {
+    let mut changed = Vec::new();
+    let mut untracked = Vec::new();
+    for line in output.lines() {
+        // The path is the last field; the number of fields before it depends on the entry type
+        let path = match line.split(' ').next() {
+            Some("1") => line.splitn(9, ' ').nth(8),
+            Some("2") => line.splitn(10, ' ').nth(9).and_then(|p| p.split('\t').next()),
+            Some("u") => line.splitn(11, ' ').nth(10),
+            Some("?") => {
+                if let Some(path) = line.get(2..) {
+                    untracked.push(path.to_string());
+                }
+                continue;
+            }
+            _ => None,
+        };
+        if let Some(path) = path {
+            changed.push(path.to_string());
+        }
+    }
+    (changed, untracked)
+}
+
+/// Parse `git rev-list --left-right --count A...B` output into (left, right).
+fn parse_left_right(output: &str) -> Option<(u32, u32)> {
+    let mut counts = output.split_whitespace().map(|n| n.parse::<u32>().ok());
+    Some((counts.next()??, counts.next()??))
+}
+
+/// Field separator for `git log --format`, unlikely to appear in commit text.
+const LOG_SEP: char = '\x1f';
+
+fn parse_commit_line(line: &str) -> Option<CommitSummary> {
+    let mut fields = line.trim_end_matches('\n').split(LOG_SEP);
+    Some(CommitSummary {
+        sha: fields.next()?.to_string(),
+        summary: fields.next()?.to_string(),
+        author: fields.next()?.to_string(),
+        committed_at: fields.next()?.to_string(),
+    })
+}
+
+/// Parse `git diff --numstat` output. Binary files report `-` for both counts.
+pub fn parse_numstat(output: &str) -> Vec<DiffFileStat> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let additions = fields.next()?;
+            let deletions = fields.next()?;
+            let path = fields.next()?.to_string();
+            let binary = additions == "-" && deletions == "-";
+            Some(DiffFileStat {
+                path,
+                additions: additions.parse().unwrap_or(0),
+                deletions: deletions.parse().unwrap_or(0),
+                binary,
+            })
+        })
+        .collect()
+}
+
+/// `git diff` arguments comparing `from` with the working tree, limited to `paths`.
+fn diff_args<'a>(mode: &'a str, from: &'a str, paths: &'a [String]) -> Vec<&'a str> {
+    let mut args = vec!["diff", "--no-renames", "--no-color", mode, from, "--"];
+    args.extend(paths.iter().map(String::as_str));
+    args
+}
+
+/// Run `git diff` with untracked files marked intent-to-add in a scratch copy
+/// of the index, so new files show up without touching the real index.
+fn run_diff(worktree_path: &str, args: &[&str], paths: &[String]) -> Result<std::process::Output> {
+    let git_path = run_git(worktree_path, &["rev-parse", "--git-path", "index"])?;
+    if !git_path.status.success() {
+        return Err(classify_failure("git rev-parse", &String::from_utf8_lossy(&git_path.stderr)));
+    }
+    let index = Path::new(worktree_path).join(String::from_utf8_lossy(&git_path.stdout).trim());
+    let scratch = index.with_file_name(format!("bunyan-diff-index-{}", uuid::Uuid::new_v4()));
+    if index.exists() {
+        std::fs::copy(&index, &scratch).map_err(|e| BunyanError::Git(format!("Cannot copy the index: {}", e)))?;
+    }
+    let with_scratch = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(worktree_path)
+            .env("GIT_INDEX_FILE", &scratch)
+            .output()
+            .map_err(|e| BunyanError::Git(format!("Failed to run git {}: {}", args[0], e)))
+    };
+    let mut add = vec!["add", "--intent-to-add", "--all", "--"];
+    add.extend(paths.iter().map(String::as_str));
+    let output = with_scratch(&add).and_then(|added| {
+        if !added.status.success() {
+            return Err(classify_failure("git add", &String::from_utf8_lossy(&added.stderr)));
+        }
+        with_scratch(args)
+    });
+    let _ = std::fs::remove_file(&scratch);
+    output
+}
+
+/// Whether a `git` executable can be run from PATH.
+pub fn cli_available() -> bool {
+    Command::new("git").arg("--version").output().is_ok_and(|o| o.status.success())
+}
+
+fn run_git(dir: &str, args: &[&str]) -> Result<std::process::Output> {
+    Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| BunyanError::Git(format!("Failed to run git {}: {}", args[0], e)))
+}
+
+/// Stdout followed by stderr, as a user would have seen it in a terminal.
+fn combined_output(output: &std::process::Output) -> String {
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    text.trim_end().to_string()
+}
+
+/// Map a failed git command's stderr to the most specific error kind.
+pub fn classify_failure(action: &str, stderr: &str) -> BunyanError {
+    let text = stderr.trim();
+    let lower = text.to_lowercase();
+    let message = text.trim_start_matches("fatal: ").to_string();
+    if lower.contains("a branch named") && lower.contains("already exists") {
+        BunyanError::BranchExists(message)
+    } else if lower.contains("already exists") {
+        BunyanError::PathExists(message)
+    } else if [
+        "authentication failed",
+        "permission denied (publickey",
+        "could not read username",
+        "terminal prompts disabled",
+    ]
+    .iter()
+    .any(|p| lower.contains(p))
+    {
+        BunyanError::GitAuth(format!("{}: {}", action, message))
+    } else {
+        BunyanError::Git(format!("{} failed: {}", action, text))
+    }
+}
+
+/// What a rebase or merge onto another ref did.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncOutcome {
+    pub status: SyncStatus,
+    /// Files left with conflicts
+    pub conflicts: Vec<String>,
+    pub output: String,
+}
+
+/// A checkpoint commit as stored under a ref.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckpointEntry {
+    pub reference: String,
+    pub sha: String,
+    /// The commit HEAD was at when the checkpoint was taken
+    pub head: String,
+    pub message: String,
+    pub created_at: String,
+}
+
+/// `for-each-ref` format read by `parse_checkpoint_list`.
+const CHECKPOINT_FORMAT: &str = "%(refname)%09%(objectname)%09%(parent)%09%(creatordate:iso-strict)%09%(subject)";
+
+/// Parse `git for-each-ref` output in `CHECKPOINT_FORMAT`.
+pub fn parse_checkpoint_list(output: &str) -> Vec<CheckpointEntry> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(5, '\t');
+            Some(CheckpointEntry {
+                reference: fields.next()?.to_string(),
+                sha: fields.next()?.to_string(),
+                head: fields.next()?.to_string(),
+                created_at: fields.next()?.to_string(),
+                message: fields.next().unwrap_or_default().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Whether `path` is a file the current user could run.
+pub(crate) fn is_executable(path: &Path) -> bool {
+    match std::fs::metadata(path) {
+        #[cfg(unix)]
+        Ok(meta) => {
+            use std::os::unix::fs::PermissionsExt;
+            meta.is_file() && meta.permissions().mode() & 0o111 != 0
+        }
+        #[cfg(not(unix))]
+        Ok(meta) => meta.is_file(),
+        Err(_) => false,
+    }
+}
+
+/// Whether any of the named hooks is installed (and executable) for the
+/// worktree, honoring `core.hooksPath`.
+fn has_hook(worktree_path: &str, names: &[&str]) -> bool {
+    names.iter().any(|name| {
+        let Ok(output) = run_git(worktree_path, &["rev-parse", "--git-path", &format!("hooks/{}", name)]) else {
+            return false;
+        };
+        is_executable(&Path::new(worktree_path).join(String::from_utf8_lossy(&output.stdout).trim()))
+    })
+}
+
+/// Classify `git push --porcelain` output. Returns None when no ref lines
+/// were printed, e.g. because a pre-push hook stopped the push first.
+pub fn parse_push_porcelain(output: &str) -> Option<PushStatus> {
+    let flags: Vec<char> = output
+        .lines()
+        .filter(|line| line.contains('\t'))
+        .filter_map(|line| line.chars().next())
+        .collect();
+    if flags.is_empty() {
+        None
+    } else if flags.contains(&'!') {
+        Some(PushStatus::Rejected)
+    } else if flags.iter().all(|f| *f == '=') {
+        Some(PushStatus::UpToDate)
+    } else {
+        Some(PushStatus::Pushed)
+    }
+}
 
 pub trait GitOps: Send + Sync {
-    fn clone_repo(&self, url: &str, path: &str) -> Result<()>;
-    fn worktree_add(&self, repo_path: &str, worktree_path: &str, branch: &str) -> Result<()>;
+    fn clone_repo(&self, url: &str, path: &str, options: &CloneOptions) -> Result<()>;
+    /// Add a worktree; a non-empty `sparse` checks out only those directories.
+    fn worktree_add(&self, repo_path: &str, worktree_path: &str, spec: &WorktreeSpec, sparse: &[String]) -> Result<()>;
+    /// Fetch a single branch from `remote`, updating its remote-tracking ref.
+    fn fetch_branch(&self, repo_path: &str, remote: &str, branch: &str) -> Result<()>;
+    /// Whether `reference` resolves to a commit.
+    fn ref_exists(&self, repo_path: &str, reference: &str) -> Result<bool>;
     fn worktree_remove(&self, repo_path: &str, worktree_path: &str, force: bool) -> Result<()>;
-    #[allow(dead_code)]
-    fn worktree_list(&self, repo_path: &str) -> Result<Vec<String>>;
+    fn worktree_list(&self, repo_path: &str) -> Result<Vec<WorktreeEntry>>;
+    /// Check that `path` is the top level of a main (non-linked) clone and read
+    /// the URL of `remote` and the remote's default branch from it.
+    fn inspect_clone(&self, path: &str, remote: &str) -> Result<CloneInfo>;
+    /// Drop administrative records of worktrees whose directories are gone.
+    fn worktree_prune(&self, repo_path: &str) -> Result<()>;
+    fn working_tree_status(&self, worktree_path: &str) -> Result<WorkingTreeStatus>;
+    /// Commits on HEAD but not `base`, and on `base` but not HEAD.
+    /// None when `base` does not resolve.
+    fn ahead_behind(&self, worktree_path: &str, base: &str) -> Result<Option<(u32, u32)>>;
+    /// The HEAD commit, or None on an unborn branch.
+    fn last_commit(&self, worktree_path: &str) -> Result<Option<CommitSummary>>;
+    /// The best common ancestor of HEAD and `other`.
+    fn merge_base(&self, worktree_path: &str, other: &str) -> Result<String>;
+    /// Per-file line counts for changes between `from` and the working tree.
+    fn diff_stat(&self, worktree_path: &str, from: &str, paths: &[String]) -> Result<Vec<DiffFileStat>>;
+    /// Unified diff between `from` and the working tree, untracked files included.
+    fn diff_patch(&self, worktree_path: &str, from: &str, paths: &[String]) -> Result<String>;
+    /// Uncommitted files and commits that exist nowhere but this worktree.
+    fn unsaved_work(&self, worktree_path: &str) -> Result<UnsavedWork>;
+    /// Whether every commit of `rev` is reachable from `into`.
+    fn is_merged(&self, repo_path: &str, rev: &str, into: &str) -> Result<bool>;
+    /// Delete a local branch, merged or not.
+    fn delete_branch(&self, repo_path: &str, branch: &str) -> Result<()>;
+    fn delete_remote_branch(&self, repo_path: &str, remote: &str, branch: &str) -> Result<()>;
+    /// Commit staged changes, after staging `paths` (only those are committed)
+    /// or, with `all`, every modified tracked file. Returns the outcome and
+    /// git's output; hook failures are an outcome, not an error.
+    fn commit(&self, worktree_path: &str, message: &str, paths: &[String], all: bool) -> Result<(CommitStatus, String)>;
+    /// Push `branch` to `remote` and make it the upstream. Rejections and
+    /// pre-push hook failures are an outcome, not an error.
+    fn push(&self, worktree_path: &str, remote: &str, branch: &str) -> Result<(PushStatus, String)>;
+    /// The worktree's sparse-checkout directories, or None when it checks out everything.
+    fn sparse_checkout(&self, worktree_path: &str) -> Result<Option<Vec<String>>>;
+    /// Add directories to a sparse worktree's cone, checking them out.
+    fn sparse_add(&self, worktree_path: &str, paths: &[String]) -> Result<()>;
+    /// Turn sparse checkout off, checking out the whole tree.
+    fn sparse_disable(&self, worktree_path: &str) -> Result<()>;
+    /// Initialize and check out the worktree's submodules, recursively.
+    fn submodule_update(&self, worktree_path: &str) -> Result<()>;
+    /// Download and check out the Git LFS objects of the worktree's HEAD.
+    fn lfs_pull(&self, worktree_path: &str) -> Result<()>;
+    /// Rebase HEAD onto `upstream`, or merge `upstream` into it. Conflicts are
+    /// an outcome, not an error; with `abort_on_conflict` the rebase or merge
+    /// is aborted, otherwise it is left in progress.
+    fn sync_onto(&self, worktree_path: &str, upstream: &str, strategy: &SyncStrategy, abort_on_conflict: bool) -> Result<SyncOutcome>;
+    /// Record the whole working tree, tracked and untracked (but not ignored)
+    /// files, as a commit on top of HEAD stored at `reference`. Neither the
+    /// index nor HEAD changes.
+    fn create_checkpoint(&self, worktree_path: &str, reference: &str, message: &str) -> Result<CheckpointEntry>;
+    /// Checkpoints under the ref prefix `prefix`, in reverse name order.
+    fn list_checkpoints(&self, repo_path: &str, prefix: &str) -> Result<Vec<CheckpointEntry>>;
+    /// Move HEAD back to the checkpoint's parent and make the working tree
+    /// match the checkpoint, removing files it did not have. Ignored files stay.
+    fn restore_checkpoint(&self, worktree_path: &str, sha: &str) -> Result<()>;
+}
+
+/// The implementation chosen by the `git_backend` setting; the git CLI
+/// unless libgit2 was selected.
+pub fn backend(conn: &Connection) -> Arc<dyn GitOps> {
+    match settings::get_effective(conn, settings::GIT_BACKEND) {
+        Ok(s) if s.value == settings::GIT_BACKEND_LIBGIT2 => Arc::new(NativeGit),
+        _ => Arc::new(RealGit),
+    }
 }
 
+/// Runs the `git` executable found on PATH.
 pub struct RealGit;
 
 impl GitOps for RealGit {
-    fn clone_repo(&self, url: &str, path: &str) -> Result<()> {
+    fn clone_repo(&self, url: &str, path: &str, options: &CloneOptions) -> Result<()> {
         let output = Command::new("git")
-            .args(["clone", url, path])
+            .args(clone_args(url, path, options))
             .output()
             .map_err(|e| BunyanError::Git(format!("Failed to run git clone: {}", e)))?;
 
         if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(BunyanError::Git(format!("git clone failed: {}", stderr)));
+            return Err(classify_failure("git clone", &String::from_utf8_lossy(&output.stderr)));
         }
 
         Ok(())
     }
 
-    fn worktree_add(&self, repo_path: &str, worktree_path: &str, branch: &str) -> Result<()> {
+    fn worktree_add(&self, repo_path: &str, worktree_path: &str, spec: &WorktreeSpec, sparse: &[String]) -> Result<()> {
+        let mut args = worktree_add_args(worktree_path, spec);
+        if !sparse.is_empty() {
+            // Set the cone before anything is checked out
+            args.insert(2, "--no-checkout".to_string());
+        }
         let output = Command::new("git")
-            .args(["worktree", "add", worktree_path, "-b", branch])
+            .args(args)
             .current_dir(repo_path)
             .output()
             .map_err(|e| BunyanError::Git(format!("Failed to run git worktree add: {}", e)))?;
 
         if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(BunyanError::Git(format!(
-                "git worktree add failed: {}",
-                stderr
-            )));
+            return Err(classify_failure("git worktree add", &String::from_utf8_lossy(&output.stderr)));
+        }
+        if sparse.is_empty() {
+            return Ok(());
+        }
+
+        let mut set = vec!["sparse-checkout", "set", "--cone", "--"];
+        set.extend(sparse.iter().map(String::as_str));
+        for args in [set, vec!["checkout"]] {
+            let output = run_git(worktree_path, &args)?;
+            if !output.status.success() {
+                return Err(classify_failure(
+                    &format!("git {}", args[0]),
+                    &String::from_utf8_lossy(&output.stderr),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn fetch_branch(&self, repo_path: &str, remote: &str, branch: &str) -> Result<()> {
+        let output = Command::new("git")
+            .args([
+                "fetch".to_string(),
+                remote.to_string(),
+                // An explicit refspec also updates the tracking ref in single-branch (shallow) clones
+                format!("+refs/heads/{}:refs/remotes/{}/{}", branch, remote, branch),
+            ])
+            .current_dir(repo_path)
+            .output()
+            .map_err(|e| BunyanError::Git(format!("Failed to run git fetch: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(classify_failure("git fetch", &String::from_utf8_lossy(&output.stderr)));
         }
 
         Ok(())
     }
 
+    fn ref_exists(&self, repo_path: &str, reference: &str) -> Result<bool> {
+        let output = Command::new("git")
+            .args(["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", reference)])
+            .current_dir(repo_path)
+            .output()
+            .map_err(|e| BunyanError::Git(format!("Failed to run git rev-parse: {}", e)))?;
+
+        Ok(output.status.success())
+    }
+
     fn worktree_remove(&self, repo_path: &str, worktree_path: &str, force: bool) -> Result<()> {
         let mut args = vec!["worktree", "remove"];
         if force {
@@ -68,7 +638,7 @@ impl GitOps for RealGit {
         Ok(())
     }
 
-    fn worktree_list(&self, repo_path: &str) -> Result<Vec<String>> {
+    fn worktree_list(&self, repo_path: &str) -> Result<Vec<WorktreeEntry>> {
         let output = Command::new("git")
             .args(["worktree", "list", "--porcelain"])
             .current_dir(repo_path)
@@ -83,13 +653,613 @@ impl GitOps for RealGit {
             )));
         }
 
+        Ok(parse_worktree_list(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn inspect_clone(&self, path: &str, remote: &str) -> Result<CloneInfo> {
+        let not_a_clone = |why: &str| BunyanError::InvalidInput(format!("{} {}", path, why));
+        if !std::path::Path::new(path).is_dir() {
+            return Err(not_a_clone("is not a directory"));
+        }
+
+        let output = run_git(path, &["rev-parse", "--show-toplevel", "--git-dir", "--git-common-dir"])?;
+        if !output.status.success() {
+            return Err(not_a_clone("is not a git repository"));
+        }
         let stdout = String::from_utf8_lossy(&output.stdout);
-        let worktrees = stdout
+        let mut lines = stdout.lines();
+        let (toplevel, git_dir, common_dir) = (lines.next(), lines.next(), lines.next());
+        let same = |a: &str, b: &str| {
+            std::fs::canonicalize(a).ok().zip(std::fs::canonicalize(b).ok()).is_some_and(|(a, b)| a == b)
+        };
+        if !toplevel.is_some_and(|t| same(t, path)) {
+            return Err(not_a_clone("is inside a git repository but not its top level"));
+        }
+        if git_dir != common_dir {
+            return Err(not_a_clone("is a linked worktree, not a main clone"));
+        }
+
+        let output = run_git(path, &["remote", "get-url", remote])?;
+        if !output.status.success() {
+            return Err(not_a_clone(&format!("has no remote named '{}'", remote)));
+        }
+        let remote_url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+        // Prefer the remote's HEAD; fall back to whatever the clone has checked out
+        let prefix = format!("{}/", remote);
+        let remote_head = format!("refs/remotes/{}/HEAD", remote);
+        let from_remote = run_git(path, &["symbolic-ref", "--short", &remote_head])?;
+        let default_branch = if from_remote.status.success() {
+            let full = String::from_utf8_lossy(&from_remote.stdout).trim().to_string();
+            full.strip_prefix(&prefix).unwrap_or(&full).to_string()
+        } else {
+            let head = run_git(path, &["symbolic-ref", "--short", "HEAD"])?;
+            if !head.status.success() {
+                return Err(not_a_clone("has a detached HEAD and no remote HEAD to take the default branch from"));
+            }
+            String::from_utf8_lossy(&head.stdout).trim().to_string()
+        };
+
+        Ok(CloneInfo {
+            remote_url,
+            default_branch,
+        })
+    }
+
+    fn worktree_prune(&self, repo_path: &str) -> Result<()> {
+        let output = run_git(repo_path, &["worktree", "prune"])?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(BunyanError::Git(format!("git worktree prune failed: {}", stderr)));
+        }
+        Ok(())
+    }
+
+    fn working_tree_status(&self, worktree_path: &str) -> Result<WorkingTreeStatus> {
+        let output = run_git(worktree_path, &["status", "--porcelain=v2", "--branch"])?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(BunyanError::Git(format!("git status failed: {}", stderr)));
+        }
+        Ok(parse_status_v2(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn ahead_behind(&self, worktree_path: &str, base: &str) -> Result<Option<(u32, u32)>> {
+        if !self.ref_exists(worktree_path, base)? {
+            return Ok(None);
+        }
+        let range = format!("HEAD...{}", base);
+        let output = run_git(worktree_path, &["rev-list", "--left-right", "--count", &range])?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(BunyanError::Git(format!("git rev-list failed: {}", stderr)));
+        }
+        Ok(parse_left_right(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn last_commit(&self, worktree_path: &str) -> Result<Option<CommitSummary>> {
+        let output = run_git(worktree_path, &["log", "-1", "--format=%H%x1f%s%x1f%an%x1f%cI"])?;
+        // `git log` fails on a branch with no commits yet
+        if !output.status.success() {
+            return Ok(None);
+        }
+        Ok(parse_commit_line(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn merge_base(&self, worktree_path: &str, other: &str) -> Result<String> {
+        let output = run_git(worktree_path, &["merge-base", "HEAD", other])?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(BunyanError::Git(format!(
+                "git merge-base failed for {}: {}",
+                other, stderr
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn diff_stat(&self, worktree_path: &str, from: &str, paths: &[String]) -> Result<Vec<DiffFileStat>> {
+        let output = run_diff(worktree_path, &diff_args("--numstat", from, paths), paths)?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(BunyanError::Git(format!("git diff failed: {}", stderr)));
+        }
+        Ok(parse_numstat(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn diff_patch(&self, worktree_path: &str, from: &str, paths: &[String]) -> Result<String> {
+        let output = run_diff(worktree_path, &diff_args("--patch", from, paths), paths)?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(BunyanError::Git(format!("git diff failed: {}", stderr)));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    fn unsaved_work(&self, worktree_path: &str) -> Result<UnsavedWork> {
+        let output = run_git(worktree_path, &["status", "--porcelain=v2"])?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(BunyanError::Git(format!("git status failed: {}", stderr)));
+        }
+        let (dirty, untracked) = parse_status_paths(&String::from_utf8_lossy(&output.stdout));
+
+        let output = run_git(worktree_path, &["log", "--format=%h %s", "HEAD", "--not", "--remotes"])?;
+        // `git log` fails on a branch with no commits yet, which has nothing to push
+        let unpushed = if output.status.success() {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(str::to_string)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(UnsavedWork {
+            dirty,
+            untracked,
+            unpushed,
+        })
+    }
+
+    fn is_merged(&self, repo_path: &str, rev: &str, into: &str) -> Result<bool> {
+        let output = run_git(repo_path, &["merge-base", "--is-ancestor", rev, into])?;
+        // Exit status 1 means "not an ancestor"; anything else is a real failure
+        match output.status.code() {
+            Some(0) => Ok(true),
+            Some(1) => Ok(false),
+            _ => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                Err(BunyanError::Git(format!("git merge-base failed: {}", stderr)))
+            }
+        }
+    }
+
+    fn delete_branch(&self, repo_path: &str, branch: &str) -> Result<()> {
+        let output = run_git(repo_path, &["branch", "-D", branch])?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(BunyanError::Git(format!("git branch -D failed: {}", stderr.trim())));
+        }
+        Ok(())
+    }
+
+    fn delete_remote_branch(&self, repo_path: &str, remote: &str, branch: &str) -> Result<()> {
+        let output = run_git(repo_path, &["push", remote, "--delete", branch])?;
+        if !output.status.success() {
+            return Err(classify_failure("git push --delete", &String::from_utf8_lossy(&output.stderr)));
+        }
+        Ok(())
+    }
+
+    fn commit(&self, worktree_path: &str, message: &str, paths: &[String], all: bool) -> Result<(CommitStatus, String)> {
+        let pathspec: Vec<&str> = paths.iter().map(String::as_str).collect();
+        let stage: Vec<&str> = if all {
+            vec!["add", "--update"]
+        } else if !paths.is_empty() {
+            [&["add", "--"][..], &pathspec].concat()
+        } else {
+            Vec::new()
+        };
+        if !stage.is_empty() {
+            let output = run_git(worktree_path, &stage)?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(BunyanError::Git(format!("git add failed: {}", stderr.trim())));
+            }
+        }
+
+        let staged = run_git(worktree_path, &[&["diff", "--cached", "--quiet", "--"][..], &pathspec].concat())?;
+        match staged.status.code() {
+            Some(0) => return Ok((CommitStatus::NothingToCommit, String::new())),
+            Some(1) => {}
+            _ => {
+                let stderr = String::from_utf8_lossy(&staged.stderr);
+                return Err(BunyanError::Git(format!("git diff failed: {}", stderr.trim())));
+            }
+        }
+
+        let output = run_git(worktree_path, &[&["commit", "--message", message, "--"][..], &pathspec].concat())?;
+        let text = combined_output(&output);
+        if output.status.success() {
+            Ok((CommitStatus::Committed, text))
+        } else if has_hook(worktree_path, &["pre-commit", "prepare-commit-msg", "commit-msg"]) {
+            Ok((CommitStatus::HookFailed, text))
+        } else {
+            Err(BunyanError::Git(format!("git commit failed: {}", text)))
+        }
+    }
+
+    fn push(&self, worktree_path: &str, remote: &str, branch: &str) -> Result<(PushStatus, String)> {
+        let refspec = format!("HEAD:refs/heads/{}", branch);
+        let output = run_git(worktree_path, &["push", "--porcelain", "--set-upstream", remote, &refspec])?;
+        let text = combined_output(&output);
+        match parse_push_porcelain(&String::from_utf8_lossy(&output.stdout)) {
+            Some(PushStatus::Rejected) => Ok((PushStatus::Rejected, text)),
+            Some(status) if output.status.success() => Ok((status, text)),
+            None if !output.status.success() && has_hook(worktree_path, &["pre-push"]) => {
+                Ok((PushStatus::HookFailed, text))
+            }
+            _ if output.status.success() => Ok((PushStatus::Pushed, text)),
+            _ => Err(classify_failure("git push", &text)),
+        }
+    }
+
+    fn sparse_checkout(&self, worktree_path: &str) -> Result<Option<Vec<String>>> {
+        let output = run_git(worktree_path, &["config", "--bool", "core.sparseCheckout"])?;
+        if String::from_utf8_lossy(&output.stdout).trim() != "true" {
+            return Ok(None);
+        }
+        let output = run_git(worktree_path, &["sparse-checkout", "list"])?;
+        if !output.status.success() {
+            return Err(classify_failure("git sparse-checkout list", &String::from_utf8_lossy(&output.stderr)));
+        }
+        Ok(Some(
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(str::to_string)
+                .collect(),
+        ))
+    }
+
+    fn sparse_add(&self, worktree_path: &str, paths: &[String]) -> Result<()> {
+        let mut args = vec!["sparse-checkout", "add", "--"];
+        args.extend(paths.iter().map(String::as_str));
+        let output = run_git(worktree_path, &args)?;
+        if !output.status.success() {
+            return Err(classify_failure("git sparse-checkout add", &String::from_utf8_lossy(&output.stderr)));
+        }
+        Ok(())
+    }
+
+    fn sparse_disable(&self, worktree_path: &str) -> Result<()> {
+        let output = run_git(worktree_path, &["sparse-checkout", "disable"])?;
+        if !output.status.success() {
+            return Err(classify_failure(
+                "git sparse-checkout disable",
+                &String::from_utf8_lossy(&output.stderr),
+            ));
+        }
+        Ok(())
+    }
+
+    fn submodule_update(&self, worktree_path: &str) -> Result<()> {
+        let output = run_git(worktree_path, &["submodule", "update", "--init", "--recursive"])?;
+        if !output.status.success() {
+            return Err(classify_failure("git submodule update", &String::from_utf8_lossy(&output.stderr)));
+        }
+        Ok(())
+    }
+
+    fn lfs_pull(&self, worktree_path: &str) -> Result<()> {
+        let output = run_git(worktree_path, &["lfs", "pull"])?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains("is not a git command") {
+                return Err(BunyanError::Git("git-lfs is not installed".to_string()));
+            }
+            return Err(classify_failure("git lfs pull", &stderr));
+        }
+        Ok(())
+    }
+
+    fn create_checkpoint(&self, worktree_path: &str, reference: &str, message: &str) -> Result<CheckpointEntry> {
+        let git = |args: &[&str], index: Option<&str>| -> Result<String> {
+            let mut command = Command::new("git");
+            command
+                .args(args)
+                .current_dir(worktree_path)
+                // Checkpoints are Bunyan's commits, whoever the user is configured as
+                .env("GIT_AUTHOR_NAME", "Bunyan")
+                .env("GIT_AUTHOR_EMAIL", "bunyan@localhost")
+                .env("GIT_COMMITTER_NAME", "Bunyan")
+                .env("GIT_COMMITTER_EMAIL", "bunyan@localhost");
+            if let Some(index) = index {
+                command.env("GIT_INDEX_FILE", index);
+            }
+            let output = command
+                .output()
+                .map_err(|e| BunyanError::Git(format!("Failed to run git {}: {}", args[0], e)))?;
+            if !output.status.success() {
+                return Err(classify_failure(&format!("git {}", args[0]), &String::from_utf8_lossy(&output.stderr)));
+            }
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+
+        let head = git(&["rev-parse", "--verify", "--quiet", "HEAD^{commit}"], None)
+            .map_err(|_| BunyanError::InvalidInput("Nothing has been committed yet".to_string()))?;
+        // Stage everything into a copy of the index, so staged changes and
+        // sparse-checkout state carry over without touching the real one
+        let index = Path::new(worktree_path).join(git(&["rev-parse", "--git-path", "index"], None)?);
+        // Named per call so concurrent checkpoints of one worktree don't share it
+        let scratch = index.with_file_name(format!("bunyan-checkpoint-index-{}", uuid::Uuid::new_v4()));
+        let scratch_str = scratch.to_string_lossy().into_owned();
+        let copied = if index.exists() {
+            std::fs::copy(&index, &scratch)
+                .map(|_| ())
+                .map_err(|e| BunyanError::Git(format!("Cannot copy the index: {}", e)))
+        } else {
+            Ok(())
+        };
+        let tree = copied
+            .and_then(|_| git(&["add", "--all"], Some(&scratch_str)))
+            .and_then(|_| git(&["write-tree"], Some(&scratch_str)));
+        let _ = std::fs::remove_file(&scratch);
+        let tree = tree?;
+
+        let sha = git(&["commit-tree", &tree, "-p", &head, "-m", message], None)?;
+        // The all-zero old value refuses to overwrite an existing checkpoint
+        git(&["update-ref", "-m", "bunyan: checkpoint", reference, &sha, &"0".repeat(40)], None)?;
+        let created_at = git(&["show", "-s", "--format=%cI", &sha], None)?;
+        Ok(CheckpointEntry {
+            reference: reference.to_string(),
+            sha,
+            head,
+            message: message.to_string(),
+            created_at,
+        })
+    }
+
+    fn list_checkpoints(&self, repo_path: &str, prefix: &str) -> Result<Vec<CheckpointEntry>> {
+        let format = format!("--format={}", CHECKPOINT_FORMAT);
+        let output = run_git(repo_path, &["for-each-ref", "--sort=-refname", &format, prefix])?;
+        if !output.status.success() {
+            return Err(classify_failure("git for-each-ref", &String::from_utf8_lossy(&output.stderr)));
+        }
+        Ok(parse_checkpoint_list(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn restore_checkpoint(&self, worktree_path: &str, sha: &str) -> Result<()> {
+        let parent = format!("{}^", sha);
+        for args in [
+            vec!["reset", "--hard", "--quiet", parent.as_str()],
+            vec!["clean", "-d", "--force", "--quiet"],
+            vec!["read-tree", "-u", "--reset", sha],
+            // Leave the checkpoint's changes unstaged on top of HEAD
+            vec!["reset", "--quiet"],
+        ] {
+            let output = run_git(worktree_path, &args)?;
+            if !output.status.success() {
+                return Err(classify_failure(&format!("git {}", args[0]), &String::from_utf8_lossy(&output.stderr)));
+            }
+        }
+        Ok(())
+    }
+
+    fn sync_onto(&self, worktree_path: &str, upstream: &str, strategy: &SyncStrategy, abort_on_conflict: bool) -> Result<SyncOutcome> {
+        let (command, args) = match strategy {
+            SyncStrategy::Rebase => ("rebase", vec!["rebase", upstream]),
+            SyncStrategy::Merge => ("merge", vec!["merge", "--no-edit", upstream]),
+        };
+        let output = run_git(worktree_path, &args)?;
+        let text = combined_output(&output);
+        if output.status.success() {
+            return Ok(SyncOutcome {
+                status: SyncStatus::Synced,
+                conflicts: Vec::new(),
+                output: text,
+            });
+        }
+
+        let unmerged = run_git(worktree_path, &["diff", "--name-only", "--diff-filter=U"])?;
+        let conflicts: Vec<String> = String::from_utf8_lossy(&unmerged.stdout)
             .lines()
-            .filter(|line| line.starts_with("worktree "))
-            .map(|line| line.trim_start_matches("worktree ").to_string())
+            .map(str::to_string)
             .collect();
+        if abort_on_conflict {
+            // Also undoes a rebase that stopped for some other reason
+            let abort = run_git(worktree_path, &[command, "--abort"])?;
+            if !conflicts.is_empty() && !abort.status.success() {
+                return Err(classify_failure(
+                    &format!("git {} --abort", command),
+                    &String::from_utf8_lossy(&abort.stderr),
+                ));
+            }
+        }
+        if conflicts.is_empty() {
+            return Err(classify_failure(&format!("git {}", command), &text));
+        }
+        Ok(SyncOutcome {
+            status: SyncStatus::Conflicted,
+            conflicts,
+            output: text,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_v2_counts_each_kind() {
+        let output = "# branch.oid 1234\n\
+# branch.head fix\n\
+1 M. N... 100644 100644 100644 aaa bbb src/staged.rs\n\
+1 .M N... 100644 100644 100644 aaa bbb src/dirty.rs\n\
+1 MM N... 100644 100644 100644 aaa bbb src/both.rs\n\
+2 R. N... 100644 100644 100644 aaa bbb R100 new.rs\told.rs\n\
+u UU N... 100644 100644 100644 100644 aaa bbb ccc conflict.rs\n\
+? notes.txt\n";
+        let status = parse_status_v2(output);
+        assert_eq!(
+            status,
+            WorkingTreeStatus {
+                branch: Some("fix".to_string()),
+                staged: 3,
+                dirty: 2,
+                untracked: 1,
+                conflicted: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn failures_are_classified_from_stderr() {
+        let err = classify_failure("git worktree add", "fatal: a branch named 'fix' already exists\n");
+        assert!(matches!(err, BunyanError::BranchExists(m) if m == "a branch named 'fix' already exists"));
+        let err = classify_failure("git clone", "fatal: destination path 'app' already exists and is not an empty directory.");
+        assert!(matches!(err, BunyanError::PathExists(_)));
+        let err = classify_failure("git fetch", "git@github.com: Permission denied (publickey).\nfatal: Could not read from remote repository.");
+        assert!(matches!(err, BunyanError::GitAuth(_)));
+        let err = classify_failure("git fetch", "fatal: couldn't find remote ref nope");
+        assert!(matches!(err, BunyanError::Git(m) if m.starts_with("git fetch failed")));
+    }
+
+    #[test]
+    fn status_paths_lists_changed_and_untracked() {
+        let output = "1 .M N... 100644 100644 100644 aaa bbb src/my file.rs\n\
+2 R. N... 100644 100644 100644 aaa bbb R100 new.rs\told.rs\n\
+u UU N... 100644 100644 100644 100644 aaa bbb ccc conflict.rs\n\
+? notes.txt\n";
+        let (changed, untracked) = parse_status_paths(output);
+        assert_eq!(changed, vec!["src/my file.rs", "new.rs", "conflict.rs"]);
+        assert_eq!(untracked, vec!["notes.txt"]);
+    }
+
+    #[test]
+    fn push_porcelain_classifies_ref_lines() {
+        let pushed = "To github.com:org/app.git\n*\tHEAD:refs/heads/fix\t[new branch]\nDone\n";
+        assert_eq!(parse_push_porcelain(pushed), Some(PushStatus::Pushed));
+        let same = "To github.com:org/app.git\n=\tHEAD:refs/heads/fix\t[up to date]\nDone\n";
+        assert_eq!(parse_push_porcelain(same), Some(PushStatus::UpToDate));
+        let rejected = "To github.com:org/app.git\n!\tHEAD:refs/heads/fix\t[rejected] (fetch first)\nDone\n";
+        assert_eq!(parse_push_porcelain(rejected), Some(PushStatus::Rejected));
+        assert_eq!(parse_push_porcelain("To github.com:org/app.git\n"), None);
+    }
+
+    #[test]
+    fn status_v2_detached_head_has_no_branch() {
+        let status = parse_status_v2("# branch.oid 1234\n# branch.head (detached)\n");
+        assert_eq!(status.branch, None);
+        assert_eq!(status.staged + status.dirty + status.untracked, 0);
+    }
+
+    #[test]
+    fn left_right_counts_parse() {
+        assert_eq!(parse_left_right("3\t7\n"), Some((3, 7)));
+        assert_eq!(parse_left_right(""), None);
+    }
+
+    #[test]
+    fn commit_line_splits_on_separator() {
+        let commit = parse_commit_line("abc123\x1fFix the thing\x1fAda\x1f2024-05-01T10:00:00+00:00\n").unwrap();
+        assert_eq!(commit.sha, "abc123");
+        assert_eq!(commit.summary, "Fix the thing");
+        assert_eq!(commit.author, "Ada");
+        assert_eq!(commit.committed_at, "2024-05-01T10:00:00+00:00");
+    }
+
+    #[test]
+    fn numstat_parses_text_and_binary_files() {
+        let files = parse_numstat("12\t3\tsrc/lib.rs\n-\t-\tlogo.png\n");
+        assert_eq!(files.len(), 2);
+        assert_eq!((files[0].additions, files[0].deletions), (12, 3));
+        assert!(!files[0].binary);
+        assert_eq!(files[1].path, "logo.png");
+        assert!(files[1].binary);
+    }
+
+    #[test]
+    fn diff_args_put_paths_after_separator() {
+        let paths = vec!["src".to_string(), "-weird".to_string()];
+        assert_eq!(
+            diff_args("--numstat", "abc", &paths),
+            vec!["diff", "--no-renames", "--no-color", "--numstat", "abc", "--", "src", "-weird"]
+        );
+    }
+
+    #[test]
+    fn worktree_list_parses_branches_and_detached() {
+        let output = "worktree /repos/app\nHEAD aaa\nbranch refs/heads/main\n\n\
+worktree /workspaces/app/fix\nHEAD bbb\nbranch refs/heads/feature/fix\n\n\
+worktree /workspaces/app/probe\nHEAD ccc\ndetached\n";
+        let entries = parse_worktree_list(output);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].branch.as_deref(), Some("main"));
+        assert_eq!(entries[1].path, "/workspaces/app/fix");
+        assert_eq!(entries[1].branch.as_deref(), Some("feature/fix"));
+        assert_eq!(entries[2].branch, None);
+    }
+
+    #[test]
+    fn clone_args_add_filter_depth_and_sparse() {
+        let plain = clone_args("git@example.com:a/b.git", "/r/b", &CloneOptions::default());
+        assert_eq!(plain, vec!["clone", "--", "git@example.com:a/b.git", "/r/b"]);
+
+        let options = CloneOptions::from_config(Some(&CheckoutConfig {
+            filter: Some("blob:none".to_string()),
+            depth: Some(10),
+            sparse: vec!["services/api".to_string()],
+            ..Default::default()
+        }));
+        assert_eq!(
+            clone_args("git@example.com:a/b.git", "/r/b", &options),
+            vec!["clone", "--filter=blob:none", "--depth=10", "--sparse", "--", "git@example.com:a/b.git", "/r/b"]
+        );
+    }
+
+    #[test]
+    fn clone_filters_and_sparse_dirs_are_checked() {
+        for ok in ["blob:none", "blob:limit=1m", "blob:limit=4096", "tree:0"] {
+            assert!(is_clone_filter(ok), "{}", ok);
+        }
+        for bad in ["", "blob", "blob:limit=", "blob:limit=1mb", "tree:x", "sparse:oid=abc"] {
+            assert!(!is_clone_filter(bad), "{}", bad);
+        }
+
+        assert!(check_sparse_dir("services/api").is_ok());
+        assert!(check_sparse_dir("libs/").is_ok());
+        for bad in ["", "/etc", "-x", "a/../b", "a//b", "./a", "src/*", "a[1]"] {
+            assert!(check_sparse_dir(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn checkpoint_list_splits_fields_and_keeps_tabs_in_subject() {
+        let output = "refs/bunyan/checkpoints/ws1/20260102T000000000Z\tbbb\taaa\t2026-01-02T00:00:00+00:00\tBefore\tClaude\n\
+refs/bunyan/checkpoints/ws1/20260101T000000000Z\tccc\taaa\t2026-01-01T00:00:00+00:00\t\n";
+        let entries = parse_checkpoint_list(output);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].sha, "bbb");
+        assert_eq!(entries[0].head, "aaa");
+        assert_eq!(entries[0].message, "Before\tClaude");
+        assert_eq!(entries[1].created_at, "2026-01-01T00:00:00+00:00");
+        assert_eq!(entries[1].message, "");
+    }
+
+    #[test]
+    fn new_branch_args_include_base() {
+        let spec = WorktreeSpec::NewBranch {
+            branch: "fix".to_string(),
+            base: Some("origin/main".to_string()),
+        };
+        assert_eq!(
+            worktree_add_args("/ws/fix", &spec),
+            vec!["worktree", "add", "--no-track", "-b", "fix", "/ws/fix", "origin/main"]
+        );
+    }
+
+    #[test]
+    fn existing_branch_args_check_out_without_creating() {
+        let spec = WorktreeSpec::Existing {
+            branch: "feature".to_string(),
+        };
+        assert_eq!(
+            worktree_add_args("/ws/feature", &spec),
+            vec!["worktree", "add", "/ws/feature", "feature"]
+        );
+    }
 
-        Ok(worktrees)
+    #[test]
+    fn track_args_set_upstream() {
+        let spec = WorktreeSpec::Track {
+            branch: "feature".to_string(),
+            upstream: "origin/feature".to_string(),
+        };
+        assert_eq!(
+            worktree_add_args("/ws/feature", &spec),
+            vec!["worktree", "add", "--track", "-b", "feature", "/ws/feature", "origin/feature"]
+        );
     }
 }
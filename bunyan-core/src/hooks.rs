@@ -0,0 +1,362 @@
+use std::io::Read;
+use std::os::unix::process::CommandExt;
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use rusqlite::Connection;
+
+#[cfg(feature = "server")]
+use crate::db::{self, hooks::NewHookRun};
+#[cfg(feature = "server")]
+use crate::docker;
+#[cfg(feature = "server")]
+use crate::error::{BunyanError, Result};
+#[cfg(feature = "server")]
+use crate::models::{ContainerMode, EventKind, HookRun};
+use crate::models::{HookKind, Repo, Workspace};
+use crate::settings;
+#[cfg(feature = "server")]
+use crate::state::AppState;
+use crate::workspace;
+
+/// Output beyond this many bytes is dropped from the start of a hook's log.
+const MAX_OUTPUT: usize = 256 * 1024;
+
+/// How often a local hook is checked for having exited.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long to keep reading output after a hook exits. A child it left
+/// running in the background can hold stdout open indefinitely.
+const OUTPUT_DRAIN: Duration = Duration::from_millis(500);
+
+/// How a hook script ended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HookOutput {
+    /// Exit status, or None if the script could not be started or was killed
+    pub exit_code: Option<i32>,
+    /// Combined stdout and stderr, trimmed to the last `MAX_OUTPUT` bytes
+    pub output: String,
+    /// Whether the script was killed for running past the hook timeout
+    pub timed_out: bool,
+}
+
+impl HookOutput {
+    /// A script that could not be started.
+    pub fn not_run(output: String) -> Self {
+        HookOutput { exit_code: None, output, timed_out: false }
+    }
+
+    /// A script killed at the timeout, keeping what it printed until then.
+    pub fn timed_out(partial: &str, timeout: Duration) -> Self {
+        HookOutput {
+            exit_code: None,
+            output: tail(&format!("{}\n[Killed after {}s: hook timeout]", partial.trim_end(), timeout.as_secs())),
+            timed_out: true,
+        }
+    }
+}
+
+/// The `hook_timeout` setting, or its default if unset or unreadable.
+pub fn timeout(conn: &Connection) -> Duration {
+    let default = || Duration::from_secs(30 * 60);
+    let Ok(setting) = settings::get_effective(conn, settings::HOOK_TIMEOUT) else {
+        return default();
+    };
+    workspace::parse_age(&setting.value)
+        .ok()
+        .and_then(|age| age.to_std().ok())
+        .unwrap_or_else(default)
+}
+
+/// The repo's script for a hook, if it has a non-blank one.
+pub fn script(repo: &Repo, hook: &HookKind) -> Option<String> {
+    let scripts = repo.config.as_ref()?.scripts.as_ref()?;
+    let script = match hook {
+        HookKind::PostCheckout => &scripts.post_checkout,
+        HookKind::Setup => &scripts.setup,
+        HookKind::Teardown => &scripts.teardown,
+    };
+    script.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(str::to_string)
+}
+
+/// Environment variables every hook script sees.
+pub fn env(repo: &Repo, ws: &Workspace, hook: &HookKind) -> Vec<(&'static str, String)> {
+    vec![
+        ("BUNYAN_HOOK", hook.as_str().to_string()),
+        ("BUNYAN_REPO_NAME", repo.name.clone()),
+        ("BUNYAN_REPO_PATH", repo.root_path.clone()),
+        ("BUNYAN_WORKSPACE_ID", ws.id.clone()),
+        ("BUNYAN_WORKSPACE_NAME", ws.directory_name.clone()),
+        ("BUNYAN_BRANCH", ws.branch.clone()),
+    ]
+}
+
+/// `sh -c` argument that runs the script with stderr merged into stdout, so
+/// the captured log keeps both streams in order.
+pub fn shell_script(command: &str) -> String {
+    format!("exec 2>&1\n{}", command)
+}
+
+/// Run a hook script with `sh` in `dir` on this machine. The script runs in
+/// its own process group, which is killed as a whole once `timeout` passes.
+/// The hook is done when the script exits, even if something it started in
+/// the background is still running.
+pub fn run_local(command: &str, dir: &str, env: &[(&'static str, String)], timeout: Duration) -> HookOutput {
+    let spawned = Command::new("sh")
+        .args(["-c", &shell_script(command)])
+        .current_dir(dir)
+        .envs(env.iter().map(|(k, v)| (*k, v.as_str())))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .process_group(0)
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => return HookOutput::not_run(format!("Failed to run hook: {}", e)),
+    };
+
+    // Read on another thread so output printed before a timeout is kept
+    let captured = Arc::new(Mutex::new(Vec::new()));
+    let (done, finished) = mpsc::channel();
+    if let Some(mut stdout) = child.stdout.take() {
+        let captured = captured.clone();
+        std::thread::spawn(move || {
+            let mut chunk = [0u8; 8192];
+            while let Ok(n @ 1..) = stdout.read(&mut chunk) {
+                captured.lock().unwrap_or_else(|e| e.into_inner()).extend_from_slice(&chunk[..n]);
+            }
+            let _ = done.send(());
+        });
+    }
+
+    let deadline = Instant::now() + timeout;
+    let mut timed_out = false;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Ok(status),
+            Ok(None) if Instant::now() >= deadline => {
+                // SAFETY: kill(2) with the negated pid of a child we have not reaped
+                // signals only that child's process group.
+                unsafe {
+                    libc::kill(-(child.id() as i32), libc::SIGKILL);
+                }
+                timed_out = true;
+                break child.wait();
+            }
+            Ok(None) => std::thread::sleep(POLL_INTERVAL),
+            Err(e) => break Err(e),
+        }
+    };
+    let _ = finished.recv_timeout(OUTPUT_DRAIN);
+    let output = String::from_utf8_lossy(&captured.lock().unwrap_or_else(|e| e.into_inner())).into_owned();
+    match status {
+        _ if timed_out => HookOutput::timed_out(&output, timeout),
+        Ok(status) => HookOutput { exit_code: status.code(), output: tail(&output), timed_out: false },
+        Err(e) => HookOutput::not_run(format!("Failed to wait for hook: {}", e)),
+    }
+}
+
+/// Keep the end of a long log, where failures usually are.
+pub fn tail(output: &str) -> String {
+    if output.len() <= MAX_OUTPUT {
+        return output.to_string();
+    }
+    let mut start = output.len() - MAX_OUTPUT;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    format!("[{} bytes truncated]\n{}", start, &output[start..])
+}
+
+/// Run one of the repo's hooks in a workspace and store its output. Returns
+/// None when the repo has no script for the hook. `post_checkout` always runs
+/// on the host; `setup` and `teardown` run in the workspace's container in
+/// container mode. A script that fails, times out, or cannot be started is
+/// still a run.
+#[cfg(feature = "server")]
+pub async fn run(
+    state: &AppState,
+    repo: &Repo,
+    ws: &Workspace,
+    ws_path: &str,
+    hook: HookKind,
+) -> Result<Option<HookRun>> {
+    Ok(execute(state, repo, ws, ws_path, hook).await?.map(|(run, _)| run))
+}
+
+/// Run a hook that must succeed: if it fails, the workspace moves to `failed`
+/// and the failure is returned.
+#[cfg(feature = "server")]
+pub async fn run_required(
+    state: &AppState,
+    repo: &Repo,
+    ws: &Workspace,
+    ws_path: &str,
+    hook: HookKind,
+) -> Result<()> {
+    match execute(state, repo, ws, ws_path, hook).await? {
+        Some((run, outcome)) if !run.success => {
+            let reason = format!("{} hook {} (hook run {})", run.hook.as_str(), outcome, run.id);
+            workspace::record_failure_async(state, &ws.id, &reason).await;
+            Err(BunyanError::Process(reason))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// `run`, also returning how the script ended in words.
+#[cfg(feature = "server")]
+async fn execute(
+    state: &AppState,
+    repo: &Repo,
+    ws: &Workspace,
+    ws_path: &str,
+    hook: HookKind,
+) -> Result<Option<(HookRun, String)>> {
+    let Some(command) = script(repo, &hook) else {
+        return Ok(None);
+    };
+    let env = env(repo, ws, &hook);
+    let limit = state.db.call(|conn| Ok(timeout(conn))).await?;
+    let started_at = chrono::Utc::now().to_rfc3339();
+
+    let in_container = hook != HookKind::PostCheckout && ws.container_mode == ContainerMode::Container;
+    let output = if in_container {
+        match &ws.container_id {
+            Some(container_id) => {
+                let workdir = docker::container_workdir(&ws.directory_name);
+                let result = async {
+                    docker::start_container(container_id).await?;
+                    docker::exec_script(container_id, &command, &workdir, &env, limit).await
+                }
+                .await;
+                result.unwrap_or_else(|e| HookOutput::not_run(format!("Failed to run hook in container: {}", e)))
+            }
+            None => HookOutput::not_run("Workspace has no container to run the hook in".to_string()),
+        }
+    } else {
+        let (command, ws_path) = (command.clone(), ws_path.to_string());
+        tokio::task::spawn_blocking(move || run_local(&command, &ws_path, &env, limit))
+            .await
+            .map_err(|e| BunyanError::Process(e.to_string()))?
+    };
+
+    let outcome = outcome(&output);
+    let new_run = NewHookRun {
+        workspace_id: ws.id.clone(),
+        hook,
+        command,
+        exit_code: output.exit_code,
+        output: output.output,
+        started_at,
+        finished_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let run = state.db.call(move |conn| db::hooks::record(conn, &new_run)).await?;
+    let message = format!("{} {} (run {})", run.hook.as_str(), outcome, run.id);
+    db::events::audit_async(&state.db, EventKind::HookRan, Some(&repo.id), Some(&ws.id), Some(&message)).await;
+    Ok(Some((run, outcome)))
+}
+
+#[cfg(feature = "server")]
+fn outcome(output: &HookOutput) -> String {
+    match output.exit_code {
+        Some(code) => format!("exited with status {}", code),
+        None if output.timed_out => "timed out".to_string(),
+        None => "could not run".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{RepoConfig, RepoScripts};
+
+    const LONG: Duration = Duration::from_secs(60);
+
+    fn make_repo(scripts: RepoScripts) -> Repo {
+        Repo {
+            id: "r1".to_string(),
+            name: "app".to_string(),
+            remote_url: "git@github.com:org/app.git".to_string(),
+            default_branch: "main".to_string(),
+            root_path: "/repos/app".to_string(),
+            remote: "origin".to_string(),
+            display_order: 0,
+            config: Some(RepoConfig {
+                scripts: Some(scripts),
+                ..Default::default()
+            }),
+            config_error: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn script_skips_blank_and_missing_hooks() {
+        let repo = make_repo(RepoScripts {
+            setup: Some("  npm install\n".to_string()),
+            teardown: Some("   ".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(script(&repo, &HookKind::Setup).as_deref(), Some("npm install"));
+        assert_eq!(script(&repo, &HookKind::Teardown), None);
+        assert_eq!(script(&repo, &HookKind::PostCheckout), None);
+    }
+
+    #[test]
+    fn run_local_merges_output_and_reports_exit_code() {
+        let dir = std::env::temp_dir();
+        let env = [("BUNYAN_HOOK", "setup".to_string())];
+        let out = run_local("echo out; echo err >&2; echo $BUNYAN_HOOK; exit 3", dir.to_str().unwrap(), &env, LONG);
+        assert_eq!(out.exit_code, Some(3));
+        assert_eq!(out.output, "out\nerr\nsetup\n");
+
+        let missing = run_local("true", "/nonexistent/bunyan-hook-dir", &[], LONG);
+        assert_eq!(missing.exit_code, None);
+        assert!(missing.output.starts_with("Failed to run hook"));
+    }
+
+    #[test]
+    fn run_local_kills_the_script_and_its_children_at_the_timeout() {
+        let dir = std::env::temp_dir();
+        let started = std::time::Instant::now();
+        let out = run_local("echo started; sleep 30 & sleep 30", dir.to_str().unwrap(), &[], Duration::from_millis(300));
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(out.timed_out);
+        assert_eq!(out.exit_code, None);
+        assert!(out.output.starts_with("started\n"));
+        assert!(out.output.ends_with("[Killed after 0s: hook timeout]"));
+    }
+
+    #[test]
+    fn run_local_finishes_when_the_script_exits_despite_a_background_child() {
+        let dir = std::env::temp_dir();
+        let started = std::time::Instant::now();
+        let out = run_local("echo started; sleep 5 & echo done", dir.to_str().unwrap(), &[], LONG);
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert!(!out.timed_out);
+        assert_eq!(out.exit_code, Some(0));
+        assert_eq!(out.output, "started\ndone\n");
+    }
+
+    #[test]
+    fn timeout_reads_the_setting() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::schema::initialize_database(&conn).unwrap();
+        assert_eq!(timeout(&conn), Duration::from_secs(30 * 60));
+        settings::set(&conn, settings::HOOK_TIMEOUT, "90s", false).unwrap();
+        assert_eq!(timeout(&conn), Duration::from_secs(90));
+    }
+
+    #[test]
+    fn tail_keeps_the_end_of_long_output() {
+        assert_eq!(tail("short"), "short");
+        let long = format!("{}end", "x".repeat(MAX_OUTPUT));
+        let kept = tail(&long);
+        assert!(kept.starts_with("[3 bytes truncated]\n"));
+        assert!(kept.ends_with("xend"));
+    }
+}
@@ -1,19 +1,24 @@
 pub mod models;
+pub mod config;
 pub mod error;
 pub mod state;
 pub mod db;
 pub mod git;
+pub mod native_git;
 pub mod tmux;
 pub mod terminal;
 pub mod editor;
 pub mod docker;
 pub mod workspace;
+pub mod hooks;
 pub mod sessions;
+pub mod settings;
+pub mod doctor;
+pub mod forge;
 
 #[cfg(feature = "server")]
 pub mod server;
 
-use rusqlite::Connection;
 use std::sync::Arc;
 
 pub fn get_db_path() -> std::path::PathBuf {
@@ -28,7 +33,6 @@ pub fn get_db_path() -> std::path::PathBuf {
 
 pub fn init_state() -> Arc<state::AppState> {
     let db_path = get_db_path();
-    let conn = Connection::open(&db_path).expect("Failed to open database");
-    db::initialize_database(&conn).expect("Failed to initialize database schema");
-    Arc::new(state::AppState::new(conn))
+    let db = db::Database::open(&db_path).expect("Failed to open database");
+    Arc::new(state::AppState::new(db))
 }
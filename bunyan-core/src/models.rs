@@ -11,7 +11,10 @@ pub struct Repo {
     pub root_path: String,
     pub remote: String,
     pub display_order: i32,
-    pub config: Option<serde_json::Value>,
+    pub config: Option<RepoConfig>,
+    /// Why the stored config could not be loaded; `config` is null while this is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_error: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -20,6 +23,8 @@ pub struct Repo {
 #[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 pub struct CreateRepoInput {
     pub name: String,
+    /// Ignored when adopting; the clone's remote URL is used instead
+    #[serde(default)]
     pub remote_url: String,
     pub root_path: String,
     #[serde(default = "default_branch")]
@@ -28,7 +33,13 @@ pub struct CreateRepoInput {
     pub remote: String,
     #[serde(default)]
     pub display_order: i32,
+    /// Checked against `RepoConfig`; field-level problems are rejected with 400
+    #[cfg_attr(feature = "server", schema(value_type = Option<RepoConfig>))]
     pub config: Option<serde_json::Value>,
+    /// Register the clone already at `root_path` instead of cloning, taking the
+    /// remote URL and default branch from it and importing its worktrees
+    #[serde(default)]
+    pub adopt: bool,
 }
 
 fn default_branch() -> String {
@@ -45,6 +56,8 @@ pub struct UpdateRepoInput {
     pub name: Option<String>,
     pub default_branch: Option<String>,
     pub display_order: Option<i32>,
+    /// Checked against `RepoConfig`; field-level problems are rejected with 400
+    #[cfg_attr(feature = "server", schema(value_type = Option<RepoConfig>))]
     pub config: Option<serde_json::Value>,
 }
 
@@ -52,25 +65,59 @@ pub struct UpdateRepoInput {
 #[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum WorkspaceState {
+    Creating,
     Ready,
+    Failed,
+    Suspended,
+    Archiving,
     Archived,
 }
 
 impl WorkspaceState {
     pub fn as_str(&self) -> &'static str {
         match self {
+            WorkspaceState::Creating => "creating",
             WorkspaceState::Ready => "ready",
+            WorkspaceState::Failed => "failed",
+            WorkspaceState::Suspended => "suspended",
+            WorkspaceState::Archiving => "archiving",
             WorkspaceState::Archived => "archived",
         }
     }
 
     pub fn from_db(s: &str) -> std::result::Result<Self, String> {
         match s {
+            "creating" => Ok(WorkspaceState::Creating),
             "ready" => Ok(WorkspaceState::Ready),
+            "failed" => Ok(WorkspaceState::Failed),
+            "suspended" => Ok(WorkspaceState::Suspended),
+            "archiving" => Ok(WorkspaceState::Archiving),
             "archived" => Ok(WorkspaceState::Archived),
             other => Err(format!("Invalid workspace state: {}", other)),
         }
     }
+
+    /// Whether a workspace may move from this state to `to`.
+    /// Archived is terminal; everything else can fail or start archiving.
+    pub fn can_transition_to(&self, to: &WorkspaceState) -> bool {
+        use WorkspaceState::*;
+        matches!(
+            (self, to),
+            (Creating, Ready)
+                | (Creating, Failed)
+                | (Ready, Suspended)
+                | (Ready, Archiving)
+                | (Ready, Failed)
+                | (Suspended, Ready)
+                | (Suspended, Archiving)
+                | (Suspended, Failed)
+                | (Failed, Creating)
+                | (Failed, Ready)
+                | (Failed, Archiving)
+                | (Archiving, Archived)
+                | (Archiving, Failed)
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -102,8 +149,113 @@ fn default_container_mode() -> ContainerMode {
     ContainerMode::Local
 }
 
+/// How a new workspace gets its branch.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum BranchMode {
+    /// Create a new branch from `base_ref` (default: `<remote>/<default_branch>`)
+    #[default]
+    New,
+    /// Check out a local branch that already exists
+    Existing,
+    /// Create a local branch tracking `<remote>/<branch>` (or `base_ref`)
+    Remote,
+}
+
+/// Per-repo configuration stored in `Repo.config`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct RepoConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<ContainerConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scripts: Option<RepoScripts>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkout: Option<CheckoutConfig>,
+    /// How the frontend runs the `run` script
+    #[serde(
+        rename = "runScriptMode",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub run_script_mode: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct RepoScripts {
+    /// Runs once a new workspace is ready, inside its container in container mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub setup: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run: Option<String>,
+    /// Runs before a workspace is archived, where `setup` ran
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub teardown: Option<String>,
+    /// Runs on the host right after a new worktree is checked out, before `setup`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_checkout: Option<String>,
+}
+
+/// How much of a large repo is downloaded and checked out. `filter` and
+/// `depth` apply when the repo is cloned; `sparse` to each new worktree.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct CheckoutConfig {
+    /// Partial clone filter, e.g. `blob:none`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    /// Shallow clone depth
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<u32>,
+    /// Directories (sparse-checkout cone) to check out; empty checks out everything
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sparse: Vec<String>,
+    /// Initialize and update submodules (recursively) in each new worktree
+    #[serde(default)]
+    pub submodules: bool,
+    /// Run `git lfs pull` in each new worktree
+    #[serde(default)]
+    pub lfs: bool,
+}
+
+/// A problem with one field of a repo config, e.g. `container.ports[1]`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct ConfigFieldError {
+    pub path: String,
+    pub message: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct ConfigValidation {
+    pub valid: bool,
+    pub errors: Vec<ConfigFieldError>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct ReorderReposInput {
+    /// Every repository ID, in the desired display order
+    pub ids: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct ValidateConfigInput {
+    /// Config to check; omit to check the repo's stored config
+    #[cfg_attr(feature = "server", schema(value_type = Option<RepoConfig>))]
+    pub config: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
 pub struct ContainerConfig {
     pub enabled: bool,
     pub image: Option<String>,
@@ -122,10 +274,239 @@ pub struct Workspace {
     pub directory_name: String,
     pub branch: String,
     pub state: WorkspaceState,
+    /// Reason recorded with the most recent state transition
+    pub state_reason: Option<String>,
     pub container_mode: ContainerMode,
     pub container_id: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    /// When the workspace was archived; null unless it is archived
+    pub archived_at: Option<String>,
+    /// Short free-form summary of what the workspace is for
+    pub description: Option<String>,
+    /// Longer free-form notes
+    pub notes: Option<String>,
+    pub labels: Vec<String>,
+    /// Overrides merged over the repo config; see `GET /workspaces/{id}/config`
+    pub config: Option<WorkspaceConfig>,
+    /// What archiving did with the workspace's branch, if cleanup was requested
+    pub branch_cleanup: Option<BranchCleanup>,
+    /// Pull request opened for the workspace's branch, as last seen on the forge
+    pub pull_request: Option<PullRequest>,
+    /// Non-fatal problems from creating the workspace, e.g. a failed LFS pull
+    pub warnings: Vec<WorkspaceWarning>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum WarningKind {
+    Submodules,
+    Lfs,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct WorkspaceWarning {
+    pub kind: WarningKind,
+    /// What failed, with git's output
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PullRequestState {
+    Open,
+    Draft,
+    Merged,
+    Closed,
+}
+
+/// Combined result of a commit's CI checks and statuses.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ChecksState {
+    Pending,
+    Success,
+    Failure,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct PullRequest {
+    pub number: u64,
+    /// Web URL of the pull request
+    pub url: String,
+    pub title: String,
+    pub state: PullRequestState,
+    /// Source branch
+    pub head: String,
+    /// Target branch
+    pub base: String,
+    /// None when the head commit has no checks, or they were not fetched
+    pub checks: Option<ChecksState>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct CreatePullRequestInput {
+    /// Defaults to the subject of the branch's latest commit
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub body: Option<String>,
+    /// Target branch (default: the repo's `default_branch`)
+    #[serde(default)]
+    pub base: Option<String>,
+    #[serde(default)]
+    pub draft: bool,
+}
+
+/// What happened to one copy (local or remote) of an archived workspace's branch.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BranchOutcome {
+    Deleted,
+    /// Left alone on purpose, e.g. it is the repo's default branch
+    Kept,
+    /// Left alone because it is not merged into the default branch
+    NotMerged,
+    /// There was no such branch to delete
+    Missing,
+    /// Deletion was attempted and git refused; see `BranchCleanup.detail`
+    Failed,
+}
+
+/// Branch cleanup recorded on a workspace when it is archived.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct BranchCleanup {
+    /// None when local deletion was not requested
+    pub local: Option<BranchOutcome>,
+    /// None when remote deletion was not requested
+    pub remote: Option<BranchOutcome>,
+    /// Why a branch was kept or why deletion failed
+    pub detail: Option<String>,
+}
+
+/// Per-workspace settings that take precedence over the repo config and
+/// global settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct WorkspaceConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<ContainerOverrides>,
+    /// Editor ID used instead of the `preferred_editor` setting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+}
+
+/// Container settings a workspace can override. `image`, `shell` and
+/// `dangerously_skip_permissions` replace the repo's values; `ports` are added
+/// to the repo's and `env` entries are merged over the repo's.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ContainerOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ports: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<HashMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dangerously_skip_permissions: Option<bool>,
+}
+
+/// The configuration a workspace actually runs with: repo config with the
+/// workspace's overrides applied, and the editor resolved from settings.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct EffectiveWorkspaceConfig {
+    pub container: Option<ContainerConfig>,
+    pub scripts: Option<RepoScripts>,
+    pub editor: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct UpdateWorkspaceInput {
+    /// New description; an empty string clears it
+    pub description: Option<String>,
+    /// New notes; an empty string clears them
+    pub notes: Option<String>,
+    /// Replaces the workspace's full set of labels
+    pub labels: Option<Vec<String>>,
+    /// Replaces the workspace's config overrides; `{}` clears them
+    #[serde(default)]
+    #[cfg_attr(feature = "server", schema(value_type = Option<WorkspaceConfig>))]
+    pub config: Option<serde_json::Value>,
+}
+
+/// A recorded change of a workspace's lifecycle state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct WorkspaceTransition {
+    pub id: i64,
+    pub workspace_id: String,
+    /// Previous state, or null for the initial state on creation
+    pub from_state: Option<WorkspaceState>,
+    pub to_state: WorkspaceState,
+    pub reason: Option<String>,
+    pub created_at: String,
+}
+
+/// A repo lifecycle script (see `RepoScripts`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum HookKind {
+    PostCheckout,
+    Setup,
+    Teardown,
+}
+
+impl HookKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookKind::PostCheckout => "post_checkout",
+            HookKind::Setup => "setup",
+            HookKind::Teardown => "teardown",
+        }
+    }
+
+    pub fn from_db(s: &str) -> std::result::Result<Self, String> {
+        match s {
+            "post_checkout" => Ok(HookKind::PostCheckout),
+            "setup" => Ok(HookKind::Setup),
+            "teardown" => Ok(HookKind::Teardown),
+            other => Err(format!("Invalid hook: {}", other)),
+        }
+    }
+}
+
+/// A finished run of a lifecycle hook in a workspace.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct HookRun {
+    pub id: i64,
+    pub workspace_id: String,
+    pub hook: HookKind,
+    pub command: String,
+    /// Whether the script exited with status 0
+    pub success: bool,
+    /// Exit status, or null if the script could not be started, timed out, or was killed by a signal
+    pub exit_code: Option<i32>,
+    /// Combined stdout and stderr (the tail, if very long)
+    pub output: String,
+    pub started_at: String,
+    pub finished_at: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -136,6 +517,228 @@ pub struct CreateWorkspaceInput {
     pub branch: String,
     #[serde(default = "default_container_mode")]
     pub container_mode: ContainerMode,
+    #[serde(default)]
+    pub branch_mode: BranchMode,
+    /// Start point for `new`, or the remote-tracking ref for `remote`
+    #[serde(default)]
+    pub base_ref: Option<String>,
+    /// Config overrides, in place before the workspace's container is created
+    #[serde(default)]
+    #[cfg_attr(feature = "server", schema(value_type = Option<WorkspaceConfig>))]
+    pub config: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct CommitSummary {
+    pub sha: String,
+    pub summary: String,
+    pub author: String,
+    pub committed_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct DiffFileStat {
+    pub path: String,
+    pub additions: u32,
+    pub deletions: u32,
+    /// Binary files have no line counts
+    pub binary: bool,
+}
+
+/// Changes in a workspace's worktree (committed or not) since it diverged from `base`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct WorkspaceDiff {
+    pub workspace_id: String,
+    /// The ref compared against
+    pub base: String,
+    /// Merge-base commit of HEAD and `base`; the diff starts here
+    pub merge_base: String,
+    pub files: Vec<DiffFileStat>,
+    /// Unified diff; omitted in stat mode
+    pub patch: Option<String>,
+    /// Whether `patch` was cut at the size cap
+    pub truncated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct CommitInput {
+    pub message: String,
+    /// Stage and commit only these paths (relative to the worktree)
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Stage every modified or deleted tracked file first, like `git commit --all`
+    #[serde(default)]
+    pub all: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CommitStatus {
+    Committed,
+    NothingToCommit,
+    /// A pre-commit, prepare-commit-msg or commit-msg hook refused the commit
+    HookFailed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct CommitResult {
+    pub workspace_id: String,
+    pub status: CommitStatus,
+    /// The new commit, when one was made
+    pub commit: Option<CommitSummary>,
+    /// git's (and any hook's) output
+    pub output: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PushStatus {
+    Pushed,
+    UpToDate,
+    /// The remote refused the update, e.g. because it is not a fast-forward
+    Rejected,
+    /// A pre-push hook stopped the push
+    HookFailed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct PushResult {
+    pub workspace_id: String,
+    pub status: PushStatus,
+    pub remote: String,
+    pub branch: String,
+    /// git's (and any hook's) output
+    pub output: String,
+}
+
+/// How `POST /workspaces/{id}/sync` brings in the default branch.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SyncStrategy {
+    /// Replay the workspace's commits on top of the default branch
+    #[default]
+    Rebase,
+    /// Merge the default branch into the workspace's branch
+    Merge,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct SyncInput {
+    #[serde(default)]
+    pub strategy: SyncStrategy,
+    /// On conflict, leave the rebase or merge in progress instead of aborting it
+    #[serde(default)]
+    pub stop_on_conflict: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SyncStatus {
+    Synced,
+    /// The branch already contains the default branch; nothing was done
+    UpToDate,
+    Conflicted,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct SyncResult {
+    pub workspace_id: String,
+    pub status: SyncStatus,
+    pub strategy: SyncStrategy,
+    /// The ref synced onto, `<remote>/<default_branch>`
+    pub base: String,
+    /// Files with conflicts, when `status` is `conflicted`
+    pub conflicts: Vec<String>,
+    /// Whether the conflicted rebase or merge was aborted, leaving the worktree as it was
+    pub aborted: bool,
+    /// git's output
+    pub output: String,
+}
+
+/// A snapshot of a workspace's working tree, stored as a commit under
+/// `refs/bunyan/checkpoints/<workspace_id>/<id>`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct Checkpoint {
+    /// UTC timestamp name, e.g. `20260101T120000123Z`
+    pub id: String,
+    pub workspace_id: String,
+    /// The checkpoint commit
+    pub sha: String,
+    /// The commit HEAD was at when the checkpoint was taken
+    pub head: String,
+    pub message: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct CreateCheckpointInput {
+    /// Defaults to "Checkpoint"
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct RestoreCheckpointResult {
+    pub restored: Checkpoint,
+    /// Checkpoint of the state the restore replaced, to undo it with
+    pub backup: Checkpoint,
+}
+
+/// Git state of a workspace's worktree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct GitStatus {
+    pub workspace_id: String,
+    /// Checked-out branch; None when HEAD is detached
+    pub branch: Option<String>,
+    pub staged: u32,
+    /// Tracked files with unstaged changes
+    pub dirty: u32,
+    pub untracked: u32,
+    pub conflicted: u32,
+    /// The ref ahead/behind are measured against, `<remote>/<default_branch>`
+    pub base: String,
+    /// None when `base` does not exist locally
+    pub ahead: Option<u32>,
+    pub behind: Option<u32>,
+    pub last_commit: Option<CommitSummary>,
+}
+
+/// Sparse-checkout state of a workspace's worktree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct SparseCheckout {
+    pub workspace_id: String,
+    /// False when the whole tree is checked out
+    pub sparse: bool,
+    /// Checked-out directories (the cone); empty when not sparse
+    pub paths: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct SparseInput {
+    /// Directories to add to the sparse-checkout cone
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Disable sparse checkout and check out the whole tree
+    #[serde(default)]
+    pub all: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -147,6 +750,166 @@ pub struct Setting {
     pub updated_at: String,
 }
 
+/// The kind of value a registered setting holds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum SettingType {
+    String,
+    Boolean,
+    Integer,
+    /// An age such as "30d", "12h" or "2w"
+    Duration,
+    /// One of `allowed_values`
+    Choice,
+}
+
+impl SettingType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            SettingType::String => "string",
+            SettingType::Boolean => "boolean",
+            SettingType::Integer => "integer",
+            SettingType::Duration => "duration",
+            SettingType::Choice => "choice",
+        }
+    }
+}
+
+/// A setting Bunyan knows about, with its type and default.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct SettingDefinition {
+    pub key: String,
+    pub value_type: SettingType,
+    pub default_value: Option<String>,
+    pub description: String,
+    /// Accepted values for `choice` settings; empty otherwise
+    pub allowed_values: Vec<String>,
+}
+
+/// The value a setting currently resolves to: the stored value if there is
+/// one, otherwise the registered default.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct EffectiveSetting {
+    pub key: String,
+    pub value: String,
+    /// True when no value is stored and the default applies
+    pub is_default: bool,
+    /// False for stored keys that are not in the settings registry
+    pub known: bool,
+    /// When the stored value was last written, or null for defaults
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    RepoCreated,
+    RepoUpdated,
+    RepoDeleted,
+    WorkspaceCreated,
+    WorkspaceFailed,
+    WorkspaceArchived,
+    WorkspaceSuspended,
+    WorkspaceResumed,
+    WorkspacePurged,
+    ContainerCreated,
+    ContainerRemoved,
+    ClaudeStarted,
+    ClaudeResumed,
+    ShellOpened,
+    PaneKilled,
+    WorkspaceCommitted,
+    WorkspacePushed,
+    PullRequestOpened,
+    WorkspaceSynced,
+    CheckpointCreated,
+    CheckpointRestored,
+    HookRan,
+}
+
+impl EventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::RepoCreated => "repo_created",
+            EventKind::RepoUpdated => "repo_updated",
+            EventKind::RepoDeleted => "repo_deleted",
+            EventKind::WorkspaceCreated => "workspace_created",
+            EventKind::WorkspaceFailed => "workspace_failed",
+            EventKind::WorkspaceArchived => "workspace_archived",
+            EventKind::WorkspaceSuspended => "workspace_suspended",
+            EventKind::WorkspaceResumed => "workspace_resumed",
+            EventKind::WorkspacePurged => "workspace_purged",
+            EventKind::ContainerCreated => "container_created",
+            EventKind::ContainerRemoved => "container_removed",
+            EventKind::ClaudeStarted => "claude_started",
+            EventKind::ClaudeResumed => "claude_resumed",
+            EventKind::ShellOpened => "shell_opened",
+            EventKind::PaneKilled => "pane_killed",
+            EventKind::WorkspaceCommitted => "workspace_committed",
+            EventKind::WorkspacePushed => "workspace_pushed",
+            EventKind::PullRequestOpened => "pull_request_opened",
+            EventKind::WorkspaceSynced => "workspace_synced",
+            EventKind::CheckpointCreated => "checkpoint_created",
+            EventKind::CheckpointRestored => "checkpoint_restored",
+            EventKind::HookRan => "hook_ran",
+        }
+    }
+
+    pub fn from_db(s: &str) -> std::result::Result<Self, String> {
+        match s {
+            "repo_created" => Ok(EventKind::RepoCreated),
+            "repo_updated" => Ok(EventKind::RepoUpdated),
+            "repo_deleted" => Ok(EventKind::RepoDeleted),
+            "workspace_created" => Ok(EventKind::WorkspaceCreated),
+            "workspace_failed" => Ok(EventKind::WorkspaceFailed),
+            "workspace_archived" => Ok(EventKind::WorkspaceArchived),
+            "workspace_suspended" => Ok(EventKind::WorkspaceSuspended),
+            "workspace_resumed" => Ok(EventKind::WorkspaceResumed),
+            "workspace_purged" => Ok(EventKind::WorkspacePurged),
+            "container_created" => Ok(EventKind::ContainerCreated),
+            "container_removed" => Ok(EventKind::ContainerRemoved),
+            "claude_started" => Ok(EventKind::ClaudeStarted),
+            "claude_resumed" => Ok(EventKind::ClaudeResumed),
+            "shell_opened" => Ok(EventKind::ShellOpened),
+            "pane_killed" => Ok(EventKind::PaneKilled),
+            "workspace_committed" => Ok(EventKind::WorkspaceCommitted),
+            "workspace_pushed" => Ok(EventKind::WorkspacePushed),
+            "pull_request_opened" => Ok(EventKind::PullRequestOpened),
+            "workspace_synced" => Ok(EventKind::WorkspaceSynced),
+            "checkpoint_created" => Ok(EventKind::CheckpointCreated),
+            "checkpoint_restored" => Ok(EventKind::CheckpointRestored),
+            "hook_ran" => Ok(EventKind::HookRan),
+            other => Err(format!("Invalid event kind: {}", other)),
+        }
+    }
+}
+
+/// An audit log entry recording something Bunyan did.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct Event {
+    pub id: i64,
+    pub kind: EventKind,
+    pub repo_id: Option<String>,
+    pub workspace_id: Option<String>,
+    pub message: Option<String>,
+    pub created_at: String,
+}
+
+/// A schema migration and whether it has been applied to the database.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub name: String,
+    /// When the migration was applied, or null if it is still pending
+    pub applied_at: Option<String>,
+}
+
 /// A tmux pane within the Bunyan-managed tmux server.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
@@ -229,14 +992,102 @@ pub struct ClaudeResumeInput {
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 pub struct OpenEditorInput {
-    pub editor_id: String,
+    /// Editor to open; defaults to the workspace's configured editor
+    #[serde(default)]
+    pub editor_id: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 pub struct ArchiveInput {
+    /// Archive even if the worktree has uncommitted files or unpushed commits,
+    /// discarding them
     #[serde(default)]
     pub force: bool,
+    /// Delete the local branch once the worktree is gone, if it is merged into
+    /// the default branch (or regardless, with `force`)
+    #[serde(default)]
+    pub delete_branch: bool,
+    /// Also delete the branch on the remote, under the same condition
+    #[serde(default)]
+    pub delete_remote_branch: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct PurgeWorkspacesInput {
+    /// Only purge workspaces archived longer ago than this age (e.g. "30d", "12h")
+    pub older_than: Option<String>,
+    /// Only purge workspaces of this repository
+    pub repository_id: Option<String>,
+    /// List what would be removed without deleting anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct PurgeWorkspacesResult {
+    pub dry_run: bool,
+    /// Workspaces that were removed, or would be removed in a dry run
+    pub workspaces: Vec<Workspace>,
+}
+
+/// A kind of drift between the database and the worktrees, tmux windows and
+/// containers it describes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    /// A live workspace row whose worktree is gone
+    MissingWorktree,
+    /// A worktree in the workspaces directory with no live workspace row
+    UntrackedWorktree,
+    /// A tmux window with no live workspace behind it
+    StaleTmuxWindow,
+    /// A Bunyan container no live workspace refers to
+    OrphanedContainer,
+    /// A live container-mode workspace whose container is gone
+    MissingContainer,
+    /// A repo whose stored config no longer passes validation
+    InvalidRepoConfig,
+}
+
+impl IssueKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueKind::MissingWorktree => "missing_worktree",
+            IssueKind::UntrackedWorktree => "untracked_worktree",
+            IssueKind::StaleTmuxWindow => "stale_tmux_window",
+            IssueKind::OrphanedContainer => "orphaned_container",
+            IssueKind::MissingContainer => "missing_container",
+            IssueKind::InvalidRepoConfig => "invalid_repo_config",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct DoctorIssue {
+    pub kind: IssueKind,
+    pub repo_id: Option<String>,
+    pub workspace_id: Option<String>,
+    /// The repo name, worktree path, tmux window (`session:window`) or container ID concerned
+    pub subject: String,
+    pub message: String,
+    /// Whether `--fix` knows how to repair this
+    pub fixable: bool,
+    /// Set once a fix has been applied successfully
+    pub fixed: bool,
+    pub fix_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct DoctorReport {
+    pub issues: Vec<DoctorIssue>,
+    /// Checks that could not run, with the reason (e.g. Docker unavailable)
+    pub skipped: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -255,4 +1106,7 @@ pub struct SystemInfo {
 #[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 pub struct ErrorResponse {
     pub error: String,
+    /// Machine-readable error kind, e.g. `branch_exists` or `git_auth`
+    #[serde(default)]
+    pub code: String,
 }
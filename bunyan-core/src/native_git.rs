@@ -0,0 +1,1294 @@
+use std::path::{Path, PathBuf};
+
+use git2::build::{CheckoutBuilder, RepoBuilder};
+use git2::{
+    AnnotatedCommit, BranchType, Cred, CredentialType, DiffFormat, DiffOptions, ErrorClass, ErrorCode,
+    FetchOptions, Index, IndexAddOption, IndexEntryExtendedFlag, Oid, Pathspec, PathspecFlags, PushOptions, RebaseOptions,
+    RemoteCallbacks, Repository, ResetType, Signature, Sort, Status, StatusOptions,
+    SubmoduleUpdateOptions, WorktreeAddOptions, WorktreePruneOptions,
+};
+
+use crate::error::{BunyanError, Result};
+use crate::git::{
+    self, is_executable, CheckpointEntry, CloneInfo, CloneOptions, GitOps, RealGit, SyncOutcome,
+    UnsavedWork, WorkingTreeStatus, WorktreeEntry, WorktreeSpec,
+};
+use crate::models::{CommitStatus, CommitSummary, DiffFileStat, PushStatus, SyncStatus, SyncStrategy};
+
+/// Runs git operations in process through libgit2, so it works without a
+/// `git` executable on PATH. A few things libgit2 lacks need the git CLI and
+/// fail with an error saying so when it is not installed: partial clones,
+/// sparse checkout, LFS, and commits and pushes in repos with hooks (libgit2
+/// does not run hooks).
+pub struct NativeGit;
+
+/// The git CLI, for an operation libgit2 cannot do, or an error naming it.
+fn via_cli(operation: &str) -> Result<RealGit> {
+    if git::cli_available() {
+        Ok(RealGit)
+    } else {
+        Err(BunyanError::Git(format!(
+            "{} needs the git CLI, which was not found on PATH; install git or set git_backend to cli",
+            operation
+        )))
+    }
+}
+
+/// Map a libgit2 error to the most specific error kind.
+fn git_error(action: &str, err: git2::Error) -> BunyanError {
+    let message = err.message().to_string();
+    let auth_class = matches!(err.class(), ErrorClass::Ssh | ErrorClass::Http);
+    if err.code() == ErrorCode::Auth || (auth_class && message.to_lowercase().contains("auth")) {
+        BunyanError::GitAuth(format!("{}: {}", action, message))
+    } else {
+        BunyanError::Git(format!("{} failed: {}", action, message))
+    }
+}
+
+fn open(path: &str) -> Result<Repository> {
+    Repository::open(path).map_err(|e| git_error(&format!("opening repository {}", path), e))
+}
+
+fn resolve_commit<'r>(repo: &'r Repository, spec: &str) -> Result<git2::Commit<'r>> {
+    repo.revparse_single(spec)
+        .and_then(|o| o.peel_to_commit())
+        .map_err(|e| git_error(&format!("resolving {}", spec), e))
+}
+
+/// Short name of the branch HEAD points at, also on an unborn branch.
+/// None when HEAD is detached.
+fn head_branch(repo: &Repository) -> Result<Option<String>> {
+    if repo.head_detached().map_err(|e| git_error("reading HEAD", e))? {
+        return Ok(None);
+    }
+    let head = repo.find_reference("HEAD").map_err(|e| git_error("reading HEAD", e))?;
+    Ok(head
+        .symbolic_target()
+        .map(|t| t.trim_start_matches("refs/heads/").to_string()))
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    match (std::fs::canonicalize(a), std::fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a.components().eq(b.components()),
+    }
+}
+
+/// Whether any of the named hooks is installed, honoring `core.hooksPath`.
+fn has_hook(repo: &Repository, names: &[&str]) -> bool {
+    let configured = repo
+        .config()
+        .ok()
+        .and_then(|c| c.get_path("core.hooksPath").ok());
+    let dir = match configured {
+        Some(p) if p.is_relative() => repo.workdir().unwrap_or(repo.path()).join(p),
+        Some(p) => p,
+        None => repo.commondir().join("hooks"),
+    };
+    names.iter().any(|name| is_executable(&dir.join(name)))
+}
+
+/// Credentials for remotes: the SSH agent, then default key files, then
+/// git's credential helpers. Each is tried once before giving up.
+fn remote_callbacks(config: &git2::Config) -> RemoteCallbacks<'_> {
+    let mut attempt = 0;
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(move |url, username, allowed| {
+        attempt += 1;
+        if allowed.contains(CredentialType::SSH_KEY) {
+            let user = username.unwrap_or("git");
+            let keys: Vec<PathBuf> = dirs::home_dir()
+                .map(|home| {
+                    ["id_ed25519", "id_ecdsa", "id_rsa"]
+                        .iter()
+                        .map(|k| home.join(".ssh").join(k))
+                        .filter(|k| k.exists())
+                        .collect()
+                })
+                .unwrap_or_default();
+            match attempt {
+                1 => return Cred::ssh_key_from_agent(user),
+                n if n - 2 < keys.len() => return Cred::ssh_key(user, None, &keys[n - 2], None),
+                _ => {}
+            }
+        } else if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) && attempt == 1 {
+            return Cred::credential_helper(config, url, username);
+        } else if allowed.contains(CredentialType::DEFAULT) && attempt == 1 {
+            return Cred::default();
+        }
+        Err(git2::Error::new(
+            ErrorCode::Auth,
+            ErrorClass::Callback,
+            format!("no accepted credentials for {}", url),
+        ))
+    });
+    callbacks
+}
+
+/// Name for a new worktree's administrative directory: the directory's base
+/// name, numbered like git does when taken by a live worktree.
+fn worktree_name(repo: &Repository, path: &Path) -> Result<String> {
+    let base = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .ok_or_else(|| BunyanError::InvalidInput(format!("Invalid worktree path {}", path.display())))?;
+    let mut name = base.clone();
+    for n in 1.. {
+        match repo.find_worktree(&name) {
+            Err(_) => break,
+            Ok(wt) if wt.is_prunable(None).unwrap_or(false) => {
+                wt.prune(None).map_err(|e| git_error("git worktree prune", e))?;
+                break;
+            }
+            Ok(_) => name = format!("{}{}", base, n),
+        }
+    }
+    Ok(name)
+}
+
+fn commit_summary(commit: &git2::Commit) -> CommitSummary {
+    let time = commit.time();
+    let committed_at = chrono::FixedOffset::east_opt(time.offset_minutes() * 60)
+        .and_then(|tz| chrono::DateTime::from_timestamp(time.seconds(), 0).map(|t| t.with_timezone(&tz)))
+        .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, false))
+        .unwrap_or_default();
+    CommitSummary {
+        sha: commit.id().to_string(),
+        summary: commit.summary().unwrap_or_default().to_string(),
+        author: commit.author().name().unwrap_or_default().to_string(),
+        committed_at,
+    }
+}
+
+fn statuses(repo: &Repository) -> Result<git2::Statuses<'_>> {
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(false)
+        .renames_head_to_index(true);
+    repo.statuses(Some(&mut options)).map_err(|e| git_error("git status", e))
+}
+
+const STAGED: Status = Status::INDEX_NEW
+    .union(Status::INDEX_MODIFIED)
+    .union(Status::INDEX_DELETED)
+    .union(Status::INDEX_RENAMED)
+    .union(Status::INDEX_TYPECHANGE);
+const UNSTAGED: Status = Status::WT_MODIFIED
+    .union(Status::WT_DELETED)
+    .union(Status::WT_RENAMED)
+    .union(Status::WT_TYPECHANGE);
+
+/// Diff from `from` to the working tree, like `git diff <from>`, with untracked
+/// (but not ignored) files shown as added.
+fn diff_to_workdir<'r>(repo: &'r Repository, from: &str, paths: &[String]) -> Result<git2::Diff<'r>> {
+    let tree = repo
+        .revparse_single(from)
+        .and_then(|o| o.peel_to_tree())
+        .map_err(|e| git_error(&format!("resolving {}", from), e))?;
+    let mut options = DiffOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    for path in paths {
+        options.pathspec(path);
+    }
+    repo.diff_tree_to_workdir_with_index(Some(&tree), Some(&mut options))
+        .map_err(|e| git_error("git diff", e))
+}
+
+/// Remote refs updated by a push, and the remote's reasons for refusing any.
+#[derive(Default)]
+struct PushReport {
+    changed: bool,
+    rejected: Vec<String>,
+}
+
+/// Push `refspecs` to `remote_name`, collecting what the remote did with each.
+fn push_refspecs(repo: &Repository, remote_name: &str, refspecs: &[&str]) -> Result<PushReport> {
+    let config = repo.config().map_err(|e| git_error("reading git config", e))?;
+    let mut remote = repo
+        .find_remote(remote_name)
+        .map_err(|e| git_error(&format!("finding remote {}", remote_name), e))?;
+    let mut report = PushReport::default();
+    let pushed = {
+        let mut callbacks = remote_callbacks(&config);
+        callbacks.push_negotiation(|updates| {
+            report.changed = updates.iter().any(|u| u.src() != u.dst());
+            Ok(())
+        });
+        let rejected = &mut report.rejected;
+        callbacks.push_update_reference(|refname, status| {
+            if let Some(reason) = status {
+                rejected.push(format!("{}: {}", refname, reason));
+            }
+            Ok(())
+        });
+        let mut options = PushOptions::new();
+        options.remote_callbacks(callbacks);
+        remote.push(refspecs, Some(&mut options))
+    };
+    match pushed {
+        Ok(()) => Ok(report),
+        // libgit2 refuses non-fast-forward updates before contacting the remote
+        Err(e) if e.code() == ErrorCode::NotFastForward => {
+            report.rejected.push(e.message().to_string());
+            Ok(report)
+        }
+        Err(e) => Err(git_error("git push", e)),
+    }
+}
+
+/// Paths with conflicts in the index, sorted.
+fn conflicted_paths(index: &Index) -> Result<Vec<String>> {
+    let mut paths: Vec<String> = index
+        .conflicts()
+        .map_err(|e| git_error("reading conflicts", e))?
+        .filter_map(|c| c.ok())
+        .filter_map(|c| c.our.or(c.their).or(c.ancestor))
+        .map(|e| String::from_utf8_lossy(&e.path).into_owned())
+        .collect();
+    paths.sort();
+    paths.dedup();
+    Ok(paths)
+}
+
+/// Remove untracked files and directories, keeping ignored ones, like `git clean -d --force`.
+fn clean_untracked(repo: &Repository) -> Result<()> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| BunyanError::Git("git clean failed: repository has no working tree".to_string()))?;
+    let untracked: Vec<PathBuf> = statuses(repo)?
+        .iter()
+        .filter(|e| e.status().contains(Status::WT_NEW))
+        .filter_map(|e| e.path().map(|p| workdir.join(p)))
+        .collect();
+    for path in untracked {
+        let removed = if path.is_dir() { std::fs::remove_dir_all(&path) } else { std::fs::remove_file(&path) };
+        removed.map_err(|e| BunyanError::Git(format!("git clean failed: {}: {}", path.display(), e)))?;
+    }
+    Ok(())
+}
+
+/// Initialize and check out every submodule, then theirs.
+fn update_submodules(repo: &Repository, config: &git2::Config) -> Result<()> {
+    for mut submodule in repo.submodules().map_err(|e| git_error("git submodule update", e))? {
+        let mut fetch = FetchOptions::new();
+        fetch.remote_callbacks(remote_callbacks(config));
+        let mut options = SubmoduleUpdateOptions::new();
+        options.fetch(fetch);
+        submodule
+            .update(true, Some(&mut options))
+            .map_err(|e| git_error("git submodule update", e))?;
+        let nested = submodule.open().map_err(|e| git_error("git submodule update", e))?;
+        update_submodules(&nested, config)?;
+    }
+    Ok(())
+}
+
+/// The message `git merge --no-edit` would write for merging `upstream`.
+fn merge_message(repo: &Repository, upstream: &str) -> Result<String> {
+    let kind = match repo.resolve_reference_from_short_name(upstream) {
+        Ok(r) if r.is_remote() => "remote-tracking branch",
+        Ok(r) if r.is_branch() => "branch",
+        _ => "commit",
+    };
+    let into = match head_branch(repo)? {
+        Some(branch) if branch != "main" && branch != "master" => format!(" into {}", branch),
+        _ => String::new(),
+    };
+    Ok(format!("Merge {} '{}'{}\n", kind, upstream, into))
+}
+
+fn rebase_onto(repo: &Repository, upstream: &AnnotatedCommit, name: &str, abort_on_conflict: bool) -> Result<SyncOutcome> {
+    let committer = repo.signature().map_err(|e| git_error("git rebase", e))?;
+    let mut options = RebaseOptions::new();
+    let mut rebase = repo
+        .rebase(None, Some(upstream), None, Some(&mut options))
+        .map_err(|e| git_error("git rebase", e))?;
+    while let Some(operation) = rebase.next() {
+        let operation = operation.map_err(|e| git_error("git rebase", e))?;
+        let index = repo.index().map_err(|e| git_error("git rebase", e))?;
+        if index.has_conflicts() {
+            let conflicts = conflicted_paths(&index)?;
+            if abort_on_conflict {
+                rebase.abort().map_err(|e| git_error("git rebase --abort", e))?;
+            }
+            return Ok(SyncOutcome {
+                status: SyncStatus::Conflicted,
+                output: format!("CONFLICT: could not apply {} onto {}: {}", operation.id(), name, conflicts.join(", ")),
+                conflicts,
+            });
+        }
+        match rebase.commit(None, &committer, None) {
+            // Already upstream: drop it, as git does
+            Err(e) if e.code() == ErrorCode::Applied => {}
+            result => {
+                result.map_err(|e| git_error("git rebase", e))?;
+            }
+        }
+    }
+    rebase.finish(Some(&committer)).map_err(|e| git_error("git rebase", e))?;
+    Ok(SyncOutcome {
+        status: SyncStatus::Synced,
+        conflicts: Vec::new(),
+        output: format!("Successfully rebased onto {}", name),
+    })
+}
+
+fn merge_into_head(repo: &Repository, upstream: &AnnotatedCommit, name: &str, abort_on_conflict: bool) -> Result<SyncOutcome> {
+    let (analysis, _) = repo.merge_analysis(&[upstream]).map_err(|e| git_error("git merge", e))?;
+    let synced = |output: String| SyncOutcome { status: SyncStatus::Synced, conflicts: Vec::new(), output };
+    if analysis.is_up_to_date() {
+        return Ok(synced("Already up to date.".to_string()));
+    }
+    let target = repo.find_commit(upstream.id()).map_err(|e| git_error("git merge", e))?;
+    if analysis.is_fast_forward() {
+        repo.checkout_tree(target.as_object(), Some(CheckoutBuilder::new().safe()))
+            .map_err(|e| git_error("git merge", e))?;
+        let mut head = repo.head().map_err(|e| git_error("git merge", e))?;
+        head.set_target(target.id(), &format!("merge {}: Fast-forward", name))
+            .map_err(|e| git_error("git merge", e))?;
+        return Ok(synced(format!("Fast-forwarded to {}", name)));
+    }
+
+    repo.merge(&[upstream], None, None).map_err(|e| git_error("git merge", e))?;
+    let mut index = repo.index().map_err(|e| git_error("git merge", e))?;
+    if index.has_conflicts() {
+        let conflicts = conflicted_paths(&index)?;
+        if abort_on_conflict {
+            let head = resolve_commit(repo, "HEAD")?;
+            repo.reset(head.as_object(), ResetType::Hard, None)
+                .and_then(|_| repo.cleanup_state())
+                .map_err(|e| git_error("git merge --abort", e))?;
+        }
+        return Ok(SyncOutcome {
+            status: SyncStatus::Conflicted,
+            output: format!("CONFLICT: automatic merge of {} failed: {}", name, conflicts.join(", ")),
+            conflicts,
+        });
+    }
+    let tree_id = index.write_tree().map_err(|e| git_error("git merge", e))?;
+    let tree = repo.find_tree(tree_id).map_err(|e| git_error("git merge", e))?;
+    let head = resolve_commit(repo, "HEAD")?;
+    let signature = repo.signature().map_err(|e| git_error("git merge", e))?;
+    repo.commit(Some("HEAD"), &signature, &signature, &merge_message(repo, name)?, &tree, &[&head, &target])
+        .map_err(|e| git_error("git merge", e))?;
+    repo.cleanup_state().map_err(|e| git_error("git merge", e))?;
+    Ok(synced(format!("Merged {}", name)))
+}
+
+impl GitOps for NativeGit {
+    fn clone_repo(&self, url: &str, path: &str, options: &CloneOptions) -> Result<()> {
+        if options.filter.is_some() || options.sparse {
+            return via_cli("A partial or sparse clone")?.clone_repo(url, path, options);
+        }
+        let config = git2::Config::open_default().map_err(|e| git_error("reading git config", e))?;
+        let mut fetch = FetchOptions::new();
+        fetch.remote_callbacks(remote_callbacks(&config));
+        if let Some(depth) = options.depth {
+            fetch.depth(depth.min(i32::MAX as u32) as i32);
+        }
+        RepoBuilder::new()
+            .fetch_options(fetch)
+            .clone(url, Path::new(path))
+            .map_err(|e| match e.code() {
+                ErrorCode::Exists => BunyanError::PathExists(e.message().to_string()),
+                _ => git_error("git clone", e),
+            })?;
+        Ok(())
+    }
+
+    fn worktree_add(&self, repo_path: &str, worktree_path: &str, spec: &WorktreeSpec, sparse: &[String]) -> Result<()> {
+        if !sparse.is_empty() {
+            return via_cli("Sparse checkout")?.worktree_add(repo_path, worktree_path, spec, sparse);
+        }
+        let repo = open(repo_path)?;
+        let path = Path::new(worktree_path);
+        if path.exists() {
+            // libgit2 wants to create the directory itself; an empty one is fine to replace
+            let empty = path.read_dir().map(|mut d| d.next().is_none()).unwrap_or(false);
+            if !empty {
+                return Err(BunyanError::PathExists(format!("'{}' already exists", worktree_path)));
+            }
+            std::fs::remove_dir(path)
+                .map_err(|e| BunyanError::Git(format!("Cannot replace {}: {}", worktree_path, e)))?;
+        }
+
+        let create = |name: &str, start: &str| {
+            let commit = resolve_commit(&repo, start)?;
+            repo.branch(name, &commit, false).map_err(|e| match e.code() {
+                ErrorCode::Exists => BunyanError::BranchExists(format!("a branch named '{}' already exists", name)),
+                _ => git_error("git branch", e),
+            })
+        };
+        let (branch, created) = match spec {
+            WorktreeSpec::NewBranch { branch, base } => (create(branch, base.as_deref().unwrap_or("HEAD"))?, true),
+            WorktreeSpec::Existing { branch } => {
+                let existing = repo
+                    .find_branch(branch, BranchType::Local)
+                    .map_err(|e| git_error(&format!("finding branch {}", branch), e))?;
+                (existing, false)
+            }
+            WorktreeSpec::Track { branch, upstream } => {
+                let mut tracking = create(branch, upstream)?;
+                tracking
+                    .set_upstream(Some(upstream))
+                    .map_err(|e| git_error("setting upstream", e))?;
+                (tracking, true)
+            }
+        };
+
+        let added = (|| {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| BunyanError::Git(format!("Cannot create {}: {}", parent.display(), e)))?;
+            }
+            let name = worktree_name(&repo, path)?;
+            let mut options = WorktreeAddOptions::new();
+            options.reference(Some(branch.get()));
+            repo.worktree(&name, path, Some(&options))
+                .map_err(|e| git_error("git worktree add", e))
+        })();
+        if let Err(e) = added {
+            // Don't leave behind a branch that only existed for this worktree
+            if created {
+                let _ = branch.into_reference().delete();
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    fn fetch_branch(&self, repo_path: &str, remote: &str, branch: &str) -> Result<()> {
+        let repo = open(repo_path)?;
+        let config = repo.config().map_err(|e| git_error("reading git config", e))?;
+        let mut found = repo
+            .find_remote(remote)
+            .map_err(|e| git_error(&format!("finding remote {}", remote), e))?;
+        let mut options = FetchOptions::new();
+        options.remote_callbacks(remote_callbacks(&config));
+        let refspec = format!("+refs/heads/{}:refs/remotes/{}/{}", branch, remote, branch);
+        found
+            .fetch(&[refspec.as_str()], Some(&mut options), None)
+            .map_err(|e| git_error("git fetch", e))
+    }
+
+    fn ref_exists(&self, repo_path: &str, reference: &str) -> Result<bool> {
+        let repo = open(repo_path)?;
+        let exists = resolve_commit(&repo, reference).is_ok();
+        Ok(exists)
+    }
+
+    fn worktree_remove(&self, repo_path: &str, worktree_path: &str, force: bool) -> Result<()> {
+        let repo = open(repo_path)?;
+        let names = repo.worktrees().map_err(|e| git_error("git worktree list", e))?;
+        let worktree = names
+            .iter()
+            .flatten()
+            .filter_map(|name| repo.find_worktree(name).ok())
+            .find(|wt| same_path(wt.path(), Path::new(worktree_path)))
+            .ok_or_else(|| BunyanError::Git(format!("'{}' is not a working tree", worktree_path)))?;
+
+        if !force {
+            let linked = Repository::open_from_worktree(&worktree)
+                .map_err(|e| git_error("opening worktree", e))?;
+            if !statuses(&linked)?.is_empty() {
+                return Err(BunyanError::Git(format!(
+                    "'{}' contains modified or untracked files, use --force to delete it",
+                    worktree_path
+                )));
+            }
+        }
+
+        if Path::new(worktree_path).exists() {
+            std::fs::remove_dir_all(worktree_path)
+                .map_err(|e| BunyanError::Git(format!("Cannot remove {}: {}", worktree_path, e)))?;
+        }
+        worktree
+            .prune(Some(WorktreePruneOptions::new().valid(true).locked(force)))
+            .map_err(|e| git_error("git worktree remove", e))
+    }
+
+    fn worktree_list(&self, repo_path: &str) -> Result<Vec<WorktreeEntry>> {
+        let repo = open(repo_path)?;
+        let main_path = repo.workdir().unwrap_or(repo.path());
+        let mut entries = vec![WorktreeEntry {
+            path: main_path.to_string_lossy().trim_end_matches('/').to_string(),
+            branch: head_branch(&repo)?,
+            bare: repo.is_bare(),
+        }];
+
+        let names = repo.worktrees().map_err(|e| git_error("git worktree list", e))?;
+        for name in names.iter().flatten() {
+            let Ok(worktree) = repo.find_worktree(name) else { continue };
+            // A worktree whose directory is gone still has its HEAD in the admin dir
+            let branch = match Repository::open_from_worktree(&worktree) {
+                Ok(linked) => head_branch(&linked)?,
+                Err(_) => std::fs::read_to_string(repo.path().join("worktrees").join(name).join("HEAD"))
+                    .ok()
+                    .and_then(|head| {
+                        head.trim()
+                            .strip_prefix("ref: refs/heads/")
+                            .map(str::to_string)
+                    }),
+            };
+            entries.push(WorktreeEntry {
+                path: worktree.path().to_string_lossy().trim_end_matches('/').to_string(),
+                branch,
+                bare: false,
+            });
+        }
+        Ok(entries)
+    }
+
+    fn inspect_clone(&self, path: &str, remote: &str) -> Result<CloneInfo> {
+        let not_a_clone = |why: &str| BunyanError::InvalidInput(format!("{} {}", path, why));
+        if !Path::new(path).is_dir() {
+            return Err(not_a_clone("is not a directory"));
+        }
+        let repo = Repository::discover(path).map_err(|_| not_a_clone("is not a git repository"))?;
+        if !repo.workdir().is_some_and(|w| same_path(w, Path::new(path))) {
+            return Err(not_a_clone("is inside a git repository but not its top level"));
+        }
+        if repo.is_worktree() {
+            return Err(not_a_clone("is a linked worktree, not a main clone"));
+        }
+
+        let remote_url = repo
+            .find_remote(remote)
+            .ok()
+            .and_then(|r| r.url().map(str::to_string))
+            .ok_or_else(|| not_a_clone(&format!("has no remote named '{}'", remote)))?;
+
+        // Prefer the remote's HEAD; fall back to whatever the clone has checked out
+        let prefix = format!("refs/remotes/{}/", remote);
+        let from_remote = repo
+            .find_reference(&format!("{}HEAD", prefix))
+            .ok()
+            .and_then(|r| r.symbolic_target().map(|t| t.trim_start_matches(&prefix).to_string()));
+        let default_branch = match from_remote {
+            Some(branch) => branch,
+            None => head_branch(&repo)?.ok_or_else(|| {
+                not_a_clone("has a detached HEAD and no remote HEAD to take the default branch from")
+            })?,
+        };
+
+        Ok(CloneInfo {
+            remote_url,
+            default_branch,
+        })
+    }
+
+    fn worktree_prune(&self, repo_path: &str) -> Result<()> {
+        let repo = open(repo_path)?;
+        let names = repo.worktrees().map_err(|e| git_error("git worktree prune", e))?;
+        for name in names.iter().flatten() {
+            let Ok(worktree) = repo.find_worktree(name) else { continue };
+            if worktree.is_prunable(None).unwrap_or(false) {
+                worktree.prune(None).map_err(|e| git_error("git worktree prune", e))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn working_tree_status(&self, worktree_path: &str) -> Result<WorkingTreeStatus> {
+        let repo = open(worktree_path)?;
+        let mut status = WorkingTreeStatus {
+            branch: head_branch(&repo)?,
+            ..Default::default()
+        };
+        for entry in statuses(&repo)?.iter() {
+            let flags = entry.status();
+            if flags.contains(Status::CONFLICTED) {
+                status.conflicted += 1;
+                continue;
+            }
+            if flags.intersects(STAGED) {
+                status.staged += 1;
+            }
+            if flags.intersects(UNSTAGED) {
+                status.dirty += 1;
+            }
+            if flags.contains(Status::WT_NEW) {
+                status.untracked += 1;
+            }
+        }
+        Ok(status)
+    }
+
+    fn ahead_behind(&self, worktree_path: &str, base: &str) -> Result<Option<(u32, u32)>> {
+        let repo = open(worktree_path)?;
+        let (Ok(base), Ok(head)) = (resolve_commit(&repo, base), resolve_commit(&repo, "HEAD")) else {
+            return Ok(None);
+        };
+        let (ahead, behind) = repo
+            .graph_ahead_behind(head.id(), base.id())
+            .map_err(|e| git_error("counting commits", e))?;
+        Ok(Some((ahead as u32, behind as u32)))
+    }
+
+    fn last_commit(&self, worktree_path: &str) -> Result<Option<CommitSummary>> {
+        let repo = open(worktree_path)?;
+        // An unborn branch has no commit yet
+        Ok(resolve_commit(&repo, "HEAD").ok().map(|c| commit_summary(&c)))
+    }
+
+    fn merge_base(&self, worktree_path: &str, other: &str) -> Result<String> {
+        let repo = open(worktree_path)?;
+        let head = resolve_commit(&repo, "HEAD")?;
+        let other_commit = resolve_commit(&repo, other)?;
+        repo.merge_base(head.id(), other_commit.id())
+            .map(|oid| oid.to_string())
+            .map_err(|e| git_error(&format!("git merge-base for {}", other), e))
+    }
+
+    fn diff_stat(&self, worktree_path: &str, from: &str, paths: &[String]) -> Result<Vec<DiffFileStat>> {
+        let repo = open(worktree_path)?;
+        let diff = diff_to_workdir(&repo, from, paths)?;
+        let mut stats = Vec::new();
+        for (idx, delta) in diff.deltas().enumerate() {
+            let file = if delta.status() == git2::Delta::Deleted {
+                delta.old_file()
+            } else {
+                delta.new_file()
+            };
+            let path = file.path().map(|p| p.to_string_lossy().into_owned()).unwrap_or_default();
+            let patch = git2::Patch::from_diff(&diff, idx).map_err(|e| git_error("git diff", e))?;
+            let binary = patch.as_ref().is_some_and(|p| p.delta().flags().is_binary());
+            let (_, additions, deletions) = match &patch {
+                Some(p) if !binary => p.line_stats().map_err(|e| git_error("git diff", e))?,
+                _ => (0, 0, 0),
+            };
+            stats.push(DiffFileStat {
+                path,
+                additions: additions as u32,
+                deletions: deletions as u32,
+                binary,
+            });
+        }
+        Ok(stats)
+    }
+
+    fn diff_patch(&self, worktree_path: &str, from: &str, paths: &[String]) -> Result<String> {
+        let repo = open(worktree_path)?;
+        let diff = diff_to_workdir(&repo, from, paths)?;
+        let mut patch = Vec::new();
+        diff.print(DiffFormat::Patch, |_, _, line| {
+            if matches!(line.origin(), '+' | '-' | ' ') {
+                patch.push(line.origin() as u8);
+            }
+            patch.extend_from_slice(line.content());
+            true
+        })
+        .map_err(|e| git_error("git diff", e))?;
+        Ok(String::from_utf8_lossy(&patch).into_owned())
+    }
+
+    fn unsaved_work(&self, worktree_path: &str) -> Result<UnsavedWork> {
+        let repo = open(worktree_path)?;
+        let mut work = UnsavedWork::default();
+        for entry in statuses(&repo)?.iter() {
+            let path = entry.path().unwrap_or_default().to_string();
+            if entry.status().contains(Status::WT_NEW) {
+                work.untracked.push(path);
+            } else {
+                work.dirty.push(path);
+            }
+        }
+
+        // An unborn branch has nothing to push
+        if resolve_commit(&repo, "HEAD").is_ok() {
+            let mut walk = repo.revwalk().map_err(|e| git_error("git log", e))?;
+            walk.set_sorting(Sort::TIME).map_err(|e| git_error("git log", e))?;
+            walk.push_head().map_err(|e| git_error("git log", e))?;
+            walk.hide_glob("refs/remotes").map_err(|e| git_error("git log", e))?;
+            for oid in walk {
+                let commit = oid
+                    .and_then(|oid| repo.find_commit(oid))
+                    .map_err(|e| git_error("git log", e))?;
+                let short = commit
+                    .as_object()
+                    .short_id()
+                    .map_err(|e| git_error("git log", e))?;
+                work.unpushed.push(format!(
+                    "{} {}",
+                    short.as_str().unwrap_or_default(),
+                    commit.summary().unwrap_or_default()
+                ));
+            }
+        }
+        Ok(work)
+    }
+
+    fn is_merged(&self, repo_path: &str, rev: &str, into: &str) -> Result<bool> {
+        let repo = open(repo_path)?;
+        let rev = resolve_commit(&repo, rev)?.id();
+        let into = resolve_commit(&repo, into)?.id();
+        Ok(rev == into
+            || repo
+                .graph_descendant_of(into, rev)
+                .map_err(|e| git_error("git merge-base", e))?)
+    }
+
+    fn delete_branch(&self, repo_path: &str, branch: &str) -> Result<()> {
+        let repo = open(repo_path)?;
+        repo.find_branch(branch, BranchType::Local)
+            .and_then(|mut b| b.delete())
+            .map_err(|e| git_error(&format!("deleting branch {}", branch), e))
+    }
+
+    fn delete_remote_branch(&self, repo_path: &str, remote: &str, branch: &str) -> Result<()> {
+        let repo = open(repo_path)?;
+        let refspec = format!(":refs/heads/{}", branch);
+        let report = push_refspecs(&repo, remote, &[&refspec])?;
+        if !report.rejected.is_empty() {
+            return Err(BunyanError::Git(format!(
+                "git push --delete failed: {}",
+                report.rejected.join("; ")
+            )));
+        }
+        // Drop the remote-tracking ref too, as `git push --delete` does
+        if let Ok(mut tracking) = repo.find_reference(&format!("refs/remotes/{}/{}", remote, branch)) {
+            let _ = tracking.delete();
+        }
+        Ok(())
+    }
+
+    fn commit(&self, worktree_path: &str, message: &str, paths: &[String], all: bool) -> Result<(CommitStatus, String)> {
+        let repo = open(worktree_path)?;
+        if has_hook(&repo, &["pre-commit", "prepare-commit-msg", "commit-msg"]) {
+            return via_cli("Committing in a repo with commit hooks")?.commit(worktree_path, message, paths, all);
+        }
+
+        let mut index = repo.index().map_err(|e| git_error("reading index", e))?;
+        if all {
+            index.update_all(["*"], None).map_err(|e| git_error("git add", e))?;
+        } else if !paths.is_empty() {
+            index
+                .add_all(paths, IndexAddOption::DEFAULT, None)
+                .and_then(|_| index.update_all(paths, None))
+                .map_err(|e| git_error("git add", e))?;
+        }
+        index.write().map_err(|e| git_error("writing index", e))?;
+
+        let parent = resolve_commit(&repo, "HEAD").ok();
+        let parent_tree = parent.as_ref().map(|c| c.tree()).transpose().map_err(|e| git_error("reading HEAD", e))?;
+        let tree_id = if paths.is_empty() {
+            index.write_tree().map_err(|e| git_error("git commit", e))?
+        } else {
+            // Commit only `paths`: HEAD's tree with the index's version of those paths
+            let spec = Pathspec::new(paths).map_err(|e| git_error("git commit", e))?;
+            let matches = |path: &[u8]| spec.matches_path(Path::new(&*String::from_utf8_lossy(path)), PathspecFlags::DEFAULT);
+            let mut partial = Index::new().map_err(|e| git_error("git commit", e))?;
+            if let Some(tree) = &parent_tree {
+                partial.read_tree(tree).map_err(|e| git_error("git commit", e))?;
+            }
+            let stale: Vec<PathBuf> = partial
+                .iter()
+                .filter(|e| matches(&e.path))
+                .map(|e| PathBuf::from(String::from_utf8_lossy(&e.path).into_owned()))
+                .collect();
+            for path in stale {
+                partial.remove_path(&path).map_err(|e| git_error("git commit", e))?;
+            }
+            for entry in index.iter().filter(|e| matches(&e.path)) {
+                partial.add(&entry).map_err(|e| git_error("git commit", e))?;
+            }
+            partial.write_tree_to(&repo).map_err(|e| git_error("git commit", e))?
+        };
+
+        let unchanged = match &parent_tree {
+            Some(tree) => tree.id() == tree_id,
+            None => repo.find_tree(tree_id).map(|t| t.is_empty()).unwrap_or(false),
+        };
+        if unchanged {
+            return Ok((CommitStatus::NothingToCommit, String::new()));
+        }
+
+        let tree = repo.find_tree(tree_id).map_err(|e| git_error("git commit", e))?;
+        let signature = repo.signature().map_err(|e| git_error("git commit", e))?;
+        let message = git2::message_prettify(message, None).map_err(|e| git_error("git commit", e))?;
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        let oid: Oid = repo
+            .commit(Some("HEAD"), &signature, &signature, &message, &tree, &parents)
+            .map_err(|e| git_error("git commit", e))?;
+
+        let branch = head_branch(&repo)?.unwrap_or_else(|| "detached HEAD".to_string());
+        let short = oid.to_string()[..7].to_string();
+        let summary = message.lines().next().unwrap_or_default();
+        Ok((CommitStatus::Committed, format!("[{} {}] {}", branch, short, summary)))
+    }
+
+    fn push(&self, worktree_path: &str, remote: &str, branch: &str) -> Result<(PushStatus, String)> {
+        let repo = open(worktree_path)?;
+        if has_hook(&repo, &["pre-push"]) {
+            return via_cli("Pushing from a repo with a pre-push hook")?.push(worktree_path, remote, branch);
+        }
+
+        let refspec = format!("HEAD:refs/heads/{}", branch);
+        let report = push_refspecs(&repo, remote, &[&refspec])?;
+        if !report.rejected.is_empty() {
+            return Ok((PushStatus::Rejected, report.rejected.join("\n")));
+        }
+
+        if let Ok(mut local) = repo.find_branch(branch, BranchType::Local) {
+            local
+                .set_upstream(Some(&format!("{}/{}", remote, branch)))
+                .map_err(|e| git_error("setting upstream", e))?;
+        }
+        if report.changed {
+            Ok((PushStatus::Pushed, format!("Pushed HEAD to {}/{}", remote, branch)))
+        } else {
+            Ok((PushStatus::UpToDate, format!("{}/{} is up to date", remote, branch)))
+        }
+    }
+
+    fn sparse_checkout(&self, worktree_path: &str) -> Result<Option<Vec<String>>> {
+        via_cli("Sparse checkout")?.sparse_checkout(worktree_path)
+    }
+
+    fn sparse_add(&self, worktree_path: &str, paths: &[String]) -> Result<()> {
+        via_cli("Sparse checkout")?.sparse_add(worktree_path, paths)
+    }
+
+    fn sparse_disable(&self, worktree_path: &str) -> Result<()> {
+        via_cli("Sparse checkout")?.sparse_disable(worktree_path)
+    }
+
+    fn submodule_update(&self, worktree_path: &str) -> Result<()> {
+        let repo = open(worktree_path)?;
+        let config = repo.config().map_err(|e| git_error("reading git config", e))?;
+        update_submodules(&repo, &config)
+    }
+
+    fn lfs_pull(&self, worktree_path: &str) -> Result<()> {
+        via_cli("Git LFS")?.lfs_pull(worktree_path)
+    }
+
+    fn sync_onto(&self, worktree_path: &str, upstream: &str, strategy: &SyncStrategy, abort_on_conflict: bool) -> Result<SyncOutcome> {
+        let repo = open(worktree_path)?;
+        let target = resolve_commit(&repo, upstream)?;
+        let annotated = repo
+            .find_annotated_commit(target.id())
+            .map_err(|e| git_error(&format!("resolving {}", upstream), e))?;
+        match strategy {
+            SyncStrategy::Rebase => rebase_onto(&repo, &annotated, upstream, abort_on_conflict),
+            SyncStrategy::Merge => merge_into_head(&repo, &annotated, upstream, abort_on_conflict),
+        }
+    }
+
+    fn create_checkpoint(&self, worktree_path: &str, reference: &str, message: &str) -> Result<CheckpointEntry> {
+        let repo = open(worktree_path)?;
+        let head = resolve_commit(&repo, "HEAD")
+            .map_err(|_| BunyanError::InvalidInput("Nothing has been committed yet".to_string()))?;
+        // Stage everything into an in-memory copy of the index, so staged
+        // changes carry over without touching the real one. Entries outside
+        // a sparse checkout are absent on disk but not deleted.
+        let mut index = repo.index().map_err(|e| git_error("reading index", e))?;
+        let skipped: Vec<Vec<u8>> = index
+            .iter()
+            .filter(|e| IndexEntryExtendedFlag::from_bits_truncate(e.flags_extended).is_skip_worktree())
+            .map(|e| e.path)
+            .collect();
+        let mut keep_skipped = |path: &Path, _: &[u8]| -> i32 {
+            let path = path.to_string_lossy();
+            i32::from(skipped.iter().any(|s| s.as_slice() == path.as_bytes()))
+        };
+        index
+            .add_all(["*"], IndexAddOption::DEFAULT, None)
+            .and_then(|_| index.update_all(["*"], Some(&mut keep_skipped)))
+            .map_err(|e| git_error("git add", e))?;
+        let tree_id = index.write_tree().map_err(|e| git_error("git write-tree", e))?;
+        let tree = repo.find_tree(tree_id).map_err(|e| git_error("git write-tree", e))?;
+
+        // Checkpoints are Bunyan's commits, whoever the user is configured as
+        let signature = Signature::now("Bunyan", "bunyan@localhost").map_err(|e| git_error("git commit-tree", e))?;
+        let oid = repo
+            .commit(None, &signature, &signature, &format!("{}\n", message), &tree, &[&head])
+            .map_err(|e| git_error("git commit-tree", e))?;
+        // Refuses to overwrite an existing checkpoint
+        repo.reference(reference, oid, false, "bunyan: checkpoint")
+            .map_err(|e| git_error("git update-ref", e))?;
+        let commit = repo.find_commit(oid).map_err(|e| git_error("git commit-tree", e))?;
+        Ok(CheckpointEntry {
+            reference: reference.to_string(),
+            sha: oid.to_string(),
+            head: head.id().to_string(),
+            message: message.to_string(),
+            created_at: commit_summary(&commit).committed_at,
+        })
+    }
+
+    fn list_checkpoints(&self, repo_path: &str, prefix: &str) -> Result<Vec<CheckpointEntry>> {
+        let repo = open(repo_path)?;
+        let references = repo
+            .references_glob(&format!("{}*", prefix))
+            .map_err(|e| git_error("git for-each-ref", e))?;
+        let mut entries: Vec<CheckpointEntry> = references
+            .filter_map(|r| r.ok())
+            .filter_map(|r| {
+                let commit = r.peel_to_commit().ok()?;
+                Some(CheckpointEntry {
+                    reference: r.name()?.to_string(),
+                    sha: commit.id().to_string(),
+                    head: commit.parent_id(0).map(|id| id.to_string()).unwrap_or_default(),
+                    message: commit.summary().unwrap_or_default().to_string(),
+                    created_at: commit_summary(&commit).committed_at,
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| b.reference.cmp(&a.reference));
+        Ok(entries)
+    }
+
+    fn restore_checkpoint(&self, worktree_path: &str, sha: &str) -> Result<()> {
+        let repo = open(worktree_path)?;
+        let checkpoint = resolve_commit(&repo, sha)?;
+        let parent = checkpoint.parent(0).map_err(|e| git_error(&format!("resolving {}^", sha), e))?;
+        repo.reset(parent.as_object(), ResetType::Hard, None)
+            .map_err(|e| git_error("git reset", e))?;
+        clean_untracked(&repo)?;
+        repo.checkout_tree(checkpoint.as_object(), Some(CheckoutBuilder::new().force()))
+            .map_err(|e| git_error("git read-tree", e))?;
+        // Leave the checkpoint's changes unstaged on top of HEAD
+        repo.reset(parent.as_object(), ResetType::Mixed, None)
+            .map_err(|e| git_error("git reset", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SyncStatus;
+
+    /// A directory under the system temp dir, removed when dropped.
+    struct Scratch(PathBuf);
+
+    impl Scratch {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("bunyan-native-git-{}-{}", name, std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            Scratch(dir)
+        }
+
+        fn path(&self, rel: &str) -> String {
+            self.0.join(rel).to_string_lossy().into_owned()
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// A repo on `main` with one commit of `README.md`.
+    fn init_repo(path: &str) -> Repository {
+        let repo = Repository::init_opts(path, git2::RepositoryInitOptions::new().initial_head("main")).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        std::fs::write(Path::new(path).join("README.md"), "hello\n").unwrap();
+        NativeGit.commit(path, "Initial commit", &["README.md".to_string()], false).unwrap();
+        repo
+    }
+
+    #[test]
+    fn auth_errors_are_classified() {
+        let err = git_error("git fetch", git2::Error::new(ErrorCode::Auth, ErrorClass::Callback, "no"));
+        assert!(matches!(err, BunyanError::GitAuth(_)));
+        let err = git_error("git fetch", git2::Error::new(ErrorCode::GenericError, ErrorClass::Net, "timeout"));
+        assert!(matches!(err, BunyanError::Git(_)));
+    }
+
+    #[test]
+    fn worktree_add_reports_existing_branch_and_path() {
+        let scratch = Scratch::new("add");
+        let root = scratch.path("app");
+        init_repo(&root);
+        let git = NativeGit;
+
+        let spec = WorktreeSpec::NewBranch { branch: "fix".to_string(), base: None };
+        git.worktree_add(&root, &scratch.path("ws/fix"), &spec, &[]).unwrap();
+        assert!(Path::new(&scratch.path("ws/fix/README.md")).exists());
+
+        let list = git.worktree_list(&root).unwrap();
+        assert_eq!(list[0].branch.as_deref(), Some("main"));
+        assert_eq!(list[1].branch.as_deref(), Some("fix"));
+
+        let again = git.worktree_add(&root, &scratch.path("ws/fix2"), &spec, &[]);
+        assert!(matches!(again, Err(BunyanError::BranchExists(_))));
+
+        std::fs::create_dir_all(scratch.path("ws/taken")).unwrap();
+        std::fs::write(scratch.path("ws/taken/file"), "x").unwrap();
+        let spec = WorktreeSpec::NewBranch { branch: "other".to_string(), base: Some("main".to_string()) };
+        let taken = git.worktree_add(&root, &scratch.path("ws/taken"), &spec, &[]);
+        assert!(matches!(taken, Err(BunyanError::PathExists(_))));
+        // The branch created for the failed worktree is rolled back
+        assert!(!git.ref_exists(&root, "refs/heads/other").unwrap());
+    }
+
+    #[test]
+    fn sparse_worktree_checks_out_only_its_cone() {
+        let scratch = Scratch::new("sparse");
+        let root = scratch.path("app");
+        init_repo(&root);
+        for dir in ["api", "web", "libs"] {
+            std::fs::create_dir_all(scratch.path(&format!("app/{}", dir))).unwrap();
+            std::fs::write(scratch.path(&format!("app/{}/main.rs", dir)), "fn main() {}\n").unwrap();
+        }
+        let paths: Vec<String> = ["api", "web", "libs"].iter().map(|d| format!("{}/main.rs", d)).collect();
+        NativeGit.commit(&root, "Add services", &paths, false).unwrap();
+        let git = NativeGit;
+
+        let spec = WorktreeSpec::NewBranch { branch: "fix".to_string(), base: None };
+        let ws = scratch.path("ws/fix");
+        git.worktree_add(&root, &ws, &spec, &["api".to_string()]).unwrap();
+        assert!(Path::new(&scratch.path("ws/fix/README.md")).exists());
+        assert!(Path::new(&scratch.path("ws/fix/api/main.rs")).exists());
+        assert!(!Path::new(&scratch.path("ws/fix/web")).exists());
+        assert_eq!(git.sparse_checkout(&ws).unwrap(), Some(vec!["api".to_string()]));
+        // The main clone is unaffected
+        assert_eq!(git.sparse_checkout(&root).unwrap(), None);
+
+        git.sparse_add(&ws, &["web".to_string()]).unwrap();
+        assert!(Path::new(&scratch.path("ws/fix/web/main.rs")).exists());
+        git.sparse_disable(&ws).unwrap();
+        assert!(Path::new(&scratch.path("ws/fix/libs/main.rs")).exists());
+        assert_eq!(git.sparse_checkout(&ws).unwrap(), None);
+    }
+
+    #[test]
+    fn status_diff_and_unsaved_work() {
+        let scratch = Scratch::new("status");
+        let root = scratch.path("app");
+        init_repo(&root);
+        let git = NativeGit;
+
+        std::fs::write(scratch.path("app/README.md"), "hello\nworld\n").unwrap();
+        std::fs::write(scratch.path("app/notes.txt"), "todo\n").unwrap();
+        std::fs::write(scratch.path("app/staged.rs"), "fn main() {}\n").unwrap();
+        let repo = Repository::open(&root).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("staged.rs")).unwrap();
+        index.write().unwrap();
+
+        let status = git.working_tree_status(&root).unwrap();
+        assert_eq!(
+            status,
+            WorkingTreeStatus { branch: Some("main".to_string()), staged: 1, dirty: 1, untracked: 1, conflicted: 0 }
+        );
+
+        let stats = git.diff_stat(&root, "HEAD", &["README.md".to_string()]).unwrap();
+        assert_eq!(stats, vec![DiffFileStat { path: "README.md".to_string(), additions: 1, deletions: 0, binary: false }]);
+        let patch = git.diff_patch(&root, "HEAD", &[]).unwrap();
+        assert!(patch.contains("+world\n"));
+        assert!(patch.contains("+fn main() {}\n"));
+
+        let work = git.unsaved_work(&root).unwrap();
+        assert_eq!(work.untracked, vec!["notes.txt"]);
+        assert_eq!(work.dirty.len(), 2);
+        // No remotes, so the initial commit is unpushed
+        assert_eq!(work.unpushed.len(), 1);
+        assert!(work.unpushed[0].ends_with(" Initial commit"));
+    }
+
+    #[test]
+    fn diff_includes_untracked_files_on_both_backends() {
+        let scratch = Scratch::new("untracked");
+        let root = scratch.path("app");
+        init_repo(&root);
+        std::fs::write(scratch.path("app/.gitignore"), "*.log\n").unwrap();
+        std::fs::write(scratch.path("app/build.log"), "noise\n").unwrap();
+        std::fs::create_dir_all(scratch.path("app/src")).unwrap();
+        std::fs::write(scratch.path("app/src/new.rs"), "fn new() {}\n").unwrap();
+
+        for git in [&NativeGit as &dyn GitOps, &RealGit] {
+            let stats = git.diff_stat(&root, "HEAD", &[]).unwrap();
+            let mut paths: Vec<_> = stats.iter().map(|s| (s.path.as_str(), s.additions)).collect();
+            paths.sort();
+            assert_eq!(paths, vec![(".gitignore", 1), ("src/new.rs", 1)]);
+            let stats = git.diff_stat(&root, "HEAD", &["src".to_string()]).unwrap();
+            assert_eq!(stats.len(), 1);
+
+            let patch = git.diff_patch(&root, "HEAD", &[]).unwrap();
+            assert!(patch.contains("+fn new() {}\n"));
+            assert!(!patch.contains("noise"));
+            // The real index is left alone
+            assert_eq!(git.working_tree_status(&root).unwrap().untracked, 2);
+        }
+    }
+
+    #[test]
+    fn commit_only_named_paths_then_push() {
+        let scratch = Scratch::new("push");
+        let root = scratch.path("app");
+        let repo = init_repo(&root);
+        Repository::init_bare(scratch.path("remote.git")).unwrap();
+        repo.remote("origin", &scratch.path("remote.git")).unwrap();
+        let git = NativeGit;
+
+        assert_eq!(git.commit(&root, "Nothing", &[], true).unwrap().0, CommitStatus::NothingToCommit);
+
+        std::fs::write(scratch.path("app/a.txt"), "a\n").unwrap();
+        std::fs::write(scratch.path("app/b.txt"), "b\n").unwrap();
+        let (status, output) = git.commit(&root, "Add a", &["a.txt".to_string()], false).unwrap();
+        assert_eq!(status, CommitStatus::Committed);
+        assert!(output.starts_with("[main "));
+        assert_eq!(git.last_commit(&root).unwrap().unwrap().summary, "Add a");
+        // b.txt was not named, so it is still untracked
+        assert_eq!(git.unsaved_work(&root).unwrap().untracked, vec!["b.txt"]);
+
+        assert_eq!(git.push(&root, "origin", "main").unwrap().0, PushStatus::Pushed);
+        assert_eq!(git.push(&root, "origin", "main").unwrap().0, PushStatus::UpToDate);
+        assert!(git.unsaved_work(&root).unwrap().unpushed.is_empty());
+        let upstream = repo.find_branch("main", BranchType::Local).unwrap().upstream().unwrap();
+        assert_eq!(upstream.name().unwrap(), Some("origin/main"));
+        assert!(git.is_merged(&root, "HEAD~1", "origin/main").unwrap());
+    }
+
+    #[test]
+    fn sync_aborts_conflicted_rebase_and_merges_clean_changes() {
+        let scratch = Scratch::new("sync");
+        let root = scratch.path("app");
+        init_repo(&root);
+        let git = NativeGit;
+        let ws = scratch.path("ws/fix");
+        let spec = WorktreeSpec::NewBranch { branch: "fix".to_string(), base: None };
+        git.worktree_add(&root, &ws, &spec, &[]).unwrap();
+
+        std::fs::write(scratch.path("ws/fix/README.md"), "hello from fix\n").unwrap();
+        git.commit(&ws, "Edit on fix", &[], true).unwrap();
+        std::fs::write(scratch.path("app/README.md"), "hello from main\n").unwrap();
+        git.commit(&root, "Edit on main", &[], true).unwrap();
+        let head = git.last_commit(&ws).unwrap().unwrap().sha;
+
+        let outcome = git.sync_onto(&ws, "main", &SyncStrategy::Rebase, true).unwrap();
+        assert_eq!(outcome.status, SyncStatus::Conflicted);
+        assert_eq!(outcome.conflicts, vec!["README.md"]);
+        // Aborted: back where it started, with nothing in progress
+        assert_eq!(git.last_commit(&ws).unwrap().unwrap().sha, head);
+        assert_eq!(git.working_tree_status(&ws).unwrap().conflicted, 0);
+
+        let stopped = git.sync_onto(&ws, "main", &SyncStrategy::Rebase, false).unwrap();
+        assert_eq!(stopped.status, SyncStatus::Conflicted);
+        assert_eq!(git.working_tree_status(&ws).unwrap().conflicted, 1);
+        let abort = std::process::Command::new("git").args(["rebase", "--abort"]).current_dir(&ws).status();
+        assert!(abort.unwrap().success());
+
+        std::fs::write(scratch.path("ws/fix/README.md"), "hello from main\n").unwrap();
+        std::fs::write(scratch.path("ws/fix/fix.txt"), "fix\n").unwrap();
+        git.commit(&ws, "Take main's README", &[], true).unwrap();
+        git.commit(&ws, "Add fix.txt", &["fix.txt".to_string()], false).unwrap();
+        let merged = git.sync_onto(&ws, "main", &SyncStrategy::Merge, true).unwrap();
+        assert_eq!(merged.status, SyncStatus::Synced);
+        assert!(git.is_merged(&ws, "main", "HEAD").unwrap());
+
+        std::fs::write(scratch.path("ws/fix/README.md"), "fix again\n").unwrap();
+        git.commit(&ws, "Edit on fix again", &[], true).unwrap();
+        std::fs::write(scratch.path("app/README.md"), "main again\n").unwrap();
+        git.commit(&root, "Edit on main again", &[], true).unwrap();
+        let head = git.last_commit(&ws).unwrap().unwrap().sha;
+        let conflicted = git.sync_onto(&ws, "main", &SyncStrategy::Merge, true).unwrap();
+        assert_eq!(conflicted.status, SyncStatus::Conflicted);
+        assert_eq!(conflicted.conflicts, vec!["README.md"]);
+        assert_eq!(git.last_commit(&ws).unwrap().unwrap().sha, head);
+        assert_eq!(Repository::open(&ws).unwrap().state(), git2::RepositoryState::Clean);
+        assert_eq!(std::fs::read_to_string(scratch.path("ws/fix/README.md")).unwrap(), "fix again\n");
+    }
+
+    #[test]
+    fn checkpoint_round_trip_leaves_index_and_ignored_files_alone() {
+        let scratch = Scratch::new("checkpoint");
+        let root = scratch.path("app");
+        init_repo(&root);
+        let git = NativeGit;
+        let read = |rel: &str| std::fs::read_to_string(scratch.path(rel)).ok();
+
+        std::fs::write(scratch.path("app/.gitignore"), "build/\n").unwrap();
+        git.commit(&root, "Ignore build", &[".gitignore".to_string()], false).unwrap();
+        let head = git.last_commit(&root).unwrap().unwrap().sha;
+        std::fs::write(scratch.path("app/README.md"), "edited\n").unwrap();
+        std::fs::write(scratch.path("app/new.txt"), "untracked\n").unwrap();
+        std::fs::create_dir_all(scratch.path("app/build")).unwrap();
+        std::fs::write(scratch.path("app/build/out"), "ignored\n").unwrap();
+        let before = git.working_tree_status(&root).unwrap();
+
+        let reference = "refs/bunyan/checkpoints/ws1/20260101T000000000Z";
+        let saved = git.create_checkpoint(&root, reference, "Before Claude").unwrap();
+        assert_eq!(saved.head, head);
+        let after = git.working_tree_status(&root).unwrap();
+        assert_eq!((after.dirty, after.untracked, after.staged), (before.dirty, before.untracked, 0));
+        assert_eq!(git.list_checkpoints(&root, "refs/bunyan/checkpoints/ws1/").unwrap(), vec![saved.clone()]);
+        // Readable by the CLI backend too, with the same fields
+        assert_eq!(RealGit.list_checkpoints(&root, "refs/bunyan/checkpoints/ws1/").unwrap(), vec![saved.clone()]);
+        assert!(git.create_checkpoint(&root, reference, "Again").is_err());
+
+        // Claude commits, deletes and adds files
+        std::fs::remove_file(scratch.path("app/new.txt")).unwrap();
+        std::fs::write(scratch.path("app/stray.txt"), "stray\n").unwrap();
+        git.commit(&root, "Claude was here", &[], true).unwrap();
+        std::fs::write(scratch.path("app/later.txt"), "later\n").unwrap();
+
+        git.restore_checkpoint(&root, &saved.sha).unwrap();
+        assert_eq!(git.last_commit(&root).unwrap().unwrap().sha, head);
+        assert_eq!(read("app/README.md").as_deref(), Some("edited\n"));
+        assert_eq!(read("app/new.txt").as_deref(), Some("untracked\n"));
+        assert_eq!(read("app/stray.txt"), None);
+        assert_eq!(read("app/later.txt"), None);
+        assert_eq!(read("app/build/out").as_deref(), Some("ignored\n"));
+        let restored = git.working_tree_status(&root).unwrap();
+        assert_eq!((restored.dirty, restored.untracked, restored.staged), (1, 1, 0));
+    }
+
+    #[test]
+    fn submodule_update_checks_out_submodules_in_a_new_worktree() {
+        let scratch = Scratch::new("submodule");
+        let root = scratch.path("app");
+        init_repo(&scratch.path("lib"));
+        init_repo(&root);
+        let cli = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(["-c", "protocol.file.allow=always"])
+                .args(args)
+                .current_dir(&root)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?}", args);
+        };
+        cli(&["submodule", "add", &scratch.path("lib"), "lib"]);
+        cli(&["commit", "-m", "Add lib"]);
+        let git = NativeGit;
+
+        let ws = scratch.path("ws/fix");
+        let spec = WorktreeSpec::NewBranch { branch: "fix".to_string(), base: None };
+        git.worktree_add(&root, &ws, &spec, &[]).unwrap();
+        assert!(!Path::new(&scratch.path("ws/fix/lib/README.md")).exists());
+        git.submodule_update(&ws).unwrap();
+        assert!(Path::new(&scratch.path("ws/fix/lib/README.md")).exists());
+    }
+
+    #[test]
+    fn worktree_remove_refuses_changes_unless_forced() {
+        let scratch = Scratch::new("remove");
+        let root = scratch.path("app");
+        init_repo(&root);
+        let git = NativeGit;
+        let path = scratch.path("ws/fix");
+        let spec = WorktreeSpec::NewBranch { branch: "fix".to_string(), base: None };
+        git.worktree_add(&root, &path, &spec, &[]).unwrap();
+        std::fs::write(scratch.path("ws/fix/scratch.txt"), "x").unwrap();
+
+        assert!(git.worktree_remove(&root, &path, false).is_err());
+        git.worktree_remove(&root, &path, true).unwrap();
+        assert!(!Path::new(&path).exists());
+        assert_eq!(git.worktree_list(&root).unwrap().len(), 1);
+
+        git.delete_branch(&root, "fix").unwrap();
+        assert!(!git.ref_exists(&root, "fix").unwrap());
+    }
+}
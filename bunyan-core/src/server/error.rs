@@ -17,24 +17,40 @@ impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let (status, message) = match &self.0 {
             BunyanError::NotFound(msg) => (StatusCode::NOT_FOUND, msg.clone()),
+            BunyanError::Conflict(msg) => (StatusCode::CONFLICT, msg.clone()),
+            BunyanError::InvalidInput(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
             BunyanError::Database(e) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e))
             }
+            BunyanError::DatabaseUnavailable(msg) => {
+                (StatusCode::SERVICE_UNAVAILABLE, format!("Database unavailable: {}", msg))
+            }
             BunyanError::Serialization(e) => {
                 (StatusCode::BAD_REQUEST, format!("Serialization error: {}", e))
             }
             BunyanError::Git(msg) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, format!("Git error: {}", msg))
             }
+            BunyanError::BranchExists(msg) | BunyanError::PathExists(msg) => {
+                (StatusCode::CONFLICT, msg.clone())
+            }
+            BunyanError::GitAuth(msg) => {
+                (StatusCode::BAD_GATEWAY, format!("Git authentication failed: {}", msg))
+            }
             BunyanError::Process(msg) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, format!("Process error: {}", msg))
             }
             BunyanError::Docker(msg) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, format!("Docker error: {}", msg))
             }
+            BunyanError::Forge(msg) => (StatusCode::BAD_GATEWAY, format!("Forge error: {}", msg)),
+            BunyanError::Migration(msg) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, format!("Migration error: {}", msg))
+            }
         };
 
-        (status, Json(json!({ "error": message }))).into_response()
+        let code = self.0.code();
+        (status, Json(json!({ "error": message, "code": code }))).into_response()
     }
 }
 
@@ -53,6 +69,19 @@ mod tests {
         assert_eq!(status_of(BunyanError::NotFound("x".into())), StatusCode::NOT_FOUND);
     }
 
+    #[test]
+    fn conflict_maps_to_409() {
+        assert_eq!(status_of(BunyanError::Conflict("x".into())), StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn invalid_input_maps_to_400() {
+        assert_eq!(
+            status_of(BunyanError::InvalidInput("x".into())),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
     #[test]
     fn database_maps_to_500() {
         let db_err = rusqlite::Connection::open_in_memory()
@@ -97,4 +126,48 @@ mod tests {
             StatusCode::INTERNAL_SERVER_ERROR
         );
     }
+
+    #[test]
+    fn forge_maps_to_502() {
+        assert_eq!(
+            status_of(BunyanError::Forge("rate limited".into())),
+            StatusCode::BAD_GATEWAY
+        );
+    }
+
+    #[test]
+    fn existing_branch_or_path_maps_to_409() {
+        assert_eq!(status_of(BunyanError::BranchExists("fix".into())), StatusCode::CONFLICT);
+        assert_eq!(status_of(BunyanError::PathExists("/ws".into())), StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn git_auth_maps_to_502() {
+        assert_eq!(status_of(BunyanError::GitAuth("denied".into())), StatusCode::BAD_GATEWAY);
+    }
+
+    #[tokio::test]
+    async fn body_carries_error_code() {
+        let resp = ApiError(BunyanError::BranchExists("fix".into())).into_response();
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["code"], "branch_exists");
+        assert_eq!(body["error"], "fix");
+    }
+
+    #[test]
+    fn migration_maps_to_500() {
+        assert_eq!(
+            status_of(BunyanError::Migration("bad step".into())),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
+    #[test]
+    fn database_unavailable_maps_to_503() {
+        assert_eq!(
+            status_of(BunyanError::DatabaseUnavailable("pool timed out".into())),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
 }
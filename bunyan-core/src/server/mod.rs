@@ -1,4 +1,5 @@
 pub mod error;
+pub mod retention;
 pub mod routes;
 
 use std::sync::Arc;
@@ -19,11 +20,20 @@ use crate::state::AppState;
         routes::repos::get,
         routes::repos::create,
         routes::repos::update,
+        routes::repos::reorder,
         routes::repos::delete,
+        routes::repos::validate_config,
         routes::workspaces::list,
         routes::workspaces::get,
         routes::workspaces::create,
+        routes::workspaces::update,
+        routes::workspaces::get_config,
         routes::workspaces::archive,
+        routes::workspaces::delete,
+        routes::workspaces::purge,
+        routes::workspaces::suspend,
+        routes::workspaces::resume,
+        routes::workspaces::transitions,
         routes::workspaces::get_sessions,
         routes::workspaces::get_panes,
         routes::workspaces::start_claude,
@@ -34,27 +44,90 @@ use crate::state::AppState;
         routes::docker::status,
         routes::docker::container_status,
         routes::docker::container_ports,
+        routes::git::status,
+        routes::git::diff,
+        routes::git::commit,
+        routes::git::push,
+        routes::git::sync,
+        routes::git::sparse,
+        routes::git::widen_sparse,
+        routes::checkpoints::list,
+        routes::checkpoints::create,
+        routes::checkpoints::restore,
+        routes::hooks::list,
+        routes::hooks::get,
+        routes::forge::create,
         routes::editors::detect,
         routes::editors::open,
         routes::sessions::active,
         routes::system::info,
         routes::settings::list,
+        routes::settings::definitions,
         routes::settings::get,
         routes::settings::set,
+        routes::db::migrations,
+        routes::db::migrate,
+        routes::events::list,
+        routes::doctor::check,
+        routes::doctor::fix,
     ),
     components(schemas(
         models::Repo,
         models::CreateRepoInput,
         models::UpdateRepoInput,
+        models::RepoConfig,
+        models::CheckoutConfig,
+        models::WarningKind,
+        models::WorkspaceWarning,
+        models::RepoScripts,
+        models::HookKind,
+        models::HookRun,
+        models::ConfigFieldError,
+        models::ConfigValidation,
+        models::ValidateConfigInput,
+        models::ReorderReposInput,
         models::WorkspaceState,
         models::ContainerMode,
+        models::BranchMode,
         models::ContainerConfig,
         models::Workspace,
+        models::WorkspaceTransition,
+        models::ArchiveInput,
+        models::BranchOutcome,
+        models::BranchCleanup,
+        models::PurgeWorkspacesInput,
+        models::PurgeWorkspacesResult,
         models::CreateWorkspaceInput,
+        models::UpdateWorkspaceInput,
+        models::WorkspaceConfig,
+        models::ContainerOverrides,
+        models::EffectiveWorkspaceConfig,
         models::Setting,
         models::TmuxPane,
         models::WorkspacePaneInfo,
         models::PortMapping,
+        models::CommitSummary,
+        models::GitStatus,
+        models::DiffFileStat,
+        models::WorkspaceDiff,
+        models::CommitInput,
+        models::CommitStatus,
+        models::CommitResult,
+        models::PushStatus,
+        models::PushResult,
+        models::SyncStrategy,
+        models::SyncInput,
+        models::SyncStatus,
+        models::SyncResult,
+        models::SparseCheckout,
+        models::SparseInput,
+        models::Checkpoint,
+        models::CreateCheckpointInput,
+        models::RestoreCheckpointResult,
+        models::PullRequestState,
+        models::ChecksState,
+        models::PullRequest,
+        models::CreatePullRequestInput,
         models::ClaudeSessionEntry,
         models::StatusResponse,
         models::DockerStatusResponse,
@@ -62,7 +135,16 @@ use crate::state::AppState;
         models::ClaudeResumeInput,
         models::OpenEditorInput,
         models::SetSettingInput,
+        models::SettingType,
+        models::SettingDefinition,
+        models::EffectiveSetting,
         models::SystemInfo,
+        models::MigrationStatus,
+        models::EventKind,
+        models::Event,
+        models::IssueKind,
+        models::DoctorIssue,
+        models::DoctorReport,
         models::ErrorResponse,
     )),
     tags(
@@ -71,9 +153,16 @@ use crate::state::AppState;
         (name = "workspaces", description = "Workspace management"),
         (name = "sessions", description = "Claude session management"),
         (name = "docker", description = "Docker container management"),
+        (name = "git", description = "Worktree git state"),
+        (name = "checkpoints", description = "Worktree snapshots"),
+        (name = "hooks", description = "Repo lifecycle script runs"),
+        (name = "forge", description = "Pull requests on the repo's forge"),
         (name = "editors", description = "Editor detection and launch"),
         (name = "settings", description = "App settings"),
         (name = "system", description = "System information"),
+        (name = "db", description = "Database maintenance"),
+        (name = "events", description = "Audit event log"),
+        (name = "doctor", description = "Drift detection and repair"),
     )
 )]
 struct ApiDoc;
@@ -90,17 +179,38 @@ pub fn build_router(state: Arc<AppState>) -> Router {
         // Repos
         .route("/repos", get(routes::repos::list))
         .route("/repos", post(routes::repos::create))
+        .route("/repos/reorder", post(routes::repos::reorder))
         .route("/repos/{id}", get(routes::repos::get))
         .route("/repos/{id}", put(routes::repos::update))
         .route("/repos/{id}", delete(routes::repos::delete))
+        .route(
+            "/repos/{id}/config/validate",
+            post(routes::repos::validate_config),
+        )
         // Workspaces
         .route("/workspaces", get(routes::workspaces::list))
         .route("/workspaces", post(routes::workspaces::create))
+        .route("/workspaces/purge", post(routes::workspaces::purge))
         .route("/workspaces/{id}", get(routes::workspaces::get))
+        .route("/workspaces/{id}", put(routes::workspaces::update))
+        .route("/workspaces/{id}", delete(routes::workspaces::delete))
+        .route("/workspaces/{id}/config", get(routes::workspaces::get_config))
         .route(
             "/workspaces/{id}/archive",
             post(routes::workspaces::archive),
         )
+        .route(
+            "/workspaces/{id}/suspend",
+            post(routes::workspaces::suspend),
+        )
+        .route(
+            "/workspaces/{id}/resume",
+            post(routes::workspaces::resume),
+        )
+        .route(
+            "/workspaces/{id}/transitions",
+            get(routes::workspaces::transitions),
+        )
         .route(
             "/workspaces/{id}/sessions",
             get(routes::workspaces::get_sessions),
@@ -136,6 +246,33 @@ pub fn build_router(state: Arc<AppState>) -> Router {
             "/workspaces/{id}/container/ports",
             get(routes::docker::container_ports),
         )
+        // Git
+        .route("/workspaces/{id}/git/status", get(routes::git::status))
+        .route("/workspaces/{id}/diff", get(routes::git::diff))
+        .route("/workspaces/{id}/commit", post(routes::git::commit))
+        .route("/workspaces/{id}/push", post(routes::git::push))
+        .route("/workspaces/{id}/sync", post(routes::git::sync))
+        .route(
+            "/workspaces/{id}/sparse",
+            get(routes::git::sparse).post(routes::git::widen_sparse),
+        )
+        // Checkpoints
+        .route(
+            "/workspaces/{id}/checkpoints",
+            get(routes::checkpoints::list).post(routes::checkpoints::create),
+        )
+        .route(
+            "/workspaces/{id}/checkpoints/{checkpoint_id}/restore",
+            post(routes::checkpoints::restore),
+        )
+        // Hooks
+        .route("/workspaces/{id}/hooks", get(routes::hooks::list))
+        .route("/workspaces/{id}/hooks/{run}", get(routes::hooks::get))
+        // Forge
+        .route(
+            "/workspaces/{id}/pull-request",
+            post(routes::forge::create),
+        )
         // Editors
         .route("/editors", get(routes::editors::detect))
         .route("/workspaces/{id}/editor", post(routes::editors::open))
@@ -145,13 +282,24 @@ pub fn build_router(state: Arc<AppState>) -> Router {
         .route("/system/info", get(routes::system::info))
         // Settings
         .route("/settings", get(routes::settings::list))
+        .route("/settings/definitions", get(routes::settings::definitions))
         .route("/settings/{key}", get(routes::settings::get))
         .route("/settings/{key}", put(routes::settings::set))
+        // Events
+        .route("/events", get(routes::events::list))
+        // Doctor
+        .route("/doctor", get(routes::doctor::check))
+        .route("/doctor/fix", post(routes::doctor::fix))
+        // Database
+        .route("/db/migrations", get(routes::db::migrations))
+        .route("/db/migrate", post(routes::db::migrate))
         .layer(CorsLayer::permissive())
         .with_state(state)
 }
 
 pub async fn start_server(state: Arc<AppState>, port: u16) {
+    tokio::spawn(retention::run(state.clone()));
+
     let app = build_router(state);
 
     // Write port file for discovery
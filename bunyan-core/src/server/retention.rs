@@ -0,0 +1,123 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use rusqlite::Connection;
+
+use crate::db;
+use crate::error::{BunyanError, Result};
+use crate::models::EventKind;
+use crate::settings::{self, ARCHIVE_RETENTION};
+use crate::state::AppState;
+use crate::workspace;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Purge archived workspaces older than the configured retention, if any.
+/// Returns how many workspaces were removed.
+pub fn apply(conn: &Connection) -> Result<usize> {
+    let retention = match settings::get_effective(conn, ARCHIVE_RETENTION) {
+        Ok(setting) if !setting.value.trim().is_empty() => setting.value,
+        Ok(_) | Err(BunyanError::NotFound(_)) => return Ok(0),
+        Err(e) => return Err(e),
+    };
+
+    let cutoff = workspace::age_cutoff(&retention)?;
+    let purged = db::workspaces::purge_archived(conn, Some(cutoff), None, false)?;
+    for ws in &purged {
+        db::events::audit(
+            conn,
+            EventKind::WorkspacePurged,
+            Some(&ws.repository_id),
+            Some(&ws.id),
+            Some(&format!("retention {}", retention)),
+        );
+    }
+    Ok(purged.len())
+}
+
+/// Apply the retention policy now and then once per interval, for the life of the server.
+pub async fn run(state: Arc<AppState>) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    loop {
+        interval.tick().await;
+        if let Err(e) = state.db.call(apply).await {
+            eprintln!("Warning: archive retention failed: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::schema::initialize_database;
+    use crate::models::{BranchMode, ContainerMode, CreateRepoInput, CreateWorkspaceInput};
+
+    fn test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn
+    }
+
+    fn archived_workspace(conn: &Connection, archived_at: &str) -> String {
+        let repo = db::repos::create(
+            conn,
+            CreateRepoInput {
+                name: "frontend".to_string(),
+                remote_url: "git@github.com:org/frontend.git".to_string(),
+                root_path: "/repos/frontend".to_string(),
+                default_branch: "main".to_string(),
+                remote: "origin".to_string(),
+                display_order: 0,
+                config: None,
+                adopt: false,
+            },
+        )
+        .unwrap();
+        let ws = db::workspaces::create(
+            conn,
+            CreateWorkspaceInput {
+                repository_id: repo.id,
+                directory_name: "old".to_string(),
+                branch: "main".to_string(),
+                container_mode: ContainerMode::Local,
+                branch_mode: BranchMode::New,
+                base_ref: None,
+                config: None,
+            },
+        )
+        .unwrap();
+        db::workspaces::archive(conn, &ws.id).unwrap();
+        conn.execute(
+            "UPDATE workspace_transitions SET created_at = ?1 WHERE workspace_id = ?2 AND to_state = 'archived'",
+            [archived_at, &ws.id],
+        )
+        .unwrap();
+        ws.id
+    }
+
+    #[test]
+    fn apply_without_setting_keeps_everything() {
+        let conn = test_db();
+        let id = archived_workspace(&conn, "2020-01-01T00:00:00+00:00");
+
+        assert_eq!(apply(&conn).unwrap(), 0);
+        assert!(db::workspaces::get(&conn, &id).is_ok());
+    }
+
+    #[test]
+    fn apply_purges_workspaces_past_retention() {
+        let conn = test_db();
+        let id = archived_workspace(&conn, "2020-01-01T00:00:00+00:00");
+        db::settings::set(&conn, ARCHIVE_RETENTION, "30d").unwrap();
+
+        assert_eq!(apply(&conn).unwrap(), 1);
+        assert!(db::workspaces::get(&conn, &id).is_err());
+    }
+
+    #[test]
+    fn apply_rejects_malformed_retention() {
+        let conn = test_db();
+        db::settings::set(&conn, ARCHIVE_RETENTION, "forever").unwrap();
+        assert!(matches!(apply(&conn), Err(BunyanError::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::Json;
+
+use crate::db;
+use crate::error::BunyanError;
+use crate::git;
+use crate::models::{Checkpoint, CreateCheckpointInput, ErrorResponse, EventKind, RestoreCheckpointResult, Workspace};
+use crate::server::error::ApiError;
+use crate::server::routes::git::{require_worktree, resolve};
+use crate::settings;
+use crate::state::AppState;
+use crate::workspace;
+
+#[utoipa::path(get, path = "/workspaces/{id}/checkpoints", params(("id" = String, Path, description = "Workspace ID")), responses((status = 200, body = Vec<Checkpoint>), (status = 404, body = ErrorResponse), (status = 500, body = ErrorResponse)), operation_id = "list_checkpoints", tag = "checkpoints")]
+pub async fn list(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<Checkpoint>>, ApiError> {
+    let (ws, repo, _, git) = resolve(&state, &id).await?;
+
+    let checkpoints = tokio::task::spawn_blocking(move || workspace::list_checkpoints(git.as_ref(), &repo, &ws))
+        .await
+        .map_err(|e| ApiError(BunyanError::Process(e.to_string())))??;
+
+    Ok(Json(checkpoints))
+}
+
+#[utoipa::path(post, path = "/workspaces/{id}/checkpoints", params(("id" = String, Path, description = "Workspace ID")), request_body(content = Option<CreateCheckpointInput>), responses((status = 200, body = Checkpoint), (status = 400, body = ErrorResponse), (status = 404, body = ErrorResponse), (status = 409, body = ErrorResponse), (status = 500, body = ErrorResponse)), operation_id = "create_checkpoint", tag = "checkpoints")]
+pub async fn create(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    input: Option<Json<CreateCheckpointInput>>,
+) -> Result<Json<Checkpoint>, ApiError> {
+    let input = input.map(|Json(i)| i).unwrap_or_default();
+    let (ws, repo, ws_path, git) = resolve(&state, &id).await?;
+    require_worktree(&ws_path)?;
+
+    let checkpoint = tokio::task::spawn_blocking(move || {
+        workspace::create_checkpoint(git.as_ref(), &ws, &ws_path, input.message.as_deref())
+    })
+    .await
+    .map_err(|e| ApiError(BunyanError::Process(e.to_string())))??;
+
+    let message = format!("{}: {}", checkpoint.id, checkpoint.message);
+    db::events::audit_async(&state.db, EventKind::CheckpointCreated, Some(&repo.id), Some(&id), Some(&message)).await;
+    Ok(Json(checkpoint))
+}
+
+#[utoipa::path(post, path = "/workspaces/{id}/checkpoints/{checkpoint_id}/restore", params(("id" = String, Path, description = "Workspace ID"), ("checkpoint_id" = String, Path, description = "Checkpoint ID")), responses((status = 200, body = RestoreCheckpointResult), (status = 400, body = ErrorResponse), (status = 404, body = ErrorResponse), (status = 409, body = ErrorResponse), (status = 500, body = ErrorResponse)), operation_id = "restore_checkpoint", tag = "checkpoints")]
+pub async fn restore(
+    State(state): State<Arc<AppState>>,
+    Path((id, checkpoint_id)): Path<(String, String)>,
+) -> Result<Json<RestoreCheckpointResult>, ApiError> {
+    let (ws, repo, ws_path, git) = resolve(&state, &id).await?;
+    require_worktree(&ws_path)?;
+
+    let repo_id = repo.id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        workspace::restore_checkpoint(git.as_ref(), &repo, &ws, &ws_path, &checkpoint_id)
+    })
+    .await
+    .map_err(|e| ApiError(BunyanError::Process(e.to_string())))??;
+
+    let message = format!("Restored {} (backup {})", result.restored.id, result.backup.id);
+    db::events::audit_async(&state.db, EventKind::CheckpointRestored, Some(&repo_id), Some(&id), Some(&message)).await;
+    Ok(Json(result))
+}
+
+/// Take a checkpoint before Claude is launched in a workspace when the
+/// `auto_checkpoint` setting is on. Failures are logged rather than returned
+/// so they never stop Claude from starting.
+pub(crate) async fn auto_checkpoint(state: &AppState, ws: &Workspace, ws_path: &str, reason: &str) {
+    let loaded = state
+        .db
+        .call(|conn| {
+            let enabled = settings::get_effective(conn, settings::AUTO_CHECKPOINT).is_ok_and(|s| s.value == "true");
+            Ok((enabled, git::backend(conn)))
+        })
+        .await;
+    let Ok((enabled, git)) = loaded else {
+        return;
+    };
+    if !enabled || !std::path::Path::new(ws_path).exists() {
+        return;
+    }
+
+    let result = tokio::task::spawn_blocking({
+        let ws = ws.clone();
+        let ws_path = ws_path.to_string();
+        let reason = reason.to_string();
+        move || workspace::create_checkpoint(git.as_ref(), &ws, &ws_path, Some(&reason))
+    })
+    .await;
+    match result {
+        Ok(Ok(checkpoint)) => {
+            let message = format!("{}: {}", checkpoint.id, checkpoint.message);
+            db::events::audit_async(&state.db, EventKind::CheckpointCreated, Some(&ws.repository_id), Some(&ws.id), Some(&message))
+                .await;
+        }
+        Ok(Err(e)) => eprintln!("Warning: auto checkpoint of {} failed: {}", ws.id, e),
+        Err(e) => eprintln!("Warning: auto checkpoint of {} failed: {}", ws.id, e),
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::Json;
+
+use crate::db;
+use crate::models::{ErrorResponse, MigrationStatus};
+use crate::server::error::ApiError;
+use crate::state::AppState;
+
+#[utoipa::path(get, path = "/db/migrations", responses((status = 200, body = Vec<MigrationStatus>), (status = 500, body = ErrorResponse)), operation_id = "migration_status", tag = "db")]
+pub async fn migrations(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<MigrationStatus>>, ApiError> {
+    let status = state.db.call(db::migrations::status).await?;
+    Ok(Json(status))
+}
+
+#[utoipa::path(post, path = "/db/migrate", responses((status = 200, body = Vec<MigrationStatus>), (status = 500, body = ErrorResponse)), operation_id = "run_migrations", tag = "db")]
+pub async fn migrate(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<MigrationStatus>>, ApiError> {
+    let status = state
+        .db
+        .call(|conn| {
+            db::migrations::run(conn)?;
+            db::migrations::status(conn)
+        })
+        .await?;
+    Ok(Json(status))
+}
@@ -20,11 +20,7 @@ pub async fn container_status(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
 ) -> Result<Json<ContainerStatusResponse>, ApiError> {
-    let container_id = {
-        let conn = state.db.lock().unwrap();
-        let ws = db::workspaces::get(&conn, &id)?;
-        ws.container_id
-    };
+    let container_id = state.db.call(move |conn| db::workspaces::get(conn, &id)).await?.container_id;
 
     let status = match container_id {
         Some(id) => docker::get_container_status(&id).await.map_err(ApiError)?,
@@ -39,11 +35,7 @@ pub async fn container_ports(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
 ) -> Result<Json<Vec<PortMapping>>, ApiError> {
-    let container_id = {
-        let conn = state.db.lock().unwrap();
-        let ws = db::workspaces::get(&conn, &id)?;
-        ws.container_id
-    };
+    let container_id = state.db.call(move |conn| db::workspaces::get(conn, &id)).await?.container_id;
 
     let ports = match container_id {
         Some(id) => docker::get_container_ports(&id).await.map_err(ApiError)?,
@@ -0,0 +1,19 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::Json;
+
+use crate::doctor;
+use crate::models::{DoctorReport, ErrorResponse};
+use crate::server::error::ApiError;
+use crate::state::AppState;
+
+#[utoipa::path(get, path = "/doctor", responses((status = 200, body = DoctorReport), (status = 500, body = ErrorResponse)), operation_id = "doctor_check", tag = "doctor")]
+pub async fn check(State(state): State<Arc<AppState>>) -> Result<Json<DoctorReport>, ApiError> {
+    Ok(Json(doctor::run(&state, false).await?))
+}
+
+#[utoipa::path(post, path = "/doctor/fix", responses((status = 200, body = DoctorReport), (status = 500, body = ErrorResponse)), operation_id = "doctor_fix", tag = "doctor")]
+pub async fn fix(State(state): State<Arc<AppState>>) -> Result<Json<DoctorReport>, ApiError> {
+    Ok(Json(doctor::run(&state, true).await?))
+}
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use axum::extract::{Path, State};
 use axum::Json;
 
+use crate::config;
 use crate::editor;
 use crate::models::{ErrorResponse, OpenEditorInput, StatusResponse};
 use crate::server::error::ApiError;
@@ -11,7 +12,7 @@ use crate::workspace;
 
 #[utoipa::path(get, path = "/editors", responses((status = 200, body = Vec<String>), (status = 500, body = ErrorResponse)), operation_id = "detect_editors", tag = "editors")]
 pub async fn detect() -> Result<Json<Vec<String>>, ApiError> {
-    let editors = tokio::task::spawn_blocking(|| editor::detect_installed_editors())
+    let editors = tokio::task::spawn_blocking(editor::detect_installed_editors)
         .await
         .map_err(|e| ApiError(crate::error::BunyanError::Process(e.to_string())))?;
 
@@ -24,18 +25,27 @@ pub async fn open(
     Path(id): Path<String>,
     Json(input): Json<OpenEditorInput>,
 ) -> Result<Json<StatusResponse>, ApiError> {
-    let ed = editor::Editor::from_id(&input.editor_id).ok_or_else(|| {
+    let requested = input.editor_id;
+    let (ws, repo, ws_path, editor_id) = state
+        .db
+        .call(move |conn| {
+            let (ws, repo, ws_path) = workspace::resolve_workspace_path(conn, &id)?;
+            // Fall back to the workspace override, then the preferred_editor setting
+            let editor_id = match requested {
+                Some(id) => id,
+                None => config::resolve(conn, &repo, &ws)?.editor,
+            };
+            Ok((ws, repo, ws_path, editor_id))
+        })
+        .await?;
+
+    let ed = editor::Editor::from_id(&editor_id).ok_or_else(|| {
         ApiError(crate::error::BunyanError::NotFound(format!(
             "Unknown editor: {}",
-            input.editor_id
+            editor_id
         )))
     })?;
 
-    let (ws, repo, ws_path) = {
-        let conn = state.db.lock().unwrap();
-        workspace::resolve_workspace_path(&conn, &id)?
-    };
-
     if ed == editor::Editor::Iterm {
         let rn = repo.name.clone();
         let wn = ws.directory_name.clone();
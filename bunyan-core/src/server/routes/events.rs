@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use axum::Json;
+use serde::Deserialize;
+
+use crate::db;
+use crate::models::{ErrorResponse, Event, EventKind};
+use crate::server::error::ApiError;
+use crate::state::AppState;
+
+#[derive(Deserialize)]
+pub struct ListQuery {
+    pub repo_id: Option<String>,
+    pub workspace_id: Option<String>,
+    pub since: Option<String>,
+    pub kind: Option<EventKind>,
+    pub limit: Option<u32>,
+}
+
+#[utoipa::path(get, path = "/events", params(("repo_id" = Option<String>, Query, description = "Filter by repository ID"), ("workspace_id" = Option<String>, Query, description = "Filter by workspace ID"), ("since" = Option<String>, Query, description = "Only events at or after this RFC 3339 timestamp"), ("kind" = Option<EventKind>, Query, description = "Filter by event kind"), ("limit" = Option<u32>, Query, description = "Maximum number of events (default 100)")), responses((status = 200, body = Vec<Event>), (status = 400, body = ErrorResponse), (status = 500, body = ErrorResponse)), operation_id = "list_events", tag = "events")]
+pub async fn list(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ListQuery>,
+) -> Result<Json<Vec<Event>>, ApiError> {
+    let filter = db::events::EventFilter {
+        repo_id: query.repo_id,
+        workspace_id: query.workspace_id,
+        since: query.since,
+        kind: query.kind,
+        limit: query.limit,
+    };
+    let events = state
+        .db
+        .call(move |conn| db::events::list(conn, &filter))
+        .await?;
+    Ok(Json(events))
+}
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::Json;
+
+use crate::db;
+use crate::error::BunyanError;
+use crate::forge;
+use crate::models::{
+    CreatePullRequestInput, ErrorResponse, EventKind, PullRequest, Workspace, WorkspaceState,
+};
+use crate::server::error::ApiError;
+use crate::server::routes::git::resolve;
+use crate::state::AppState;
+use crate::workspace;
+
+#[utoipa::path(post, path = "/workspaces/{id}/pull-request", params(("id" = String, Path, description = "Workspace ID")), request_body(content = Option<CreatePullRequestInput>, description = "Optional title, body, base and draft flag"), responses((status = 200, body = PullRequest), (status = 400, body = ErrorResponse), (status = 404, body = ErrorResponse), (status = 409, body = ErrorResponse), (status = 502, body = ErrorResponse)), operation_id = "workspace_pull_request", tag = "forge")]
+pub async fn create(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    input: Option<Json<CreatePullRequestInput>>,
+) -> Result<Json<PullRequest>, ApiError> {
+    let input = input.map(|Json(i)| i).unwrap_or_default();
+    let (ws, repo, ws_path, git) = resolve(&state, &id).await?;
+    super::git::require_worktree(&ws_path)?;
+
+    let enterprise_hosts = state.db.call(|conn| Ok(forge::enterprise_hosts(conn))).await?;
+    let repo_id = repo.id.clone();
+    let pr = tokio::task::spawn_blocking(move || {
+        let forge = forge::for_repo(&repo, &enterprise_hosts)?;
+        workspace::open_pull_request(forge.as_ref(), git.as_ref(), &repo, &ws_path, &input)
+    })
+    .await
+    .map_err(|e| ApiError(BunyanError::Process(e.to_string())))??;
+
+    let opened = ws.pull_request.as_ref().map(|p| p.number) != Some(pr.number);
+    let stored = pr.clone();
+    state
+        .db
+        .call(move |conn| {
+            db::workspaces::set_pull_request(conn, &id, &stored)?;
+            if opened {
+                db::events::audit(
+                    conn,
+                    EventKind::PullRequestOpened,
+                    Some(&repo_id),
+                    Some(&id),
+                    Some(&format!("#{} {}", stored.number, stored.url)),
+                );
+            }
+            Ok(())
+        })
+        .await?;
+    Ok(Json(pr))
+}
+
+/// Bring a workspace's pull request state up to date from its forge.
+/// Best-effort: a forge that can't be reached leaves the stored state as is.
+pub(crate) async fn refresh(state: &AppState, mut ws: Workspace) -> Workspace {
+    if ws.state == WorkspaceState::Archived {
+        return ws;
+    }
+    let repo_id = ws.repository_id.clone();
+    let Ok((repo, enterprise_hosts)) = state
+        .db
+        .call(move |conn| Ok((db::repos::get(conn, &repo_id)?, forge::enterprise_hosts(conn))))
+        .await
+    else {
+        return ws;
+    };
+    let current = ws.clone();
+    let refreshed = tokio::task::spawn_blocking(move || {
+        let forge = forge::for_repo(&repo, &enterprise_hosts)?;
+        workspace::refresh_pull_request(forge.as_ref(), &current)
+    })
+    .await;
+
+    if let Ok(Ok(Some(pr))) = refreshed {
+        if ws.pull_request.as_ref() != Some(&pr) {
+            let (id, stored) = (ws.id.clone(), pr.clone());
+            let _ = state.db.call(move |conn| db::workspaces::set_pull_request(conn, &id, &stored)).await;
+            ws.pull_request = Some(pr);
+        }
+    }
+    ws
+}
@@ -0,0 +1,227 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use serde::Deserialize;
+
+use crate::db;
+use crate::error::BunyanError;
+use crate::git::{self, GitOps};
+use crate::models::{
+    CommitInput, CommitResult, CommitStatus, ErrorResponse, EventKind, GitStatus, PushResult,
+    PushStatus, Repo, SparseCheckout, SparseInput, SyncInput, SyncResult, SyncStatus, SyncStrategy,
+    Workspace, WorkspaceDiff,
+};
+use crate::server::error::ApiError;
+use crate::state::AppState;
+use crate::workspace;
+
+#[utoipa::path(get, path = "/workspaces/{id}/git/status", params(("id" = String, Path, description = "Workspace ID")), responses((status = 200, body = GitStatus), (status = 404, body = ErrorResponse), (status = 500, body = ErrorResponse)), operation_id = "workspace_git_status", tag = "git")]
+pub async fn status(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<GitStatus>, ApiError> {
+    let (ws, repo, ws_path, git) = resolve(&state, &id).await?;
+
+    let status = tokio::task::spawn_blocking(move || {
+        workspace::git_status(git.as_ref(), &repo, &ws, &ws_path)
+    })
+    .await
+    .map_err(|e| ApiError(BunyanError::Process(e.to_string())))??;
+
+    Ok(Json(status))
+}
+
+#[derive(Deserialize)]
+pub struct DiffQuery {
+    pub base: Option<String>,
+    /// Comma-separated paths to limit the diff to
+    pub paths: Option<String>,
+    #[serde(default)]
+    pub stat: bool,
+    pub max_bytes: Option<usize>,
+}
+
+#[utoipa::path(get, path = "/workspaces/{id}/diff", params(("id" = String, Path, description = "Workspace ID"), ("base" = Option<String>, Query, description = "Ref to compare against (default: <remote>/<default_branch>)"), ("paths" = Option<String>, Query, description = "Comma-separated paths to limit the diff to"), ("stat" = Option<bool>, Query, description = "Only return per-file stats"), ("max_bytes" = Option<usize>, Query, description = "Patch size cap in bytes (default 1 MiB)")), responses((status = 200, body = WorkspaceDiff), (status = 400, body = ErrorResponse), (status = 404, body = ErrorResponse), (status = 500, body = ErrorResponse)), operation_id = "workspace_diff", tag = "git")]
+pub async fn diff(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Query(query): Query<DiffQuery>,
+) -> Result<Json<WorkspaceDiff>, ApiError> {
+    let (ws, repo, ws_path, git) = resolve(&state, &id).await?;
+    let options = workspace::DiffOptions {
+        base: query.base,
+        paths: query
+            .paths
+            .as_deref()
+            .map(|p| {
+                p.split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        stat_only: query.stat,
+        max_bytes: query.max_bytes,
+    };
+
+    let diff = tokio::task::spawn_blocking(move || {
+        workspace::diff(git.as_ref(), &repo, &ws, &ws_path, &options)
+    })
+    .await
+    .map_err(|e| ApiError(BunyanError::Process(e.to_string())))??;
+
+    Ok(Json(diff))
+}
+
+/// Load a workspace, its repo and worktree path, and the configured git backend.
+pub(crate) async fn resolve(
+    state: &AppState,
+    id: &str,
+) -> Result<(Workspace, Repo, String, Arc<dyn GitOps>), ApiError> {
+    let id = id.to_string();
+    let resolved = state
+        .db
+        .call(move |conn| {
+            let (ws, repo, ws_path) = workspace::resolve_workspace_path(conn, &id)?;
+            Ok((ws, repo, ws_path, git::backend(conn)))
+        })
+        .await?;
+    Ok(resolved)
+}
+
+/// Commit and push need the worktree on disk; archived or never-created
+/// workspaces don't have one.
+pub(crate) fn require_worktree(ws_path: &str) -> Result<(), ApiError> {
+    if std::path::Path::new(ws_path).is_dir() {
+        Ok(())
+    } else {
+        Err(ApiError(BunyanError::Conflict(format!("Worktree {} does not exist", ws_path))))
+    }
+}
+
+#[utoipa::path(post, path = "/workspaces/{id}/commit", params(("id" = String, Path, description = "Workspace ID")), request_body = CommitInput, responses((status = 200, body = CommitResult), (status = 400, body = ErrorResponse), (status = 404, body = ErrorResponse), (status = 409, body = ErrorResponse), (status = 500, body = ErrorResponse)), operation_id = "workspace_commit", tag = "git")]
+pub async fn commit(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(input): Json<CommitInput>,
+) -> Result<Json<CommitResult>, ApiError> {
+    let (ws, repo, ws_path, git) = resolve(&state, &id).await?;
+    require_worktree(&ws_path)?;
+
+    let result = tokio::task::spawn_blocking(move || {
+        workspace::commit(git.as_ref(), &ws, &ws_path, &input)
+    })
+    .await
+    .map_err(|e| ApiError(BunyanError::Process(e.to_string())))??;
+
+    if let (CommitStatus::Committed, Some(c)) = (&result.status, &result.commit) {
+        db::events::audit_async(
+            &state.db,
+            EventKind::WorkspaceCommitted,
+            Some(&repo.id),
+            Some(&id),
+            Some(&format!("{} {}", c.sha, c.summary)),
+        )
+        .await;
+    }
+    Ok(Json(result))
+}
+
+#[utoipa::path(post, path = "/workspaces/{id}/push", params(("id" = String, Path, description = "Workspace ID")), responses((status = 200, body = PushResult), (status = 400, body = ErrorResponse), (status = 404, body = ErrorResponse), (status = 409, body = ErrorResponse), (status = 500, body = ErrorResponse)), operation_id = "workspace_push", tag = "git")]
+pub async fn push(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<PushResult>, ApiError> {
+    let (ws, repo, ws_path, git) = resolve(&state, &id).await?;
+    require_worktree(&ws_path)?;
+
+    let repo_id = repo.id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        workspace::push(git.as_ref(), &repo, &ws, &ws_path)
+    })
+    .await
+    .map_err(|e| ApiError(BunyanError::Process(e.to_string())))??;
+
+    if result.status == PushStatus::Pushed {
+        db::events::audit_async(
+            &state.db,
+            EventKind::WorkspacePushed,
+            Some(&repo_id),
+            Some(&id),
+            Some(&format!("{} to {}", result.branch, result.remote)),
+        )
+        .await;
+    }
+    Ok(Json(result))
+}
+
+#[utoipa::path(post, path = "/workspaces/{id}/sync", params(("id" = String, Path, description = "Workspace ID")), request_body(content = Option<SyncInput>), responses((status = 200, body = SyncResult), (status = 400, body = ErrorResponse), (status = 404, body = ErrorResponse), (status = 409, body = ErrorResponse), (status = 500, body = ErrorResponse)), operation_id = "workspace_sync", tag = "git")]
+pub async fn sync(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    input: Option<Json<SyncInput>>,
+) -> Result<Json<SyncResult>, ApiError> {
+    let input = input.map(|Json(input)| input).unwrap_or_default();
+    let (ws, repo, ws_path, git) = resolve(&state, &id).await?;
+    require_worktree(&ws_path)?;
+
+    let repo_id = repo.id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        workspace::sync(git.as_ref(), &repo, &ws, &ws_path, &input)
+    })
+    .await
+    .map_err(|e| ApiError(BunyanError::Process(e.to_string())))??;
+
+    if result.status != SyncStatus::UpToDate {
+        let verb = match result.strategy {
+            SyncStrategy::Rebase => "Rebased onto",
+            SyncStrategy::Merge => "Merged",
+        };
+        let message = match result.status {
+            SyncStatus::Conflicted => format!(
+                "{} {}: {} conflicted file(s){}",
+                verb,
+                result.base,
+                result.conflicts.len(),
+                if result.aborted { ", aborted" } else { "" }
+            ),
+            _ => format!("{} {}", verb, result.base),
+        };
+        db::events::audit_async(&state.db, EventKind::WorkspaceSynced, Some(&repo_id), Some(&id), Some(&message)).await;
+    }
+    Ok(Json(result))
+}
+
+#[utoipa::path(get, path = "/workspaces/{id}/sparse", params(("id" = String, Path, description = "Workspace ID")), responses((status = 200, body = SparseCheckout), (status = 404, body = ErrorResponse), (status = 409, body = ErrorResponse), (status = 500, body = ErrorResponse)), operation_id = "workspace_sparse_checkout", tag = "git")]
+pub async fn sparse(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<SparseCheckout>, ApiError> {
+    let (ws, _, ws_path, git) = resolve(&state, &id).await?;
+    require_worktree(&ws_path)?;
+
+    let sparse = tokio::task::spawn_blocking(move || workspace::sparse_checkout(git.as_ref(), &ws, &ws_path))
+        .await
+        .map_err(|e| ApiError(BunyanError::Process(e.to_string())))??;
+
+    Ok(Json(sparse))
+}
+
+#[utoipa::path(post, path = "/workspaces/{id}/sparse", params(("id" = String, Path, description = "Workspace ID")), request_body = SparseInput, responses((status = 200, body = SparseCheckout), (status = 400, body = ErrorResponse), (status = 404, body = ErrorResponse), (status = 409, body = ErrorResponse), (status = 500, body = ErrorResponse)), operation_id = "workspace_widen_sparse", tag = "git")]
+pub async fn widen_sparse(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(input): Json<SparseInput>,
+) -> Result<Json<SparseCheckout>, ApiError> {
+    let (ws, _, ws_path, git) = resolve(&state, &id).await?;
+    require_worktree(&ws_path)?;
+
+    let sparse = tokio::task::spawn_blocking(move || {
+        workspace::widen_sparse(git.as_ref(), &ws, &ws_path, &input)
+    })
+    .await
+    .map_err(|e| ApiError(BunyanError::Process(e.to_string())))??;
+
+    Ok(Json(sparse))
+}
@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::Json;
+
+use crate::db;
+use crate::models::{ErrorResponse, HookRun};
+use crate::server::error::ApiError;
+use crate::state::AppState;
+
+#[utoipa::path(get, path = "/workspaces/{id}/hooks", params(("id" = String, Path, description = "Workspace ID")), responses((status = 200, body = Vec<HookRun>), (status = 404, body = ErrorResponse)), operation_id = "list_hook_runs", tag = "hooks")]
+pub async fn list(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<HookRun>>, ApiError> {
+    let runs = state
+        .db
+        .call(move |conn| {
+            db::workspaces::get(conn, &id)?;
+            db::hooks::list(conn, &id)
+        })
+        .await?;
+    Ok(Json(runs))
+}
+
+#[utoipa::path(get, path = "/workspaces/{id}/hooks/{run}", params(("id" = String, Path, description = "Workspace ID"), ("run" = i64, Path, description = "Hook run ID")), responses((status = 200, body = HookRun), (status = 404, body = ErrorResponse)), operation_id = "get_hook_run", tag = "hooks")]
+pub async fn get(
+    State(state): State<Arc<AppState>>,
+    Path((id, run)): Path<(String, i64)>,
+) -> Result<Json<HookRun>, ApiError> {
+    let run = state.db.call(move |conn| db::hooks::get(conn, &id, run)).await?;
+    Ok(Json(run))
+}
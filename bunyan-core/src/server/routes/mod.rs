@@ -6,3 +6,10 @@ pub mod docker;
 pub mod settings;
 pub mod editors;
 pub mod system;
+pub mod db;
+pub mod events;
+pub mod git;
+pub mod doctor;
+pub mod forge;
+pub mod checkpoints;
+pub mod hooks;
@@ -3,9 +3,15 @@ use std::sync::Arc;
 use axum::extract::{Path, State};
 use axum::Json;
 
+use crate::config;
 use crate::db;
-use crate::git::{GitOps, RealGit};
-use crate::models::{CreateRepoInput, ErrorResponse, Repo, UpdateRepoInput};
+use crate::error::BunyanError;
+use crate::git;
+use crate::models::{
+    BranchMode, ConfigValidation, ContainerMode, CreateRepoInput, CreateWorkspaceInput,
+    ErrorResponse, EventKind, ReorderReposInput, Repo, UpdateRepoInput, ValidateConfigInput,
+};
+use crate::workspace;
 use crate::server::error::ApiError;
 use crate::state::AppState;
 
@@ -19,8 +25,7 @@ use crate::state::AppState;
     operation_id = "list_repos", tag = "repos"
 )]
 pub async fn list(State(state): State<Arc<AppState>>) -> Result<Json<Vec<Repo>>, ApiError> {
-    let conn = state.db.lock().unwrap();
-    let repos = db::repos::list(&conn)?;
+    let repos = state.db.call(db::repos::list).await?;
     Ok(Json(repos))
 }
 
@@ -39,8 +44,10 @@ pub async fn get(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
 ) -> Result<Json<Repo>, ApiError> {
-    let conn = state.db.lock().unwrap();
-    let repo = db::repos::get(&conn, &id)?;
+    let repo = state
+        .db
+        .call(move |conn| db::repos::get(conn, &id))
+        .await?;
     Ok(Json(repo))
 }
 
@@ -50,6 +57,8 @@ pub async fn get(
     request_body = CreateRepoInput,
     responses(
         (status = 200, body = Repo),
+        (status = 400, body = ErrorResponse),
+        (status = 409, body = ErrorResponse),
         (status = 500, body = ErrorResponse)
     ),
     operation_id = "create_repo", tag = "repos"
@@ -58,18 +67,108 @@ pub async fn create(
     State(state): State<Arc<AppState>>,
     Json(input): Json<CreateRepoInput>,
 ) -> Result<Json<Repo>, ApiError> {
+    let parsed = match &input.config {
+        Some(cfg) => Some(config::parse(cfg)?),
+        None => None,
+    };
+    if input.adopt {
+        return adopt(state, input).await;
+    }
+    if input.remote_url.trim().is_empty() {
+        return Err(ApiError(BunyanError::InvalidInput(
+            "remote_url is required unless adopting an existing clone".to_string(),
+        )));
+    }
     let url = input.remote_url.clone();
     let path = input.root_path.clone();
-    tokio::task::spawn_blocking(move || {
-        let git = RealGit;
-        git.clone_repo(&url, &path)
-    })
+    let options = git::CloneOptions::from_config(parsed.as_ref().and_then(|c| c.checkout.as_ref()));
+    let git = state.db.call(|conn| Ok(git::backend(conn))).await?;
+    tokio::task::spawn_blocking(move || git.clone_repo(&url, &path, &options))
     .await
-    .map_err(|e| ApiError(crate::error::BunyanError::Process(e.to_string())))?
+    .map_err(|e| ApiError(BunyanError::Process(e.to_string())))?
     .map_err(ApiError)?;
 
-    let conn = state.db.lock().unwrap();
-    let repo = db::repos::create(&conn, input)?;
+    let repo = state
+        .db
+        .call(move |conn| {
+            let repo = db::repos::create(conn, input)?;
+            db::events::audit(
+                conn,
+                EventKind::RepoCreated,
+                Some(&repo.id),
+                None,
+                Some(&format!("Cloned {} into {}", repo.remote_url, repo.root_path)),
+            );
+            Ok(repo)
+        })
+        .await?;
+    Ok(Json(repo))
+}
+
+/// Register an existing clone in place and import its linked worktrees as
+/// ready workspaces.
+async fn adopt(state: Arc<AppState>, mut input: CreateRepoInput) -> Result<Json<Repo>, ApiError> {
+    let root = input.root_path.trim_end_matches('/').to_string();
+    input.root_path = root.clone();
+    let already = state
+        .db
+        .call({
+            let root = root.clone();
+            move |conn| Ok(db::repos::list(conn)?.into_iter().any(|r| r.root_path == root))
+        })
+        .await?;
+    if already {
+        return Err(ApiError(BunyanError::Conflict(format!(
+            "{} is already registered",
+            root
+        ))));
+    }
+
+    let (path, remote, name) = (root.clone(), input.remote.clone(), input.name.clone());
+    let git = state.db.call(|conn| Ok(git::backend(conn))).await?;
+    let (info, entries) = tokio::task::spawn_blocking(move || {
+        let info = git.inspect_clone(&path, &remote)?;
+        let entries = git.worktree_list(&path)?;
+        Ok::<_, BunyanError>((info, entries))
+    })
+    .await
+    .map_err(|e| ApiError(BunyanError::Process(e.to_string())))??;
+    let (worktrees, skipped) = workspace::adoptable_worktrees(&root, &name, &entries);
+
+    input.remote_url = info.remote_url;
+    input.default_branch = info.default_branch;
+
+    let repo = state
+        .db
+        .call(move |conn| {
+            let repo = db::repos::create(conn, input)?;
+            for wt in &worktrees {
+                db::workspaces::create(
+                    conn,
+                    CreateWorkspaceInput {
+                        repository_id: repo.id.clone(),
+                        directory_name: wt.directory_name.clone(),
+                        branch: wt.branch.clone(),
+                        container_mode: ContainerMode::Local,
+                        branch_mode: BranchMode::Existing,
+                        base_ref: None,
+                        config: None,
+                    },
+                )?;
+            }
+
+            let mut message = format!(
+                "Adopted {} with {} workspace(s)",
+                repo.root_path,
+                worktrees.len()
+            );
+            if !skipped.is_empty() {
+                message.push_str(&format!("; skipped {}", skipped.join(", ")));
+            }
+            db::events::audit(conn, EventKind::RepoCreated, Some(&repo.id), None, Some(&message));
+            Ok(repo)
+        })
+        .await?;
     Ok(Json(repo))
 }
 
@@ -80,6 +179,7 @@ pub async fn create(
     request_body = UpdateRepoInput,
     responses(
         (status = 200, body = Repo),
+        (status = 400, body = ErrorResponse),
         (status = 404, body = ErrorResponse),
         (status = 500, body = ErrorResponse)
     ),
@@ -91,11 +191,53 @@ pub async fn update(
     Json(mut input): Json<UpdateRepoInput>,
 ) -> Result<Json<Repo>, ApiError> {
     input.id = id;
-    let conn = state.db.lock().unwrap();
-    let repo = db::repos::update(&conn, input)?;
+    if let Some(cfg) = &input.config {
+        config::parse(cfg)?;
+    }
+    let repo = state
+        .db
+        .call(move |conn| {
+            let repo = db::repos::update(conn, input)?;
+            db::events::audit(conn, EventKind::RepoUpdated, Some(&repo.id), None, None);
+            Ok(repo)
+        })
+        .await?;
     Ok(Json(repo))
 }
 
+#[utoipa::path(
+    post,
+    path = "/repos/reorder",
+    request_body = ReorderReposInput,
+    responses(
+        (status = 200, body = Vec<Repo>),
+        (status = 400, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+        (status = 409, body = ErrorResponse)
+    ),
+    operation_id = "reorder_repos", tag = "repos"
+)]
+pub async fn reorder(
+    State(state): State<Arc<AppState>>,
+    Json(input): Json<ReorderReposInput>,
+) -> Result<Json<Vec<Repo>>, ApiError> {
+    let repos = state
+        .db
+        .call_mut(move |conn| {
+            let repos = db::repos::reorder(conn, &input.ids)?;
+            db::events::audit(
+                conn,
+                EventKind::RepoUpdated,
+                None,
+                None,
+                Some("Reordered repositories"),
+            );
+            Ok(repos)
+        })
+        .await?;
+    Ok(Json(repos))
+}
+
 #[utoipa::path(
     delete,
     path = "/repos/{id}",
@@ -111,7 +253,43 @@ pub async fn delete(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
 ) -> Result<Json<()>, ApiError> {
-    let conn = state.db.lock().unwrap();
-    db::repos::delete(&conn, &id)?;
+    state
+        .db
+        .call(move |conn| {
+            db::repos::delete(conn, &id)?;
+            db::events::audit(conn, EventKind::RepoDeleted, Some(&id), None, None);
+            Ok(())
+        })
+        .await?;
     Ok(Json(()))
 }
+
+#[utoipa::path(
+    post,
+    path = "/repos/{id}/config/validate",
+    params(("id" = String, Path, description = "Repository ID")),
+    request_body = ValidateConfigInput,
+    responses(
+        (status = 200, body = ConfigValidation),
+        (status = 404, body = ErrorResponse)
+    ),
+    operation_id = "validate_repo_config", tag = "repos"
+)]
+pub async fn validate_config(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(input): Json<ValidateConfigInput>,
+) -> Result<Json<ConfigValidation>, ApiError> {
+    let raw = state
+        .db
+        .call(move |conn| match input.config {
+            Some(cfg) => db::repos::get(conn, &id).map(|_| Some(cfg)),
+            None => db::repos::get_raw_config(conn, &id),
+        })
+        .await?;
+    let errors = raw.as_ref().map(config::validate).unwrap_or_default();
+    Ok(Json(ConfigValidation {
+        valid: errors.is_empty(),
+        errors,
+    }))
+}
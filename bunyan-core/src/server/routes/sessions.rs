@@ -13,7 +13,7 @@ use crate::tmux;
 pub async fn active(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<Vec<WorkspacePaneInfo>>, ApiError> {
-    let all_panes = tokio::task::spawn_blocking(|| tmux::list_all_panes())
+    let all_panes = tokio::task::spawn_blocking(tmux::list_all_panes)
         .await
         .map_err(|e| ApiError(crate::error::BunyanError::Process(e.to_string())))?
         .map_err(ApiError)?;
@@ -31,12 +31,10 @@ pub async fn active(
             .push(pane);
     }
 
-    let (workspaces, repos) = {
-        let conn = state.db.lock().unwrap();
-        let ws = db::workspaces::list(&conn, None)?;
-        let rp = db::repos::list(&conn)?;
-        (ws, rp)
-    };
+    let (workspaces, repos) = state
+        .db
+        .call(|conn| Ok((db::workspaces::list(conn, None)?, db::repos::list(conn)?)))
+        .await?;
 
     let mut results = Vec::new();
     for ((session_name, window_name), panes) in grouped {
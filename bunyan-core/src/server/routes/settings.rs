@@ -1,37 +1,56 @@
 use std::sync::Arc;
 
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::Json;
+use serde::Deserialize;
 
-use crate::db;
-use crate::models::{ErrorResponse, SetSettingInput, Setting};
+use crate::models::{EffectiveSetting, ErrorResponse, SetSettingInput, Setting, SettingDefinition};
 use crate::server::error::ApiError;
+use crate::settings;
 use crate::state::AppState;
 
-#[utoipa::path(get, path = "/settings", responses((status = 200, body = Vec<Setting>), (status = 500, body = ErrorResponse)), operation_id = "list_settings", tag = "settings")]
-pub async fn list(State(state): State<Arc<AppState>>) -> Result<Json<Vec<Setting>>, ApiError> {
-    let conn = state.db.lock().unwrap();
-    let settings = db::settings::get_all(&conn)?;
+#[derive(Deserialize)]
+pub struct SetQuery {
+    /// Store the value even if the key is unknown or the value fails validation
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[utoipa::path(get, path = "/settings", responses((status = 200, body = Vec<EffectiveSetting>), (status = 500, body = ErrorResponse)), operation_id = "list_settings", tag = "settings")]
+pub async fn list(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<EffectiveSetting>>, ApiError> {
+    let settings = state.db.call(settings::list_effective).await?;
     Ok(Json(settings))
 }
 
-#[utoipa::path(get, path = "/settings/{key}", params(("key" = String, Path, description = "Setting key")), responses((status = 200, body = Setting), (status = 404, body = ErrorResponse)), operation_id = "get_setting", tag = "settings")]
+#[utoipa::path(get, path = "/settings/definitions", responses((status = 200, body = Vec<SettingDefinition>)), operation_id = "list_setting_definitions", tag = "settings")]
+pub async fn definitions() -> Json<Vec<SettingDefinition>> {
+    Json(settings::definitions())
+}
+
+#[utoipa::path(get, path = "/settings/{key}", params(("key" = String, Path, description = "Setting key")), responses((status = 200, body = EffectiveSetting), (status = 404, body = ErrorResponse)), operation_id = "get_setting", tag = "settings")]
 pub async fn get(
     State(state): State<Arc<AppState>>,
     Path(key): Path<String>,
-) -> Result<Json<Setting>, ApiError> {
-    let conn = state.db.lock().unwrap();
-    let setting = db::settings::get(&conn, &key)?;
+) -> Result<Json<EffectiveSetting>, ApiError> {
+    let setting = state
+        .db
+        .call(move |conn| settings::get_effective(conn, &key))
+        .await?;
     Ok(Json(setting))
 }
 
-#[utoipa::path(put, path = "/settings/{key}", params(("key" = String, Path, description = "Setting key")), request_body = SetSettingInput, responses((status = 200, body = Setting), (status = 500, body = ErrorResponse)), operation_id = "set_setting", tag = "settings")]
+#[utoipa::path(put, path = "/settings/{key}", params(("key" = String, Path, description = "Setting key"), ("force" = Option<bool>, Query, description = "Store unknown keys and unvalidated values")), request_body = SetSettingInput, responses((status = 200, body = Setting), (status = 400, body = ErrorResponse), (status = 500, body = ErrorResponse)), operation_id = "set_setting", tag = "settings")]
 pub async fn set(
     State(state): State<Arc<AppState>>,
     Path(key): Path<String>,
+    Query(query): Query<SetQuery>,
     Json(input): Json<SetSettingInput>,
 ) -> Result<Json<Setting>, ApiError> {
-    let conn = state.db.lock().unwrap();
-    let setting = db::settings::set(&conn, &key, &input.value)?;
+    let setting = state
+        .db
+        .call(move |conn| settings::set(conn, &key, &input.value, query.force))
+        .await?;
     Ok(Json(setting))
 }
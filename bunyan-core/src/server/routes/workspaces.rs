@@ -4,12 +4,16 @@ use axum::extract::{Path, Query, State};
 use axum::Json;
 use serde::Deserialize;
 
+use crate::config;
 use crate::db;
 use crate::docker;
-use crate::git::{GitOps, RealGit};
+use crate::git;
+use crate::hooks;
 use crate::models::{
-    ClaudeResumeInput, ClaudeSessionEntry, ContainerMode, CreateWorkspaceInput, ErrorResponse,
-    StatusResponse, TmuxPane, Workspace,
+    ArchiveInput, ClaudeResumeInput, ClaudeSessionEntry, ContainerMode, CreateWorkspaceInput, EffectiveWorkspaceConfig,
+    ErrorResponse, EventKind, HookKind,
+    PurgeWorkspacesInput, PurgeWorkspacesResult, StatusResponse, TmuxPane, UpdateWorkspaceInput,
+    Workspace, WorkspaceState, WorkspaceTransition,
 };
 use crate::server::error::ApiError;
 use crate::sessions;
@@ -21,87 +25,285 @@ use crate::workspace;
 #[derive(Deserialize)]
 pub struct ListQuery {
     pub repo_id: Option<String>,
+    /// Comma-separated labels; a workspace must carry all of them
+    pub label: Option<String>,
+    pub state: Option<WorkspaceState>,
+    pub container_mode: Option<ContainerMode>,
 }
 
-#[utoipa::path(get, path = "/workspaces", params(("repo_id" = Option<String>, Query, description = "Filter by repository ID")), responses((status = 200, body = Vec<Workspace>), (status = 500, body = ErrorResponse)), operation_id = "list_workspaces", tag = "workspaces")]
+#[utoipa::path(get, path = "/workspaces", params(("repo_id" = Option<String>, Query, description = "Filter by repository ID"), ("label" = Option<String>, Query, description = "Comma-separated labels; all must match"), ("state" = Option<WorkspaceState>, Query, description = "Filter by state"), ("container_mode" = Option<ContainerMode>, Query, description = "Filter by container mode")), responses((status = 200, body = Vec<Workspace>), (status = 400, body = ErrorResponse), (status = 500, body = ErrorResponse)), operation_id = "list_workspaces", tag = "workspaces")]
 pub async fn list(
     State(state): State<Arc<AppState>>,
     Query(query): Query<ListQuery>,
 ) -> Result<Json<Vec<Workspace>>, ApiError> {
-    let conn = state.db.lock().unwrap();
-    let workspaces = db::workspaces::list(&conn, query.repo_id.as_deref())?;
+    let labels = query
+        .label
+        .as_deref()
+        .map(|l| {
+            l.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    let filter = db::workspaces::WorkspaceFilter {
+        repository_id: query.repo_id,
+        state: query.state,
+        container_mode: query.container_mode,
+        labels,
+    };
+    let workspaces = state
+        .db
+        .call(move |conn| db::workspaces::list_filtered(conn, &filter))
+        .await?;
     Ok(Json(workspaces))
 }
 
-#[utoipa::path(get, path = "/workspaces/{id}", params(("id" = String, Path, description = "Workspace ID")), responses((status = 200, body = Workspace), (status = 404, body = ErrorResponse)), operation_id = "get_workspace", tag = "workspaces")]
+#[derive(Deserialize)]
+pub struct GetQuery {
+    /// Refresh `pull_request` from the forge before returning
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+#[utoipa::path(get, path = "/workspaces/{id}", params(("id" = String, Path, description = "Workspace ID"), ("refresh" = Option<bool>, Query, description = "Refresh the pull request from the forge (default: false)")), responses((status = 200, body = Workspace), (status = 404, body = ErrorResponse)), operation_id = "get_workspace", tag = "workspaces")]
 pub async fn get(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
+    Query(query): Query<GetQuery>,
 ) -> Result<Json<Workspace>, ApiError> {
-    let conn = state.db.lock().unwrap();
-    let ws = db::workspaces::get(&conn, &id)?;
+    let ws = state
+        .db
+        .call(move |conn| db::workspaces::get(conn, &id))
+        .await?;
+    if query.refresh {
+        return Ok(Json(super::forge::refresh(&state, ws).await));
+    }
     Ok(Json(ws))
 }
 
-#[utoipa::path(post, path = "/workspaces", request_body = CreateWorkspaceInput, responses((status = 200, body = Workspace), (status = 500, body = ErrorResponse)), operation_id = "create_workspace", tag = "workspaces")]
+#[utoipa::path(put, path = "/workspaces/{id}", params(("id" = String, Path, description = "Workspace ID")), request_body = UpdateWorkspaceInput, responses((status = 200, body = Workspace), (status = 400, body = ErrorResponse), (status = 404, body = ErrorResponse), (status = 409, body = ErrorResponse)), operation_id = "update_workspace", tag = "workspaces")]
+pub async fn update(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(input): Json<UpdateWorkspaceInput>,
+) -> Result<Json<Workspace>, ApiError> {
+    let ws = state
+        .db
+        .call(move |conn| db::workspaces::update(conn, &id, input))
+        .await?;
+    Ok(Json(ws))
+}
+
+#[utoipa::path(get, path = "/workspaces/{id}/config", params(("id" = String, Path, description = "Workspace ID")), responses((status = 200, body = EffectiveWorkspaceConfig), (status = 404, body = ErrorResponse)), operation_id = "get_workspace_config", tag = "workspaces")]
+pub async fn get_config(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<EffectiveWorkspaceConfig>, ApiError> {
+    let effective = state
+        .db
+        .call(move |conn| {
+            let ws = db::workspaces::get(conn, &id)?;
+            let repo = db::repos::get(conn, &ws.repository_id)?;
+            config::resolve(conn, &repo, &ws)
+        })
+        .await?;
+    Ok(Json(effective))
+}
+
+#[utoipa::path(post, path = "/workspaces", request_body = CreateWorkspaceInput, responses((status = 200, body = Workspace), (status = 400, body = ErrorResponse), (status = 409, body = ErrorResponse), (status = 500, body = ErrorResponse)), operation_id = "create_workspace", tag = "workspaces")]
 pub async fn create(
     State(state): State<Arc<AppState>>,
-    Json(input): Json<CreateWorkspaceInput>,
+    Json(mut input): Json<CreateWorkspaceInput>,
 ) -> Result<Json<Workspace>, ApiError> {
-    let repo = {
-        let conn = state.db.lock().unwrap();
-        db::repos::get(&conn, &input.repository_id)?
+    let (repo, git) = {
+        let repository_id = input.repository_id.clone();
+        state
+            .db
+            .call(move |conn| Ok((db::repos::get(conn, &repository_id)?, git::backend(conn))))
+            .await?
     };
 
     let wt_path = workspace::workspace_path(&repo.root_path, &repo.name, &input.directory_name)?;
     let repo_root = repo.root_path.clone();
-    let branch = input.branch.clone();
+    let checkout = repo
+        .config
+        .as_ref()
+        .and_then(|c| c.checkout.clone())
+        .unwrap_or_default();
     let container_mode = input.container_mode.clone();
 
-    tokio::task::spawn_blocking(move || {
-        let git = RealGit;
-        git.worktree_add(&repo_root, &wt_path, &branch)
-    })
-    .await
-    .map_err(|e| ApiError(crate::error::BunyanError::Process(e.to_string())))?
-    .map_err(ApiError)?;
+    let spec = {
+        let repo = repo.clone();
+        let mode = input.branch_mode.clone();
+        let branch = input.branch.clone();
+        let base_ref = input.base_ref.clone();
+        let git = git.clone();
+        tokio::task::spawn_blocking(move || {
+            workspace::plan_worktree(git.as_ref(), &repo, &mode, &branch, base_ref.as_deref())
+        })
+        .await
+        .map_err(|e| ApiError(crate::error::BunyanError::Process(e.to_string())))??
+    };
+    // Remote mode may strip the remote prefix, so record the local branch name
+    input.branch = spec.branch().to_string();
 
-    let ws = {
-        let conn = state.db.lock().unwrap();
-        db::workspaces::create(&conn, input)?
+    let mut ws = state
+        .db
+        .call(move |conn| db::workspaces::create_pending(conn, input))
+        .await?;
+
+    let added = {
+        let (git, wt_path, sparse) = (git.clone(), wt_path.clone(), checkout.sparse.clone());
+        tokio::task::spawn_blocking(move || git.worktree_add(&repo_root, &wt_path, &spec, &sparse))
+        .await
+        .map_err(|e| crate::error::BunyanError::Process(e.to_string()))
+        .and_then(|r| r)
     };
+    if let Err(e) = added {
+        workspace::record_failure_async(&state, &ws.id, &format!("Worktree creation failed: {}", e)).await;
+        return Err(ApiError(e));
+    }
+
+    if checkout.submodules || checkout.lfs {
+        let wt_path = wt_path.clone();
+        let prepared = match tokio::task::spawn_blocking(move || {
+            workspace::prepare_worktree(git.as_ref(), &checkout, &wt_path)
+        })
+        .await
+        {
+            Ok(warnings) if warnings.is_empty() => Ok(()),
+            Ok(warnings) => {
+                let id = ws.id.clone();
+                state
+                    .db
+                    .call(move |conn| db::workspaces::set_warnings(conn, &id, &warnings))
+                    .await
+            }
+            Err(e) => Err(crate::error::BunyanError::Process(e.to_string())),
+        };
+        if let Err(e) = prepared {
+            workspace::record_failure_async(&state, &ws.id, &format!("Worktree preparation failed: {}", e)).await;
+            return Err(ApiError(e));
+        }
+    }
+
+    hooks::run_required(&state, &repo, &ws, &wt_path, HookKind::PostCheckout).await?;
 
     if container_mode == ContainerMode::Container {
-        let updated = workspace::setup_workspace_container(&state, &ws, &repo)
-            .await
-            .map_err(|e| ApiError(crate::error::BunyanError::Process(e)))?;
-        return Ok(Json(updated));
+        match workspace::setup_workspace_container(&state, &ws, &repo).await {
+            Ok(updated) => {
+                db::events::audit_async(
+                    &state.db,
+                    EventKind::ContainerCreated,
+                    Some(&repo.id),
+                    Some(&ws.id),
+                    updated.container_id.as_deref(),
+                )
+                .await;
+                ws = updated;
+            }
+            Err(e) => {
+                workspace::record_failure_async(&state, &ws.id, &format!("Container setup failed: {}", e)).await;
+                return Err(ApiError(crate::error::BunyanError::Process(e)));
+            }
+        }
     }
 
-    Ok(Json(ws))
+    hooks::run_required(&state, &repo, &ws, &wt_path, HookKind::Setup).await?;
+
+    let ready = state
+        .db
+        .call(move |conn| {
+            let ready = db::workspaces::transition(conn, &ws.id, WorkspaceState::Ready, None)?;
+            db::events::audit(
+                conn,
+                EventKind::WorkspaceCreated,
+                Some(&repo.id),
+                Some(&ws.id),
+                Some(&match ready.warnings.len() {
+                    0 => format!("{} on branch {}", ready.directory_name, ready.branch),
+                    n => format!("{} on branch {} ({} warnings)", ready.directory_name, ready.branch, n),
+                }),
+            );
+            Ok(ready)
+        })
+        .await?;
+    Ok(Json(ready))
 }
 
-#[utoipa::path(post, path = "/workspaces/{id}/archive", params(("id" = String, Path, description = "Workspace ID")), responses((status = 200, body = Workspace), (status = 404, body = ErrorResponse)), operation_id = "archive_workspace", tag = "workspaces")]
+#[utoipa::path(post, path = "/workspaces/{id}/archive", params(("id" = String, Path, description = "Workspace ID")), request_body(content = Option<ArchiveInput>), responses((status = 200, body = Workspace), (status = 404, body = ErrorResponse), (status = 409, body = ErrorResponse)), operation_id = "archive_workspace", tag = "workspaces")]
 pub async fn archive(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
+    input: Option<Json<ArchiveInput>>,
 ) -> Result<Json<Workspace>, ApiError> {
-    let (ws, repo) = {
-        let conn = state.db.lock().unwrap();
-        let ws = db::workspaces::get(&conn, &id)?;
-        let rp = db::repos::get(&conn, &ws.repository_id)?;
-        (ws, rp)
+    let input = input.map(|Json(input)| input).unwrap_or_default();
+    let force = input.force;
+    let (ws, repo, git) = {
+        let id = id.clone();
+        state
+            .db
+            .call(move |conn| {
+                let ws = db::workspaces::get(conn, &id)?;
+                let rp = db::repos::get(conn, &ws.repository_id)?;
+                Ok((ws, rp, git::backend(conn)))
+            })
+            .await?
     };
 
+    if ws.state == WorkspaceState::Archived {
+        return Ok(Json(ws));
+    }
+
+    let wt_path = workspace::workspace_path(&repo.root_path, &repo.name, &ws.directory_name)?;
+    // A workspace that failed during creation may never have had a worktree
+    let has_worktree = std::path::Path::new(&wt_path).exists();
+    if has_worktree && !force {
+        let (path, git) = (wt_path.clone(), git.clone());
+        tokio::task::spawn_blocking(move || workspace::ensure_nothing_unsaved(git.as_ref(), &path))
+            .await
+            .map_err(|e| ApiError(crate::error::BunyanError::Process(e.to_string())))??;
+    }
+
+    if ws.state != WorkspaceState::Archiving {
+        let id = id.clone();
+        state
+            .db
+            .call(move |conn| db::workspaces::transition(conn, &id, WorkspaceState::Archiving, None))
+            .await?;
+    }
+
+    // A failed teardown stops the archive unless it is forced
+    if has_worktree {
+        if force {
+            hooks::run(&state, &repo, &ws, &wt_path, HookKind::Teardown).await?;
+        } else {
+            hooks::run_required(&state, &repo, &ws, &wt_path, HookKind::Teardown).await?;
+        }
+    }
+
     workspace::kill_workspace_window(&repo.name, &ws.directory_name);
 
     if ws.container_mode == ContainerMode::Container {
         if let Some(ref container_id) = ws.container_id {
-            let _ = docker::remove_container(container_id).await;
+            if docker::remove_container(container_id).await.is_ok() {
+                db::events::audit_async(
+                    &state.db,
+                    EventKind::ContainerRemoved,
+                    Some(&repo.id),
+                    Some(&id),
+                    Some(container_id),
+                )
+                .await;
+            }
         }
         let remaining = {
-            let conn = state.db.lock().unwrap();
-            db::workspaces::count_container_workspaces(&conn, &repo.id)?
+            let repo_id = repo.id.clone();
+            state
+                .db
+                .call(move |conn| db::workspaces::count_container_workspaces(conn, &repo_id))
+                .await?
         };
         if remaining <= 1 {
             let _ = docker::remove_network(
@@ -111,31 +313,222 @@ pub async fn archive(
         }
     }
 
-    let wt_path = workspace::workspace_path(&repo.root_path, &repo.name, &ws.directory_name)?;
     let repo_root = repo.root_path.clone();
+    if has_worktree {
+        let git = git.clone();
+        let removed = tokio::task::spawn_blocking(move || git.worktree_remove(&repo_root, &wt_path, true))
+        .await
+        .map_err(|e| crate::error::BunyanError::Process(e.to_string()))
+        .and_then(|r| r);
+        if let Err(e) = removed {
+            workspace::record_failure_async(&state, &id, &format!("Worktree removal failed: {}", e)).await;
+            return Err(ApiError(e));
+        }
+    }
 
-    tokio::task::spawn_blocking(move || {
-        let git = RealGit;
-        git.worktree_remove(&repo_root, &wt_path, true)
+    let options = workspace::BranchCleanupOptions {
+        local: input.delete_branch,
+        remote: input.delete_remote_branch,
+        force,
+    };
+    let branch = ws.branch.clone();
+    let cleanup_repo = repo.clone();
+    let cleanup = tokio::task::spawn_blocking(move || {
+        workspace::cleanup_branch(git.as_ref(), &cleanup_repo, &branch, &options)
     })
     .await
-    .map_err(|e| ApiError(crate::error::BunyanError::Process(e.to_string())))?
-    .map_err(ApiError)?;
-
-    let conn = state.db.lock().unwrap();
-    let archived = db::workspaces::archive(&conn, &id)?;
+    .map_err(|e| ApiError(crate::error::BunyanError::Process(e.to_string())))?;
+
+    let archived = state
+        .db
+        .call(move |conn| {
+            if let Some(cleanup) = &cleanup {
+                db::workspaces::set_branch_cleanup(conn, &id, cleanup)?;
+            }
+            let archived = db::workspaces::archive(conn, &id)?;
+            let message = cleanup
+                .and_then(|c| c.detail)
+                .map(|detail| format!("Branch cleanup: {}", detail));
+            db::events::audit(
+                conn,
+                EventKind::WorkspaceArchived,
+                Some(&repo.id),
+                Some(&id),
+                message.as_deref(),
+            );
+            Ok(archived)
+        })
+        .await?;
     Ok(Json(archived))
 }
 
+#[utoipa::path(delete, path = "/workspaces/{id}", params(("id" = String, Path, description = "Workspace ID")), responses((status = 200, body = Workspace), (status = 404, body = ErrorResponse), (status = 409, body = ErrorResponse)), operation_id = "delete_workspace", tag = "workspaces")]
+pub async fn delete(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<Workspace>, ApiError> {
+    let deleted = state
+        .db
+        .call(move |conn| {
+            let deleted = db::workspaces::delete_archived(conn, &id)?;
+            db::events::audit(
+                conn,
+                EventKind::WorkspacePurged,
+                Some(&deleted.repository_id),
+                Some(&deleted.id),
+                None,
+            );
+            Ok(deleted)
+        })
+        .await?;
+    Ok(Json(deleted))
+}
+
+#[utoipa::path(post, path = "/workspaces/purge", request_body = PurgeWorkspacesInput, responses((status = 200, body = PurgeWorkspacesResult), (status = 400, body = ErrorResponse), (status = 500, body = ErrorResponse)), operation_id = "purge_workspaces", tag = "workspaces")]
+pub async fn purge(
+    State(state): State<Arc<AppState>>,
+    Json(input): Json<PurgeWorkspacesInput>,
+) -> Result<Json<PurgeWorkspacesResult>, ApiError> {
+    let cutoff = input
+        .older_than
+        .as_deref()
+        .map(workspace::age_cutoff)
+        .transpose()?;
+
+    let dry_run = input.dry_run;
+    let workspaces = state
+        .db
+        .call(move |conn| {
+            let purged = db::workspaces::purge_archived(
+                conn,
+                cutoff,
+                input.repository_id.as_deref(),
+                dry_run,
+            )?;
+            if !dry_run {
+                for ws in &purged {
+                    db::events::audit(
+                        conn,
+                        EventKind::WorkspacePurged,
+                        Some(&ws.repository_id),
+                        Some(&ws.id),
+                        None,
+                    );
+                }
+            }
+            Ok(purged)
+        })
+        .await?;
+
+    Ok(Json(PurgeWorkspacesResult {
+        dry_run,
+        workspaces,
+    }))
+}
+
+#[utoipa::path(post, path = "/workspaces/{id}/suspend", params(("id" = String, Path, description = "Workspace ID")), responses((status = 200, body = Workspace), (status = 404, body = ErrorResponse), (status = 409, body = ErrorResponse)), operation_id = "suspend_workspace", tag = "workspaces")]
+pub async fn suspend(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<Workspace>, ApiError> {
+    let (ws, repo) = {
+        let id = id.clone();
+        state
+            .db
+            .call(move |conn| {
+                let ws = db::workspaces::get(conn, &id)?;
+                let rp = db::repos::get(conn, &ws.repository_id)?;
+                Ok((ws, rp))
+            })
+            .await?
+    };
+
+    if !ws.state.can_transition_to(&WorkspaceState::Suspended) {
+        return Err(ApiError(crate::error::BunyanError::Conflict(format!(
+            "Cannot suspend a workspace that is {}",
+            ws.state.as_str()
+        ))));
+    }
+
+    workspace::kill_workspace_window(&repo.name, &ws.directory_name);
+
+    if let Some(ref container_id) = ws.container_id {
+        docker::stop_container(container_id).await.map_err(ApiError)?;
+    }
+
+    let suspended = state
+        .db
+        .call(move |conn| {
+            let suspended = db::workspaces::transition(conn, &id, WorkspaceState::Suspended, None)?;
+            db::events::audit(conn, EventKind::WorkspaceSuspended, Some(&repo.id), Some(&id), None);
+            Ok(suspended)
+        })
+        .await?;
+    Ok(Json(suspended))
+}
+
+#[utoipa::path(post, path = "/workspaces/{id}/resume", params(("id" = String, Path, description = "Workspace ID")), responses((status = 200, body = Workspace), (status = 404, body = ErrorResponse), (status = 409, body = ErrorResponse)), operation_id = "resume_workspace", tag = "workspaces")]
+pub async fn resume(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<Workspace>, ApiError> {
+    let ws = {
+        let id = id.clone();
+        state.db.call(move |conn| db::workspaces::get(conn, &id)).await?
+    };
+
+    if ws.state != WorkspaceState::Suspended {
+        return Err(ApiError(crate::error::BunyanError::Conflict(format!(
+            "Cannot resume a workspace that is {}",
+            ws.state.as_str()
+        ))));
+    }
+
+    if let Some(ref container_id) = ws.container_id {
+        if let Err(e) = docker::start_container(container_id).await {
+            workspace::record_failure_async(&state, &id, &format!("Container start failed: {}", e)).await;
+            return Err(ApiError(e));
+        }
+    }
+
+    let ready = state
+        .db
+        .call(move |conn| {
+            let ready = db::workspaces::transition(conn, &id, WorkspaceState::Ready, None)?;
+            db::events::audit(
+                conn,
+                EventKind::WorkspaceResumed,
+                Some(&ready.repository_id),
+                Some(&id),
+                None,
+            );
+            Ok(ready)
+        })
+        .await?;
+    Ok(Json(ready))
+}
+
+#[utoipa::path(get, path = "/workspaces/{id}/transitions", params(("id" = String, Path, description = "Workspace ID")), responses((status = 200, body = Vec<WorkspaceTransition>), (status = 404, body = ErrorResponse)), operation_id = "list_workspace_transitions", tag = "workspaces")]
+pub async fn transitions(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<WorkspaceTransition>>, ApiError> {
+    let history = state
+        .db
+        .call(move |conn| db::workspaces::list_transitions(conn, &id))
+        .await?;
+    Ok(Json(history))
+}
+
 #[utoipa::path(get, path = "/workspaces/{id}/sessions", params(("id" = String, Path, description = "Workspace ID")), responses((status = 200, body = Vec<ClaudeSessionEntry>), (status = 404, body = ErrorResponse)), tag = "workspaces")]
 pub async fn get_sessions(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
 ) -> Result<Json<Vec<ClaudeSessionEntry>>, ApiError> {
-    let (ws, _, ws_path) = {
-        let conn = state.db.lock().unwrap();
-        workspace::resolve_workspace_path(&conn, &id)?
-    };
+    let (ws, _, ws_path) = state
+        .db
+        .call(move |conn| workspace::resolve_workspace_path(conn, &id))
+        .await?;
 
     let container_mode = ws.container_mode.clone();
     let dir_name = ws.directory_name.clone();
@@ -154,10 +547,10 @@ pub async fn get_panes(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
 ) -> Result<Json<Vec<TmuxPane>>, ApiError> {
-    let (ws, repo, _) = {
-        let conn = state.db.lock().unwrap();
-        workspace::resolve_workspace_path(&conn, &id)?
-    };
+    let (ws, repo, _) = state
+        .db
+        .call(move |conn| workspace::resolve_workspace_path(conn, &id))
+        .await?;
 
     let repo_name = repo.name;
     let ws_name = ws.directory_name;
@@ -176,8 +569,8 @@ pub async fn start_claude(
     Path(id): Path<String>,
 ) -> Result<Json<StatusResponse>, ApiError> {
     let (ws, repo, ws_path) = {
-        let conn = state.db.lock().unwrap();
-        workspace::resolve_workspace_path(&conn, &id)?
+        let id = id.clone();
+        state.db.call(move |conn| workspace::resolve_workspace_path(conn, &id)).await?
     };
 
     let repo_name = repo.name.clone();
@@ -203,6 +596,8 @@ pub async fn start_claude(
         return Ok(Json(StatusResponse { status: "attached".into() }));
     }
 
+    super::checkpoints::auto_checkpoint(&state, &ws, &ws_path, "Before starting Claude").await;
+
     let has_previous = {
         let cm = ws.container_mode.clone();
         let dn = ws.directory_name.clone();
@@ -213,7 +608,7 @@ pub async fn start_claude(
     };
 
     let skip_perms = ws.container_mode == ContainerMode::Container
-        && workspace::should_skip_permissions(&repo);
+        && workspace::should_skip_permissions(&repo, &ws);
 
     let base_cmd = if has_previous {
         workspace::build_claude_cmd("claude --continue", skip_perms)
@@ -223,7 +618,7 @@ pub async fn start_claude(
 
     let claude_cmd = if ws.container_mode == ContainerMode::Container {
         match &ws.container_id {
-            Some(cid) => docker::docker_exec_cmd(cid, &base_cmd).map_err(|e| ApiError(e))?,
+            Some(cid) => docker::docker_exec_cmd(cid, &base_cmd).map_err(ApiError)?,
             None => base_cmd,
         }
     } else {
@@ -239,6 +634,15 @@ pub async fn start_claude(
         .map_err(|e| ApiError(crate::error::BunyanError::Process(e.to_string())))?
         .map_err(ApiError)?;
 
+    db::events::audit_async(
+        &state.db,
+        EventKind::ClaudeStarted,
+        Some(&repo.id),
+        Some(&ws.id),
+        Some(&claude_cmd),
+    )
+    .await;
+
     let rn = repo_name.clone();
     let wn = ws_name.clone();
     tokio::task::spawn_blocking(move || terminal::attach_iterm(&rn, &wn))
@@ -259,8 +663,8 @@ pub async fn resume_claude(
         .map_err(|e| ApiError(crate::error::BunyanError::Process(e)))?;
 
     let (ws, repo, ws_path) = {
-        let conn = state.db.lock().unwrap();
-        workspace::resolve_workspace_path(&conn, &id)?
+        let id = id.clone();
+        state.db.call(move |conn| workspace::resolve_workspace_path(conn, &id)).await?
     };
 
     let repo_name = repo.name.clone();
@@ -286,15 +690,17 @@ pub async fn resume_claude(
         return Ok(Json(StatusResponse { status: "attached".into() }));
     }
 
+    super::checkpoints::auto_checkpoint(&state, &ws, &ws_path, "Before resuming Claude").await;
+
     let skip_perms = ws.container_mode == ContainerMode::Container
-        && workspace::should_skip_permissions(&repo);
+        && workspace::should_skip_permissions(&repo, &ws);
     let base_cmd = workspace::build_claude_cmd(
         &format!("claude --resume {}", input.session_id),
         skip_perms,
     );
     let claude_cmd = if ws.container_mode == ContainerMode::Container {
         match &ws.container_id {
-            Some(cid) => docker::docker_exec_cmd(cid, &base_cmd).map_err(|e| ApiError(e))?,
+            Some(cid) => docker::docker_exec_cmd(cid, &base_cmd).map_err(ApiError)?,
             None => base_cmd,
         }
     } else {
@@ -329,6 +735,15 @@ pub async fn resume_claude(
             .map_err(ApiError)?;
     }
 
+    db::events::audit_async(
+        &state.db,
+        EventKind::ClaudeResumed,
+        Some(&repo.id),
+        Some(&ws.id),
+        Some(&input.session_id),
+    )
+    .await;
+
     let rn = repo_name.clone();
     let wn = ws_name.clone();
     tokio::task::spawn_blocking(move || terminal::attach_iterm(&rn, &wn))
@@ -345,8 +760,8 @@ pub async fn open_shell(
     Path(id): Path<String>,
 ) -> Result<Json<StatusResponse>, ApiError> {
     let (ws, repo, ws_path) = {
-        let conn = state.db.lock().unwrap();
-        workspace::resolve_workspace_path(&conn, &id)?
+        let id = id.clone();
+        state.db.call(move |conn| workspace::resolve_workspace_path(conn, &id)).await?
     };
 
     let repo_name = repo.name.clone();
@@ -395,6 +810,8 @@ pub async fn open_shell(
     .map_err(|e| ApiError(crate::error::BunyanError::Process(e.to_string())))?
     .map_err(ApiError)?;
 
+    db::events::audit_async(&state.db, EventKind::ShellOpened, Some(&repo.id), Some(&ws.id), None).await;
+
     let rn = repo_name.clone();
     let wn = ws_name.clone();
     tokio::task::spawn_blocking(move || terminal::attach_iterm(&rn, &wn))
@@ -411,8 +828,8 @@ pub async fn view(
     Path(id): Path<String>,
 ) -> Result<Json<StatusResponse>, ApiError> {
     let (ws, repo, ws_path) = {
-        let conn = state.db.lock().unwrap();
-        workspace::resolve_workspace_path(&conn, &id)?
+        let id = id.clone();
+        state.db.call(move |conn| workspace::resolve_workspace_path(conn, &id)).await?
     };
 
     let rn = repo.name.clone();
@@ -438,10 +855,10 @@ pub async fn kill_pane_handler(
     State(state): State<Arc<AppState>>,
     Path((id, pane_index)): Path<(String, u32)>,
 ) -> Result<Json<StatusResponse>, ApiError> {
-    let (ws, repo, _) = {
-        let conn = state.db.lock().unwrap();
-        workspace::resolve_workspace_path(&conn, &id)?
-    };
+    let (ws, repo, _) = state
+        .db
+        .call(move |conn| workspace::resolve_workspace_path(conn, &id))
+        .await?;
 
     let rn = repo.name;
     let wn = ws.directory_name;
@@ -450,5 +867,14 @@ pub async fn kill_pane_handler(
         .map_err(|e| ApiError(crate::error::BunyanError::Process(e.to_string())))?
         .map_err(ApiError)?;
 
+    db::events::audit_async(
+        &state.db,
+        EventKind::PaneKilled,
+        Some(&repo.id),
+        Some(&ws.id),
+        Some(&format!("pane {}", pane_index)),
+    )
+    .await;
+
     Ok(Json(StatusResponse { status: "killed".into() }))
 }
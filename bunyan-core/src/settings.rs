@@ -0,0 +1,277 @@
+use rusqlite::Connection;
+
+use crate::db;
+use crate::editor::Editor;
+use crate::error::{BunyanError, Result};
+use crate::models::{EffectiveSetting, Setting, SettingDefinition, SettingType};
+use crate::workspace;
+
+pub const PREFERRED_EDITOR: &str = "preferred_editor";
+/// Age (e.g. "30d") after which archived workspaces are purged automatically.
+/// Unset means archived workspaces are kept forever.
+pub const ARCHIVE_RETENTION: &str = "archive_retention";
+/// Whether a checkpoint is taken each time Claude is started or resumed in a workspace.
+pub const AUTO_CHECKPOINT: &str = "auto_checkpoint";
+/// Which `GitOps` implementation runs git operations.
+pub const GIT_BACKEND: &str = "git_backend";
+pub const GIT_BACKEND_CLI: &str = "cli";
+pub const GIT_BACKEND_LIBGIT2: &str = "libgit2";
+/// Comma-separated GitHub Enterprise Server hosts that pull requests may be opened on.
+pub const GITHUB_ENTERPRISE_HOSTS: &str = "github_enterprise_hosts";
+/// How long (e.g. "30m") a hook script may run before it is killed and counted as failed.
+pub const HOOK_TIMEOUT: &str = "hook_timeout";
+
+/// A registered setting. Add new settings to `REGISTRY`.
+pub struct SettingSpec {
+    pub key: &'static str,
+    pub value_type: SettingType,
+    pub default: Option<&'static str>,
+    pub description: &'static str,
+    /// Accepted values for `SettingType::Choice`
+    pub choices: fn() -> Vec<String>,
+}
+
+fn no_choices() -> Vec<String> {
+    Vec::new()
+}
+
+fn editor_ids() -> Vec<String> {
+    Editor::all().iter().map(|e| e.id().to_string()).collect()
+}
+
+fn git_backends() -> Vec<String> {
+    vec![GIT_BACKEND_CLI.to_string(), GIT_BACKEND_LIBGIT2.to_string()]
+}
+
+pub const REGISTRY: &[SettingSpec] = &[
+    SettingSpec {
+        key: PREFERRED_EDITOR,
+        value_type: SettingType::Choice,
+        default: Some("iterm"),
+        description: "Editor used when opening a workspace without naming one",
+        choices: editor_ids,
+    },
+    SettingSpec {
+        key: ARCHIVE_RETENTION,
+        value_type: SettingType::Duration,
+        default: None,
+        description: "Purge archived workspaces older than this (e.g. 30d); unset keeps them forever",
+        choices: no_choices,
+    },
+    SettingSpec {
+        key: AUTO_CHECKPOINT,
+        value_type: SettingType::Boolean,
+        default: Some("false"),
+        description: "Checkpoint a workspace's working tree each time Claude is started or resumed in it",
+        choices: no_choices,
+    },
+    SettingSpec {
+        key: GIT_BACKEND,
+        value_type: SettingType::Choice,
+        default: Some(GIT_BACKEND_CLI),
+        description: "Run git through the git executable on PATH (cli) or in process with libgit2",
+        choices: git_backends,
+    },
+    SettingSpec {
+        key: GITHUB_ENTERPRISE_HOSTS,
+        value_type: SettingType::String,
+        default: None,
+        description: "Comma-separated GitHub Enterprise Server hosts (e.g. github.example.com) to open pull requests on",
+        choices: no_choices,
+    },
+    SettingSpec {
+        key: HOOK_TIMEOUT,
+        value_type: SettingType::Duration,
+        default: Some("30m"),
+        description: "Kill a post_checkout, setup or teardown script that runs longer than this (e.g. 30m) and count it as failed",
+        choices: no_choices,
+    },
+];
+
+pub fn lookup(key: &str) -> Option<&'static SettingSpec> {
+    REGISTRY.iter().find(|s| s.key == key)
+}
+
+pub fn definitions() -> Vec<SettingDefinition> {
+    REGISTRY
+        .iter()
+        .map(|s| SettingDefinition {
+            key: s.key.to_string(),
+            value_type: s.value_type.clone(),
+            default_value: s.default.map(|d| d.to_string()),
+            description: s.description.to_string(),
+            allowed_values: (s.choices)(),
+        })
+        .collect()
+}
+
+/// Check that `key` is registered and `value` parses as its type.
+pub fn validate(key: &str, value: &str) -> Result<()> {
+    let spec = lookup(key)
+        .ok_or_else(|| BunyanError::InvalidInput(format!("Unknown setting: '{}'", key)))?;
+
+    let valid = match spec.value_type {
+        SettingType::String => true,
+        SettingType::Boolean => matches!(value, "true" | "false"),
+        SettingType::Integer => value.parse::<i64>().is_ok(),
+        SettingType::Duration => workspace::age_cutoff(value).is_ok(),
+        SettingType::Choice => (spec.choices)().iter().any(|c| c == value),
+    };
+    if !valid {
+        let expected = match spec.value_type {
+            SettingType::Choice => format!("one of {}", (spec.choices)().join(", ")),
+            SettingType::Duration => "a duration such as 30d, 12h or 2w".to_string(),
+            SettingType::Boolean => "true or false".to_string(),
+            SettingType::Integer => "an integer".to_string(),
+            SettingType::String => "a string".to_string(),
+        };
+        return Err(BunyanError::InvalidInput(format!(
+            "Invalid value '{}' for setting '{}': expected {}",
+            value, key, expected
+        )));
+    }
+    Ok(())
+}
+
+/// Store a setting. Unless `force` is set, unknown keys and badly typed values are rejected.
+pub fn set(conn: &Connection, key: &str, value: &str, force: bool) -> Result<Setting> {
+    if !force {
+        validate(key, value)?;
+    }
+    db::settings::set(conn, key, value)
+}
+
+fn from_stored(setting: Setting) -> EffectiveSetting {
+    EffectiveSetting {
+        known: lookup(&setting.key).is_some(),
+        key: setting.key,
+        value: setting.value,
+        is_default: false,
+        updated_at: Some(setting.updated_at),
+    }
+}
+
+fn from_default(spec: &SettingSpec) -> Option<EffectiveSetting> {
+    spec.default.map(|value| EffectiveSetting {
+        key: spec.key.to_string(),
+        value: value.to_string(),
+        is_default: true,
+        known: true,
+        updated_at: None,
+    })
+}
+
+/// Resolve a setting to its stored value or registered default.
+pub fn get_effective(conn: &Connection, key: &str) -> Result<EffectiveSetting> {
+    match db::settings::get(conn, key) {
+        Ok(setting) => Ok(from_stored(setting)),
+        Err(BunyanError::NotFound(msg)) => lookup(key)
+            .and_then(from_default)
+            .ok_or(BunyanError::NotFound(msg)),
+        Err(e) => Err(e),
+    }
+}
+
+/// Every stored setting plus the defaults of registered settings that are not stored, sorted by key.
+pub fn list_effective(conn: &Connection) -> Result<Vec<EffectiveSetting>> {
+    let mut settings: Vec<EffectiveSetting> = db::settings::get_all(conn)?
+        .into_iter()
+        .map(from_stored)
+        .collect();
+    for spec in REGISTRY {
+        if !settings.iter().any(|s| s.key == spec.key) {
+            settings.extend(from_default(spec));
+        }
+    }
+    settings.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::schema::initialize_database;
+
+    fn test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize_database(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn registry_keys_are_unique() {
+        for (i, spec) in REGISTRY.iter().enumerate() {
+            assert!(REGISTRY[i + 1..].iter().all(|other| other.key != spec.key));
+        }
+    }
+
+    #[test]
+    fn registry_defaults_pass_validation() {
+        for spec in REGISTRY {
+            if let Some(default) = spec.default {
+                validate(spec.key, default).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn validate_rejects_unknown_key() {
+        let result = validate("defualt_editor", "zed");
+        assert!(matches!(result, Err(BunyanError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn validate_checks_value_type() {
+        assert!(validate(PREFERRED_EDITOR, "zed").is_ok());
+        assert!(validate(PREFERRED_EDITOR, "notepad").is_err());
+        assert!(validate(ARCHIVE_RETENTION, "30d").is_ok());
+        assert!(validate(ARCHIVE_RETENTION, "a month").is_err());
+        assert!(validate(ARCHIVE_RETENTION, "4000000000d").is_err());
+        // Accepted with or without git on PATH; what needs the CLI fails when used
+        assert!(validate(GIT_BACKEND, GIT_BACKEND_LIBGIT2).is_ok());
+    }
+
+    #[test]
+    fn set_with_force_stores_unknown_key() {
+        let conn = test_db();
+        assert!(set(&conn, "custom_flag", "on", false).is_err());
+        let stored = set(&conn, "custom_flag", "on", true).unwrap();
+        assert_eq!(stored.value, "on");
+
+        let effective = get_effective(&conn, "custom_flag").unwrap();
+        assert!(!effective.known);
+        assert!(!effective.is_default);
+    }
+
+    #[test]
+    fn get_effective_falls_back_to_default() {
+        let conn = test_db();
+        let editor = get_effective(&conn, PREFERRED_EDITOR).unwrap();
+        assert_eq!(editor.value, "iterm");
+        assert!(editor.is_default);
+
+        set(&conn, PREFERRED_EDITOR, "cursor", false).unwrap();
+        let editor = get_effective(&conn, PREFERRED_EDITOR).unwrap();
+        assert_eq!(editor.value, "cursor");
+        assert!(!editor.is_default);
+    }
+
+    #[test]
+    fn get_effective_without_value_or_default_is_not_found() {
+        let conn = test_db();
+        let result = get_effective(&conn, ARCHIVE_RETENTION);
+        assert!(matches!(result, Err(BunyanError::NotFound(_))));
+    }
+
+    #[test]
+    fn list_effective_merges_defaults_and_stored_values() {
+        let conn = test_db();
+        set(&conn, ARCHIVE_RETENTION, "30d", false).unwrap();
+
+        let all = list_effective(&conn).unwrap();
+        let keys: Vec<&str> = all.iter().map(|s| s.key.as_str()).collect();
+        assert_eq!(keys, vec![ARCHIVE_RETENTION, AUTO_CHECKPOINT, GIT_BACKEND, HOOK_TIMEOUT, PREFERRED_EDITOR]);
+        assert!(!all[0].is_default);
+        assert!(all[1..].iter().all(|s| s.is_default));
+    }
+}
@@ -1,14 +1,11 @@
-use rusqlite::Connection;
-use std::sync::Mutex;
+use crate::db::Database;
 
 pub struct AppState {
-    pub db: Mutex<Connection>,
+    pub db: Database,
 }
 
 impl AppState {
-    pub fn new(db: Connection) -> Self {
-        Self {
-            db: Mutex::new(db),
-        }
+    pub fn new(db: Database) -> Self {
+        Self { db }
     }
 }
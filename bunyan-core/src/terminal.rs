@@ -12,10 +12,8 @@ pub fn attach_iterm(repo_name: &str, workspace_name: &str) -> Result<()> {
 
     // Try to reuse an existing iTerm window already attached to this repo's session
     let client_ttys = tmux::list_client_ttys_for_session(repo_name)?;
-    if !client_ttys.is_empty() {
-        if focus_iterm_by_tty(&client_ttys)? {
-            return Ok(());
-        }
+    if !client_ttys.is_empty() && focus_iterm_by_tty(&client_ttys)? {
+        return Ok(());
     }
 
     // No existing attachment — open a new iTerm window
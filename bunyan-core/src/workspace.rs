@@ -1,13 +1,26 @@
-use std::path::PathBuf;
-
+use std::path::{Path, PathBuf};
+#[cfg(feature = "server")]
 use std::sync::Arc;
 
 use rusqlite::Connection;
 
+use crate::config;
 use crate::db;
+#[cfg(feature = "server")]
 use crate::docker;
 use crate::error::{BunyanError, Result};
-use crate::models::{ContainerConfig, Repo, Workspace};
+use crate::forge::{Forge, NewPullRequest};
+use crate::git::{
+    check_sparse_dir, CheckpointEntry, GitOps, SyncOutcome, UnsavedWork, WorktreeEntry, WorktreeSpec,
+};
+use crate::models::{
+    BranchCleanup, BranchMode, BranchOutcome, Checkpoint, CheckoutConfig, CommitInput,
+    CommitResult, CommitStatus, ContainerConfig, CreatePullRequestInput, EventKind, GitStatus,
+    PullRequest, PullRequestState, PushResult, Repo, RestoreCheckpointResult, SparseCheckout,
+    SparseInput, SyncInput, SyncResult, SyncStatus, WarningKind, Workspace, WorkspaceDiff,
+    WorkspaceState, WorkspaceWarning,
+};
+#[cfg(feature = "server")]
 use crate::state::AppState;
 use crate::tmux;
 
@@ -38,22 +51,706 @@ pub fn resolve_workspace_path(
     Ok((ws, rp, ws_path))
 }
 
+/// Move a workspace to `failed` with the given reason. Best-effort: the caller
+/// is already reporting an error, so a failed transition is only logged.
+pub fn record_failure(conn: &Connection, workspace_id: &str, reason: &str) {
+    match db::workspaces::transition(conn, workspace_id, WorkspaceState::Failed, Some(reason)) {
+        Ok(ws) => db::events::audit(
+            conn,
+            EventKind::WorkspaceFailed,
+            Some(&ws.repository_id),
+            Some(&ws.id),
+            Some(reason),
+        ),
+        Err(e) => eprintln!("Warning: could not mark workspace {} failed: {}", workspace_id, e),
+    }
+}
+
+/// `record_failure` for async handlers: the update runs on the blocking pool.
+#[cfg(feature = "server")]
+pub async fn record_failure_async(state: &AppState, workspace_id: &str, reason: &str) {
+    let (id, reason) = (workspace_id.to_string(), reason.to_string());
+    let result = state
+        .db
+        .call(move |conn| {
+            record_failure(conn, &id, &reason);
+            Ok(())
+        })
+        .await;
+    if let Err(e) = result {
+        eprintln!("Warning: could not mark workspace {} failed: {}", workspace_id, e);
+    }
+}
+
+/// Reject ref names git would read as an option or refuse outright.
+fn validate_ref_name(kind: &str, name: &str) -> Result<()> {
+    if name.is_empty() || name.starts_with('-') || name.chars().any(|c| c.is_whitespace()) {
+        return Err(BunyanError::InvalidInput(format!("Invalid {} '{}'", kind, name)));
+    }
+    Ok(())
+}
+
+/// Work out which branch a new workspace checks out, based on the requested
+/// branch mode. Remote-tracking refs are fetched first: remote mode so a
+/// teammate's freshly pushed work is available, new mode so the branch starts
+/// from the remote's current tip rather than whenever the clone last fetched.
+pub fn plan_worktree(
+    git: &dyn GitOps,
+    repo: &Repo,
+    branch_mode: &BranchMode,
+    branch: &str,
+    base_ref: Option<&str>,
+) -> Result<WorktreeSpec> {
+    validate_ref_name("branch", branch)?;
+    if let Some(base) = base_ref {
+        validate_ref_name("base ref", base)?;
+    }
+    let local_exists = |name: &str| git.ref_exists(&repo.root_path, &format!("refs/heads/{}", name));
+    let prefix = format!("{}/", repo.remote);
+    // Best-effort: offline, the last fetched state is still usable
+    let fetch = |name: &str| {
+        if let Err(e) = git.fetch_branch(&repo.root_path, &repo.remote, name) {
+            eprintln!("Warning: could not fetch {}{}: {}", prefix, name, e);
+        }
+    };
+
+    match branch_mode {
+        BranchMode::New => {
+            let base = match base_ref {
+                Some(base) => {
+                    if let Some(remote_branch) = base.strip_prefix(&prefix) {
+                        fetch(remote_branch);
+                    }
+                    if !git.ref_exists(&repo.root_path, base)? {
+                        return Err(BunyanError::InvalidInput(format!(
+                            "Base ref '{}' does not exist",
+                            base
+                        )));
+                    }
+                    Some(base.to_string())
+                }
+                // Prefer the remote default branch over whatever HEAD the main clone has
+                None => {
+                    fetch(&repo.default_branch);
+                    let upstream = format!("{}{}", prefix, repo.default_branch);
+                    if git.ref_exists(&repo.root_path, &upstream)? {
+                        Some(upstream)
+                    } else if local_exists(&repo.default_branch)? {
+                        Some(repo.default_branch.clone())
+                    } else {
+                        None
+                    }
+                }
+            };
+            Ok(WorktreeSpec::NewBranch {
+                branch: branch.to_string(),
+                base,
+            })
+        }
+        BranchMode::Existing => {
+            if base_ref.is_some() {
+                return Err(BunyanError::InvalidInput(
+                    "base_ref cannot be used with an existing branch".to_string(),
+                ));
+            }
+            if !local_exists(branch)? {
+                return Err(BunyanError::InvalidInput(format!(
+                    "Branch '{}' does not exist locally",
+                    branch
+                )));
+            }
+            Ok(WorktreeSpec::Existing {
+                branch: branch.to_string(),
+            })
+        }
+        BranchMode::Remote => {
+            // Accept both "feature" and "origin/feature"
+            let local = branch.strip_prefix(&prefix).unwrap_or(branch).to_string();
+            let upstream = base_ref
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{}{}", prefix, local));
+
+            if base_ref.is_none() {
+                fetch(&local);
+            }
+            if !git.ref_exists(&repo.root_path, &upstream)? {
+                return Err(BunyanError::InvalidInput(format!(
+                    "Remote branch '{}' does not exist",
+                    upstream
+                )));
+            }
+            if local_exists(&local)? {
+                return Err(BunyanError::Conflict(format!(
+                    "Local branch '{}' already exists; use branch mode 'existing'",
+                    local
+                )));
+            }
+            Ok(WorktreeSpec::Track {
+                branch: local,
+                upstream,
+            })
+        }
+    }
+}
+
+/// A linked worktree of an adopted clone that maps onto a Bunyan workspace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdoptedWorktree {
+    pub directory_name: String,
+    pub branch: String,
+}
+
+/// Sort the worktrees of an adopted clone into ones that can become
+/// workspaces and ones that can't, with a reason for each skipped path.
+/// Only worktrees on a branch that live where `workspace_path` would put them
+/// are imported; the main worktree itself is never a workspace.
+pub fn adoptable_worktrees(
+    repo_root: &str,
+    repo_name: &str,
+    entries: &[WorktreeEntry],
+) -> (Vec<AdoptedWorktree>, Vec<String>) {
+    let root = Path::new(repo_root);
+    let mut adopted = Vec::new();
+    let mut skipped = Vec::new();
+    for entry in entries {
+        if entry.bare || Path::new(&entry.path) == root {
+            continue;
+        }
+        let Some(dir_name) = Path::new(&entry.path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(str::to_string)
+        else {
+            skipped.push(format!("{} (unusable path)", entry.path));
+            continue;
+        };
+        let Some(branch) = entry.branch.clone() else {
+            skipped.push(format!("{} (detached HEAD)", entry.path));
+            continue;
+        };
+        match workspace_path(repo_root, repo_name, &dir_name) {
+            Ok(expected) if Path::new(&expected) == Path::new(&entry.path) => {
+                adopted.push(AdoptedWorktree {
+                    directory_name: dir_name,
+                    branch,
+                });
+            }
+            _ => skipped.push(format!("{} (outside the workspaces directory)", entry.path)),
+        }
+    }
+    (adopted, skipped)
+}
+
+/// Collect the git status of a workspace's worktree, measuring ahead/behind
+/// against the repo's remote default branch.
+pub fn git_status(git: &dyn GitOps, repo: &Repo, ws: &Workspace, ws_path: &str) -> Result<GitStatus> {
+    let tree = git.working_tree_status(ws_path)?;
+    let base = format!("{}/{}", repo.remote, repo.default_branch);
+    let counts = git.ahead_behind(ws_path, &base)?;
+    Ok(GitStatus {
+        workspace_id: ws.id.clone(),
+        branch: tree.branch,
+        staged: tree.staged,
+        dirty: tree.dirty,
+        untracked: tree.untracked,
+        conflicted: tree.conflicted,
+        base,
+        ahead: counts.map(|(ahead, _)| ahead),
+        behind: counts.map(|(_, behind)| behind),
+        last_commit: git.last_commit(ws_path)?,
+    })
+}
+
+/// Commit in a workspace's worktree. Paths must stay inside the worktree.
+pub fn commit(git: &dyn GitOps, ws: &Workspace, ws_path: &str, input: &CommitInput) -> Result<CommitResult> {
+    if input.message.trim().is_empty() {
+        return Err(BunyanError::InvalidInput("Commit message is empty".to_string()));
+    }
+    if input.all && !input.paths.is_empty() {
+        return Err(BunyanError::InvalidInput("Use either all or paths, not both".to_string()));
+    }
+    for path in &input.paths {
+        let p = Path::new(path);
+        if path.is_empty() || p.is_absolute() || p.components().any(|c| c == std::path::Component::ParentDir) {
+            return Err(BunyanError::InvalidInput(format!("Invalid path '{}'", path)));
+        }
+    }
+
+    let (status, output) = git.commit(ws_path, &input.message, &input.paths, input.all)?;
+    let commit = match status {
+        CommitStatus::Committed => git.last_commit(ws_path)?,
+        _ => None,
+    };
+    Ok(CommitResult {
+        workspace_id: ws.id.clone(),
+        status,
+        commit,
+        output,
+    })
+}
+
+/// Initialize submodules and pull LFS objects in a new worktree, as the
+/// repo's checkout config asks. Failures don't stop workspace creation; they
+/// come back as warnings to record on the workspace.
+pub fn prepare_worktree(git: &dyn GitOps, checkout: &CheckoutConfig, ws_path: &str) -> Vec<WorkspaceWarning> {
+    let mut warnings = Vec::new();
+    if checkout.submodules {
+        if let Err(e) = git.submodule_update(ws_path) {
+            warnings.push(WorkspaceWarning {
+                kind: WarningKind::Submodules,
+                message: e.to_string(),
+            });
+        }
+    }
+    if checkout.lfs {
+        if let Err(e) = git.lfs_pull(ws_path) {
+            warnings.push(WorkspaceWarning {
+                kind: WarningKind::Lfs,
+                message: e.to_string(),
+            });
+        }
+    }
+    warnings
+}
+
+/// A workspace's sparse-checkout directories.
+pub fn sparse_checkout(git: &dyn GitOps, ws: &Workspace, ws_path: &str) -> Result<SparseCheckout> {
+    let paths = git.sparse_checkout(ws_path)?;
+    Ok(SparseCheckout {
+        workspace_id: ws.id.clone(),
+        sparse: paths.is_some(),
+        paths: paths.unwrap_or_default(),
+    })
+}
+
+/// Widen a workspace's sparse checkout by more directories, or with `all`
+/// check out the whole tree. Widening a full checkout changes nothing.
+pub fn widen_sparse(git: &dyn GitOps, ws: &Workspace, ws_path: &str, input: &SparseInput) -> Result<SparseCheckout> {
+    if input.all && !input.paths.is_empty() {
+        return Err(BunyanError::InvalidInput("Use either all or paths, not both".to_string()));
+    }
+    if !input.all && input.paths.is_empty() {
+        return Err(BunyanError::InvalidInput("No directories to add".to_string()));
+    }
+    for path in &input.paths {
+        check_sparse_dir(path).map_err(|e| BunyanError::InvalidInput(format!("Invalid directory '{}': {}", path, e)))?;
+    }
+
+    if git.sparse_checkout(ws_path)?.is_some() {
+        if input.all {
+            git.sparse_disable(ws_path)?;
+        } else {
+            git.sparse_add(ws_path, &input.paths)?;
+        }
+    }
+    sparse_checkout(git, ws, ws_path)
+}
+
+/// Push the branch checked out in a workspace's worktree to the repo's remote.
+pub fn push(git: &dyn GitOps, repo: &Repo, ws: &Workspace, ws_path: &str) -> Result<PushResult> {
+    let branch = git
+        .working_tree_status(ws_path)?
+        .branch
+        .ok_or_else(|| BunyanError::InvalidInput("HEAD is detached; nothing to push".to_string()))?;
+    let (status, output) = git.push(ws_path, &repo.remote, &branch)?;
+    Ok(PushResult {
+        workspace_id: ws.id.clone(),
+        status,
+        remote: repo.remote.clone(),
+        branch,
+        output,
+    })
+}
+
+/// Where a workspace's checkpoints are stored.
+fn checkpoint_prefix(ws: &Workspace) -> String {
+    format!("refs/bunyan/checkpoints/{}/", ws.id)
+}
+
+fn to_checkpoint(ws: &Workspace, entry: CheckpointEntry) -> Checkpoint {
+    Checkpoint {
+        id: entry.reference.rsplit('/').next().unwrap_or_default().to_string(),
+        workspace_id: ws.id.clone(),
+        sha: entry.sha,
+        head: entry.head,
+        message: entry.message,
+        created_at: entry.created_at,
+    }
+}
+
+/// Snapshot a workspace's working tree, tracked and untracked files, without
+/// touching its index or HEAD.
+pub fn create_checkpoint(git: &dyn GitOps, ws: &Workspace, ws_path: &str, message: Option<&str>) -> Result<Checkpoint> {
+    let message = message.map(str::trim).filter(|m| !m.is_empty()).unwrap_or("Checkpoint");
+    // The suffix keeps two checkpoints taken in the same millisecond apart;
+    // the timestamp first keeps ids sorting oldest to newest
+    let id = format!(
+        "{}-{}",
+        chrono::Utc::now().format("%Y%m%dT%H%M%S%3fZ"),
+        &uuid::Uuid::new_v4().simple().to_string()[..8]
+    );
+    let entry = git.create_checkpoint(ws_path, &format!("{}{}", checkpoint_prefix(ws), id), message)?;
+    Ok(to_checkpoint(ws, entry))
+}
+
+/// A workspace's checkpoints, newest first.
+pub fn list_checkpoints(git: &dyn GitOps, repo: &Repo, ws: &Workspace) -> Result<Vec<Checkpoint>> {
+    Ok(git
+        .list_checkpoints(&repo.root_path, &checkpoint_prefix(ws))?
+        .into_iter()
+        .map(|entry| to_checkpoint(ws, entry))
+        .collect())
+}
+
+/// Put a workspace back to a checkpoint: HEAD to the commit it was taken on
+/// and the working tree to its files. The current state is checkpointed first
+/// so the restore can itself be undone.
+pub fn restore_checkpoint(
+    git: &dyn GitOps,
+    repo: &Repo,
+    ws: &Workspace,
+    ws_path: &str,
+    checkpoint_id: &str,
+) -> Result<RestoreCheckpointResult> {
+    let restored = list_checkpoints(git, repo, ws)?
+        .into_iter()
+        .find(|c| c.id == checkpoint_id)
+        .ok_or_else(|| BunyanError::NotFound(format!("Checkpoint not found: {}", checkpoint_id)))?;
+    if git.working_tree_status(ws_path)?.conflicted > 0 {
+        return Err(BunyanError::Conflict(
+            "Worktree has unresolved conflicts; finish or abort the rebase or merge first".to_string(),
+        ));
+    }
+
+    let backup = create_checkpoint(git, ws, ws_path, Some(&format!("Before restoring {}", checkpoint_id)))?;
+    git.restore_checkpoint(ws_path, &restored.sha)?;
+    Ok(RestoreCheckpointResult { restored, backup })
+}
+
+/// Fetch the repo's default branch and rebase the workspace's branch onto it
+/// (or merge it in). The worktree must have no uncommitted changes.
+pub fn sync(git: &dyn GitOps, repo: &Repo, ws: &Workspace, ws_path: &str, input: &SyncInput) -> Result<SyncResult> {
+    let status = git.working_tree_status(ws_path)?;
+    if status.conflicted > 0 {
+        return Err(BunyanError::Conflict(
+            "Worktree has unresolved conflicts; finish or abort the rebase or merge first".to_string(),
+        ));
+    }
+    if status.staged > 0 || status.dirty > 0 {
+        return Err(BunyanError::Conflict(
+            "Worktree has uncommitted changes; commit them before syncing".to_string(),
+        ));
+    }
+    if status.branch.is_none() {
+        return Err(BunyanError::InvalidInput("HEAD is detached; nothing to sync".to_string()));
+    }
+
+    git.fetch_branch(&repo.root_path, &repo.remote, &repo.default_branch)?;
+    let base = format!("{}/{}", repo.remote, repo.default_branch);
+    let outcome = match git.ahead_behind(ws_path, &base)? {
+        None => return Err(BunyanError::InvalidInput(format!("Unknown ref '{}'", base))),
+        Some((_, 0)) => SyncOutcome {
+            status: SyncStatus::UpToDate,
+            conflicts: Vec::new(),
+            output: String::new(),
+        },
+        Some(_) => git.sync_onto(ws_path, &base, &input.strategy, !input.stop_on_conflict)?,
+    };
+    Ok(SyncResult {
+        workspace_id: ws.id.clone(),
+        aborted: outcome.status == SyncStatus::Conflicted && !input.stop_on_conflict,
+        status: outcome.status,
+        strategy: input.strategy.clone(),
+        base,
+        conflicts: outcome.conflicts,
+        output: outcome.output,
+    })
+}
+
+/// Open a pull request for the branch checked out in a workspace, or return
+/// the one already open for it. The branch must have been pushed.
+pub fn open_pull_request(
+    forge: &dyn Forge,
+    git: &dyn GitOps,
+    repo: &Repo,
+    ws_path: &str,
+    input: &CreatePullRequestInput,
+) -> Result<PullRequest> {
+    let branch = git
+        .working_tree_status(ws_path)?
+        .branch
+        .ok_or_else(|| BunyanError::InvalidInput("HEAD is detached; no branch to open a pull request for".to_string()))?;
+    let base = input.base.clone().unwrap_or_else(|| repo.default_branch.clone());
+    validate_ref_name("base", &base)?;
+    if base == branch {
+        return Err(BunyanError::InvalidInput(format!(
+            "Branch '{}' is the pull request base",
+            branch
+        )));
+    }
+
+    let existing = forge
+        .pull_requests_for_branch(&branch)?
+        .into_iter()
+        .find(|pr| matches!(pr.state, PullRequestState::Open | PullRequestState::Draft));
+    if let Some(pr) = existing {
+        return forge.pull_request(pr.number);
+    }
+
+    let title = match input.title.as_deref().map(str::trim) {
+        Some(title) if !title.is_empty() => title.to_string(),
+        _ => git
+            .last_commit(ws_path)?
+            .map(|c| c.summary)
+            .unwrap_or_else(|| branch.clone()),
+    };
+    forge.create_pull_request(&NewPullRequest {
+        head: branch,
+        base,
+        title,
+        body: input.body.clone(),
+        draft: input.draft,
+    })
+}
+
+/// Current state of a workspace's pull request: the recorded one if any,
+/// otherwise the most recent open one for its branch. A merged or closed PR
+/// for the branch is not adopted, since it may predate the workspace.
+pub fn refresh_pull_request(forge: &dyn Forge, ws: &Workspace) -> Result<Option<PullRequest>> {
+    let number = match &ws.pull_request {
+        Some(pr) => Some(pr.number),
+        None => forge
+            .pull_requests_for_branch(&ws.branch)?
+            .iter()
+            .find(|pr| matches!(pr.state, PullRequestState::Open | PullRequestState::Draft))
+            .map(|pr| pr.number),
+    };
+    number.map(|n| forge.pull_request(n)).transpose()
+}
+
+/// Size cap for a diff patch when the caller doesn't give one.
+pub const DEFAULT_DIFF_BYTES: usize = 1024 * 1024;
+
+/// What `diff` should compare and return.
+#[derive(Debug, Default)]
+pub struct DiffOptions {
+    /// Ref to compare against (default: `<remote>/<default_branch>`)
+    pub base: Option<String>,
+    /// Limit the diff to these paths
+    pub paths: Vec<String>,
+    /// Only return per-file stats, no patch
+    pub stat_only: bool,
+    /// Size cap for the patch (default: `DEFAULT_DIFF_BYTES`)
+    pub max_bytes: Option<usize>,
+}
+
+/// Cut `patch` to at most `max` bytes, ending on a whole line.
+fn truncate_patch(patch: &mut String, max: usize) -> bool {
+    if patch.len() <= max {
+        return false;
+    }
+    let mut cut = max;
+    while !patch.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    let cut = patch[..cut].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    patch.truncate(cut);
+    true
+}
+
+/// Diff a workspace's worktree against the merge-base of HEAD and the base
+/// ref, so only the workspace's own changes (committed or not) show up.
+pub fn diff(
+    git: &dyn GitOps,
+    repo: &Repo,
+    ws: &Workspace,
+    ws_path: &str,
+    options: &DiffOptions,
+) -> Result<WorkspaceDiff> {
+    if options.max_bytes == Some(0) {
+        return Err(BunyanError::InvalidInput("max_bytes must be positive".to_string()));
+    }
+    let base = match &options.base {
+        Some(base) => {
+            validate_ref_name("base ref", base)?;
+            if !git.ref_exists(ws_path, base)? {
+                return Err(BunyanError::InvalidInput(format!("Base ref '{}' does not exist", base)));
+            }
+            base.clone()
+        }
+        None => {
+            let upstream = format!("{}/{}", repo.remote, repo.default_branch);
+            if git.ref_exists(ws_path, &upstream)? {
+                upstream
+            } else {
+                repo.default_branch.clone()
+            }
+        }
+    };
+
+    let merge_base = git.merge_base(ws_path, &base)?;
+    let files = git.diff_stat(ws_path, &merge_base, &options.paths)?;
+    let (patch, truncated) = if options.stat_only {
+        (None, false)
+    } else {
+        let mut patch = git.diff_patch(ws_path, &merge_base, &options.paths)?;
+        let truncated = truncate_patch(&mut patch, options.max_bytes.unwrap_or(DEFAULT_DIFF_BYTES));
+        (Some(patch), truncated)
+    };
+
+    Ok(WorkspaceDiff {
+        workspace_id: ws.id.clone(),
+        base,
+        merge_base,
+        files,
+        patch,
+        truncated,
+    })
+}
+
+/// How many entries of each unsaved-work list to name before summarising.
+const UNSAVED_LIST_LIMIT: usize = 10;
+
+fn describe_unsaved(work: &UnsavedWork) -> String {
+    let section = |label: &str, items: &[String]| {
+        let shown: Vec<&str> = items.iter().take(UNSAVED_LIST_LIMIT).map(String::as_str).collect();
+        let mut text = format!("{} {}: {}", items.len(), label, shown.join(", "));
+        if items.len() > UNSAVED_LIST_LIMIT {
+            text.push_str(&format!(" and {} more", items.len() - UNSAVED_LIST_LIMIT));
+        }
+        text
+    };
+    let mut parts = Vec::new();
+    if !work.dirty.is_empty() {
+        parts.push(section("uncommitted file(s)", &work.dirty));
+    }
+    if !work.untracked.is_empty() {
+        parts.push(section("untracked file(s)", &work.untracked));
+    }
+    if !work.unpushed.is_empty() {
+        parts.push(section("unpushed commit(s)", &work.unpushed));
+    }
+    format!("Workspace has unsaved work ({}); archive with force to discard it", parts.join("; "))
+}
+
+/// Refuse with `Conflict` if removing the worktree at `ws_path` would lose
+/// uncommitted files or commits that were never pushed.
+pub fn ensure_nothing_unsaved(git: &dyn GitOps, ws_path: &str) -> Result<()> {
+    let work = git.unsaved_work(ws_path)?;
+    if work.is_empty() {
+        Ok(())
+    } else {
+        Err(BunyanError::Conflict(describe_unsaved(&work)))
+    }
+}
+
+/// Which copies of a workspace's branch archiving should try to delete.
+#[derive(Debug, Default)]
+pub struct BranchCleanupOptions {
+    pub local: bool,
+    pub remote: bool,
+    /// Delete even if not merged into the default branch
+    pub force: bool,
+}
+
+/// Delete an archived workspace's branch once its worktree is gone. Unless
+/// forced, each copy is only deleted if it is merged into the default branch,
+/// and the default branch itself is never deleted. Git failures are recorded
+/// in the result rather than returned: the archive has already happened.
+pub fn cleanup_branch(
+    git: &dyn GitOps,
+    repo: &Repo,
+    branch: &str,
+    options: &BranchCleanupOptions,
+) -> Option<BranchCleanup> {
+    if !options.local && !options.remote {
+        return None;
+    }
+    let root = repo.root_path.as_str();
+    let mut notes = Vec::new();
+
+    let upstream = format!("{}/{}", repo.remote, repo.default_branch);
+    let target = if git.ref_exists(root, &upstream).unwrap_or(false) {
+        upstream
+    } else {
+        repo.default_branch.clone()
+    };
+
+    // Decide one copy's fate; `delete` runs only once it is safe to
+    let mut settle = |rev: String, delete: &dyn Fn() -> Result<()>| -> BranchOutcome {
+        if branch == repo.default_branch {
+            notes.push(format!("{} is the default branch", branch));
+            return BranchOutcome::Kept;
+        }
+        match git.ref_exists(root, &rev) {
+            Ok(true) => {}
+            Ok(false) => return BranchOutcome::Missing,
+            Err(e) => {
+                notes.push(e.to_string());
+                return BranchOutcome::Failed;
+            }
+        }
+        if !options.force {
+            match git.is_merged(root, &rev, &target) {
+                Ok(true) => {}
+                Ok(false) => {
+                    notes.push(format!("{} is not merged into {}", rev, target));
+                    return BranchOutcome::NotMerged;
+                }
+                Err(e) => {
+                    notes.push(e.to_string());
+                    return BranchOutcome::Failed;
+                }
+            }
+        }
+        match delete() {
+            Ok(()) => BranchOutcome::Deleted,
+            Err(e) => {
+                notes.push(e.to_string());
+                BranchOutcome::Failed
+            }
+        }
+    };
+
+    let local = options.local.then(|| {
+        settle(format!("refs/heads/{}", branch), &|| git.delete_branch(root, branch))
+    });
+    // Judge the remote copy by its own tracking ref: it may hold commits the local branch lacks
+    let remote = options.remote.then(|| {
+        settle(format!("refs/remotes/{}/{}", repo.remote, branch), &|| {
+            git.delete_remote_branch(root, &repo.remote, branch)
+        })
+    });
+
+    notes.dedup();
+    Some(BranchCleanup {
+        local,
+        remote,
+        detail: (!notes.is_empty()).then(|| notes.join("; ")),
+    })
+}
+
 /// Kill the entire tmux window for a workspace (used before archiving).
 pub fn kill_workspace_window(repo_name: &str, workspace_name: &str) {
     let _ = tmux::kill_window(repo_name, workspace_name);
 }
 
-/// Extract container config from a repo's JSON config blob.
+/// The container section of a repo's config, if any.
 pub fn get_container_config(repo: &Repo) -> Option<ContainerConfig> {
-    repo.config
-        .as_ref()
-        .and_then(|v| v.get("container"))
-        .and_then(|v| serde_json::from_value::<ContainerConfig>(v.clone()).ok())
+    repo.config.as_ref().and_then(|c| c.container.clone())
 }
 
-/// Check if dangerously_skip_permissions is enabled in the repo's container config.
-pub fn should_skip_permissions(repo: &Repo) -> bool {
-    get_container_config(repo)
+/// The repo's container config with the workspace's overrides applied.
+pub fn effective_container_config(repo: &Repo, ws: &Workspace) -> Option<ContainerConfig> {
+    config::merge_container(
+        get_container_config(repo).as_ref(),
+        ws.config.as_ref().and_then(|c| c.container.as_ref()),
+    )
+}
+
+/// Check if dangerously_skip_permissions is enabled in the workspace's effective container config.
+pub fn should_skip_permissions(repo: &Repo, ws: &Workspace) -> bool {
+    effective_container_config(repo, ws)
         .map(|c| c.dangerously_skip_permissions)
         .unwrap_or(false)
 }
@@ -67,6 +764,40 @@ pub fn build_claude_cmd(base: &str, skip_permissions: bool) -> String {
     }
 }
 
+/// Parse an age like "90m", "12h", "30d" or "2w" into a duration.
+pub fn parse_age(s: &str) -> Result<chrono::Duration> {
+    let s = s.trim();
+    let invalid = || {
+        BunyanError::InvalidInput(format!(
+            "Invalid age '{}': expected a number followed by s, m, h, d or w",
+            s
+        ))
+    };
+    let unit = s.chars().last().ok_or_else(invalid)?;
+    let amount = i64::from(
+        s[..s.len() - unit.len_utf8()]
+            .parse::<u32>()
+            .map_err(|_| invalid())?,
+    );
+    match unit {
+        's' => Ok(chrono::Duration::seconds(amount)),
+        'm' => Ok(chrono::Duration::minutes(amount)),
+        'h' => Ok(chrono::Duration::hours(amount)),
+        'd' => Ok(chrono::Duration::days(amount)),
+        'w' => Ok(chrono::Duration::weeks(amount)),
+        _ => Err(invalid()),
+    }
+}
+
+/// The moment an age like "30d" before now was. Ages reaching back past the
+/// earliest representable date are rejected rather than overflowing.
+pub fn age_cutoff(s: &str) -> Result<chrono::DateTime<chrono::Utc>> {
+    let age = parse_age(s)?;
+    chrono::Utc::now()
+        .checked_sub_signed(age)
+        .ok_or_else(|| BunyanError::InvalidInput(format!("Invalid age '{}': too far in the past", s.trim())))
+}
+
 /// Validate that a session ID is a safe UUID-like string (hex + dashes + underscores).
 pub fn validate_session_id(id: &str) -> std::result::Result<(), String> {
     if id.is_empty() {
@@ -79,10 +810,808 @@ pub fn validate_session_id(id: &str) -> std::result::Result<(), String> {
     Ok(())
 }
 
+/// The image, ports and `KEY=value` env a workspace's container is created with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerSpec {
+    pub image: String,
+    pub ports: Vec<String>,
+    pub env: Vec<String>,
+}
+
+/// The container spec from the repo's container config with the workspace's
+/// overrides applied. Env entries are sorted so the spec is stable.
+pub fn container_spec(repo: &Repo, workspace: &Workspace) -> ContainerSpec {
+    let config = effective_container_config(repo, workspace);
+    let image = config
+        .as_ref()
+        .and_then(|c| c.image.clone())
+        .unwrap_or_else(|| "node:22".to_string());
+    let ports = config
+        .as_ref()
+        .and_then(|c| c.ports.clone())
+        .unwrap_or_default();
+    let mut env: Vec<String> = config
+        .and_then(|c| c.env)
+        .map(|m| m.into_iter().map(|(k, v)| format!("{}={}", k, v)).collect())
+        .unwrap_or_default();
+    env.sort();
+    ContainerSpec { image, ports, env }
+}
+
+/// Create a workspace container (Docker container setup for container-mode workspaces).
+/// Returns the updated workspace with container_id set.
+/// Takes Arc<AppState> so a pooled connection is never held across await points.
+#[cfg(feature = "server")]
+pub async fn setup_workspace_container(
+    state: &Arc<AppState>,
+    workspace: &Workspace,
+    repo: &Repo,
+) -> std::result::Result<Workspace, String> {
+    let spec = container_spec(repo, workspace);
+
+    let wt_path = workspace_path(&repo.root_path, &repo.name, &workspace.directory_name)
+        .map_err(|e| e.to_string())?;
+    let container_name = docker::sanitize_docker_name(
+        &format!("bunyan-{}-{}", repo.name, workspace.directory_name),
+    );
+
+    let network_name = docker::sanitize_docker_name(&format!("bunyan-{}", repo.name));
+    docker::create_network(&network_name)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let container_id = docker::create_workspace_container(
+        &spec.image,
+        &wt_path,
+        &container_name,
+        &spec.ports,
+        &spec.env,
+        Some(&network_name),
+        &workspace.directory_name,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    // Best-effort: install claude in the container
+    if let Err(e) = docker::ensure_claude(&container_id).await {
+        eprintln!("Warning: could not install Claude in container: {}", e);
+    }
+
+    let id = workspace.id.clone();
+    state
+        .db
+        .call(move |conn| {
+            db::workspaces::set_container_id(conn, &id, &container_id)?;
+            db::workspaces::get(conn, &id)
+        })
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::Repo;
+    use crate::models::{ContainerMode, Repo};
+
+    /// GitOps double that knows a fixed set of refs and records fetches.
+    struct FakeGit {
+        refs: Vec<&'static str>,
+        fetched: std::sync::Mutex<Vec<String>>,
+        unsaved: UnsavedWork,
+        merged: Vec<&'static str>,
+        deleted: std::sync::Mutex<Vec<String>>,
+        remote_rejects: bool,
+        commit_status: CommitStatus,
+        pushed: std::sync::Mutex<Vec<String>>,
+        sparse: std::sync::Mutex<Option<Vec<String>>>,
+        prepared: std::sync::Mutex<Vec<&'static str>>,
+        lfs_fails: bool,
+        dirty: u32,
+        sync_conflicts: Vec<&'static str>,
+        synced: std::sync::Mutex<Vec<String>>,
+        checkpoints: std::sync::Mutex<Vec<CheckpointEntry>>,
+        restored: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl FakeGit {
+        fn with_refs(refs: &[&'static str]) -> Self {
+            FakeGit {
+                refs: refs.to_vec(),
+                fetched: std::sync::Mutex::new(Vec::new()),
+                unsaved: UnsavedWork::default(),
+                merged: Vec::new(),
+                deleted: std::sync::Mutex::new(Vec::new()),
+                remote_rejects: false,
+                commit_status: CommitStatus::Committed,
+                pushed: std::sync::Mutex::new(Vec::new()),
+                sparse: std::sync::Mutex::new(None),
+                prepared: std::sync::Mutex::new(Vec::new()),
+                lfs_fails: false,
+                dirty: 2,
+                sync_conflicts: Vec::new(),
+                synced: std::sync::Mutex::new(Vec::new()),
+                checkpoints: std::sync::Mutex::new(Vec::new()),
+                restored: std::sync::Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    impl GitOps for FakeGit {
+        fn clone_repo(&self, _url: &str, _path: &str, _options: &crate::git::CloneOptions) -> Result<()> {
+            Ok(())
+        }
+        fn worktree_add(&self, _repo: &str, _path: &str, _spec: &WorktreeSpec, sparse: &[String]) -> Result<()> {
+            if !sparse.is_empty() {
+                *self.sparse.lock().unwrap() = Some(sparse.to_vec());
+            }
+            Ok(())
+        }
+        fn fetch_branch(&self, _repo: &str, remote: &str, branch: &str) -> Result<()> {
+            self.fetched.lock().unwrap().push(format!("{}/{}", remote, branch));
+            Ok(())
+        }
+        fn ref_exists(&self, _repo: &str, reference: &str) -> Result<bool> {
+            Ok(self.refs.contains(&reference))
+        }
+        fn worktree_remove(&self, _repo: &str, _path: &str, _force: bool) -> Result<()> {
+            Ok(())
+        }
+        fn worktree_list(&self, _repo: &str) -> Result<Vec<crate::git::WorktreeEntry>> {
+            Ok(Vec::new())
+        }
+        fn inspect_clone(&self, _path: &str, _remote: &str) -> Result<crate::git::CloneInfo> {
+            Err(BunyanError::InvalidInput("not a clone".to_string()))
+        }
+        fn worktree_prune(&self, _repo: &str) -> Result<()> {
+            Ok(())
+        }
+        fn working_tree_status(&self, _path: &str) -> Result<crate::git::WorkingTreeStatus> {
+            Ok(crate::git::WorkingTreeStatus {
+                branch: Some("fix".to_string()),
+                dirty: self.dirty,
+                ..Default::default()
+            })
+        }
+        fn ahead_behind(&self, _path: &str, base: &str) -> Result<Option<(u32, u32)>> {
+            Ok(self.refs.contains(&base).then_some((1, 4)))
+        }
+        fn last_commit(&self, _path: &str) -> Result<Option<crate::models::CommitSummary>> {
+            Ok(None)
+        }
+        fn merge_base(&self, _path: &str, other: &str) -> Result<String> {
+            Ok(format!("base-of-{}", other))
+        }
+        fn diff_stat(
+            &self,
+            _path: &str,
+            _from: &str,
+            paths: &[String],
+        ) -> Result<Vec<crate::models::DiffFileStat>> {
+            Ok(paths
+                .iter()
+                .map(|p| crate::models::DiffFileStat {
+                    path: p.clone(),
+                    additions: 1,
+                    deletions: 0,
+                    binary: false,
+                })
+                .collect())
+        }
+        fn diff_patch(&self, _path: &str, from: &str, _paths: &[String]) -> Result<String> {
+            Ok(format!("diff from {}\n+line one\n+line two\n", from))
+        }
+        fn unsaved_work(&self, _path: &str) -> Result<UnsavedWork> {
+            Ok(self.unsaved.clone())
+        }
+        fn is_merged(&self, _repo: &str, rev: &str, _into: &str) -> Result<bool> {
+            Ok(self.merged.contains(&rev))
+        }
+        fn delete_branch(&self, _repo: &str, branch: &str) -> Result<()> {
+            self.deleted.lock().unwrap().push(branch.to_string());
+            Ok(())
+        }
+        fn delete_remote_branch(&self, _repo: &str, remote: &str, branch: &str) -> Result<()> {
+            if self.remote_rejects {
+                return Err(BunyanError::Git("remote rejected".to_string()));
+            }
+            self.deleted.lock().unwrap().push(format!("{}/{}", remote, branch));
+            Ok(())
+        }
+        fn commit(&self, _path: &str, _message: &str, _paths: &[String], _all: bool) -> Result<(CommitStatus, String)> {
+            let output = match self.commit_status {
+                CommitStatus::HookFailed => "lint failed",
+                _ => "",
+            };
+            Ok((self.commit_status.clone(), output.to_string()))
+        }
+        fn push(&self, _path: &str, remote: &str, branch: &str) -> Result<(crate::models::PushStatus, String)> {
+            self.pushed.lock().unwrap().push(format!("{}/{}", remote, branch));
+            let status = if self.remote_rejects {
+                crate::models::PushStatus::Rejected
+            } else {
+                crate::models::PushStatus::Pushed
+            };
+            Ok((status, String::new()))
+        }
+        fn sparse_checkout(&self, _path: &str) -> Result<Option<Vec<String>>> {
+            Ok(self.sparse.lock().unwrap().clone())
+        }
+        fn sparse_add(&self, _path: &str, paths: &[String]) -> Result<()> {
+            if let Some(cone) = self.sparse.lock().unwrap().as_mut() {
+                cone.extend(paths.iter().cloned());
+            }
+            Ok(())
+        }
+        fn sparse_disable(&self, _path: &str) -> Result<()> {
+            *self.sparse.lock().unwrap() = None;
+            Ok(())
+        }
+        fn submodule_update(&self, _path: &str) -> Result<()> {
+            self.prepared.lock().unwrap().push("submodules");
+            Ok(())
+        }
+        fn lfs_pull(&self, _path: &str) -> Result<()> {
+            self.prepared.lock().unwrap().push("lfs");
+            if self.lfs_fails {
+                return Err(BunyanError::Git("git-lfs is not installed".to_string()));
+            }
+            Ok(())
+        }
+        fn sync_onto(
+            &self,
+            _path: &str,
+            upstream: &str,
+            strategy: &crate::models::SyncStrategy,
+            abort_on_conflict: bool,
+        ) -> Result<SyncOutcome> {
+            self.synced
+                .lock()
+                .unwrap()
+                .push(format!("{:?} {} abort={}", strategy, upstream, abort_on_conflict));
+            let status = if self.sync_conflicts.is_empty() {
+                SyncStatus::Synced
+            } else {
+                SyncStatus::Conflicted
+            };
+            Ok(SyncOutcome {
+                status,
+                conflicts: self.sync_conflicts.iter().map(|c| c.to_string()).collect(),
+                output: String::new(),
+            })
+        }
+        fn create_checkpoint(&self, _path: &str, reference: &str, message: &str) -> Result<CheckpointEntry> {
+            let mut checkpoints = self.checkpoints.lock().unwrap();
+            let entry = CheckpointEntry {
+                reference: reference.to_string(),
+                sha: format!("sha{}", checkpoints.len()),
+                head: "head".to_string(),
+                message: message.to_string(),
+                created_at: "2026-01-01T00:00:00Z".to_string(),
+            };
+            checkpoints.push(entry.clone());
+            Ok(entry)
+        }
+        fn list_checkpoints(&self, _repo: &str, prefix: &str) -> Result<Vec<CheckpointEntry>> {
+            let checkpoints = self.checkpoints.lock().unwrap();
+            Ok(checkpoints.iter().rev().filter(|c| c.reference.starts_with(prefix)).cloned().collect())
+        }
+        fn restore_checkpoint(&self, _path: &str, sha: &str) -> Result<()> {
+            self.restored.lock().unwrap().push(sha.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn diff_starts_at_merge_base_with_default_branch() {
+        let git = FakeGit::with_refs(&["origin/main"]);
+        let options = DiffOptions {
+            paths: vec!["src/lib.rs".to_string()],
+            ..Default::default()
+        };
+        let result = diff(&git, &make_repo(None), &make_workspace(None), "/ws", &options).unwrap();
+        assert_eq!(result.base, "origin/main");
+        assert_eq!(result.merge_base, "base-of-origin/main");
+        assert_eq!(result.files.len(), 1);
+        assert!(result.patch.unwrap().starts_with("diff from base-of-origin/main"));
+        assert!(!result.truncated);
+    }
+
+    #[test]
+    fn diff_stat_mode_omits_patch() {
+        let git = FakeGit::with_refs(&["origin/main"]);
+        let options = DiffOptions {
+            stat_only: true,
+            ..Default::default()
+        };
+        let result = diff(&git, &make_repo(None), &make_workspace(None), "/ws", &options).unwrap();
+        assert!(result.patch.is_none());
+    }
+
+    #[test]
+    fn diff_truncates_on_line_boundary() {
+        let git = FakeGit::with_refs(&["origin/main"]);
+        let options = DiffOptions {
+            max_bytes: Some(40),
+            ..Default::default()
+        };
+        let result = diff(&git, &make_repo(None), &make_workspace(None), "/ws", &options).unwrap();
+        assert!(result.truncated);
+        let patch = result.patch.unwrap();
+        assert!(patch.len() <= 40);
+        assert!(patch.ends_with('\n'));
+    }
+
+    #[test]
+    fn diff_rejects_unknown_base() {
+        let git = FakeGit::with_refs(&["origin/main"]);
+        let options = DiffOptions {
+            base: Some("release".to_string()),
+            ..Default::default()
+        };
+        let result = diff(&git, &make_repo(None), &make_workspace(None), "/ws", &options);
+        assert!(matches!(result, Err(BunyanError::InvalidInput(_))));
+    }
+
+    fn cleanup_options(local: bool, remote: bool, force: bool) -> BranchCleanupOptions {
+        BranchCleanupOptions { local, remote, force }
+    }
+
+    #[test]
+    fn cleanup_not_requested_records_nothing() {
+        let git = FakeGit::with_refs(&[]);
+        let options = BranchCleanupOptions::default();
+        assert_eq!(cleanup_branch(&git, &make_repo(None), "fix", &options), None);
+    }
+
+    #[test]
+    fn cleanup_deletes_merged_branch_locally_and_remotely() {
+        let mut git = FakeGit::with_refs(&["origin/main", "refs/heads/fix", "refs/remotes/origin/fix"]);
+        git.merged = vec!["refs/heads/fix", "refs/remotes/origin/fix"];
+        let cleanup = cleanup_branch(&git, &make_repo(None), "fix", &cleanup_options(true, true, false)).unwrap();
+        assert_eq!(cleanup.local, Some(BranchOutcome::Deleted));
+        assert_eq!(cleanup.remote, Some(BranchOutcome::Deleted));
+        assert_eq!(cleanup.detail, None);
+        assert_eq!(*git.deleted.lock().unwrap(), vec!["fix", "origin/fix"]);
+    }
+
+    #[test]
+    fn cleanup_keeps_unmerged_branch_unless_forced() {
+        let git = FakeGit::with_refs(&["origin/main", "refs/heads/fix"]);
+        let kept = cleanup_branch(&git, &make_repo(None), "fix", &cleanup_options(true, false, false)).unwrap();
+        assert_eq!(kept.local, Some(BranchOutcome::NotMerged));
+        assert_eq!(kept.remote, None);
+        assert!(kept.detail.unwrap().contains("not merged into origin/main"));
+        assert!(git.deleted.lock().unwrap().is_empty());
+
+        let forced = cleanup_branch(&git, &make_repo(None), "fix", &cleanup_options(true, true, true)).unwrap();
+        assert_eq!(forced.local, Some(BranchOutcome::Deleted));
+        assert_eq!(forced.remote, Some(BranchOutcome::Missing));
+    }
+
+    #[test]
+    fn cleanup_never_deletes_default_branch() {
+        let git = FakeGit::with_refs(&["refs/heads/main"]);
+        let cleanup = cleanup_branch(&git, &make_repo(None), "main", &cleanup_options(true, true, true)).unwrap();
+        assert_eq!(cleanup.local, Some(BranchOutcome::Kept));
+        assert_eq!(cleanup.remote, Some(BranchOutcome::Kept));
+        assert_eq!(cleanup.detail.as_deref(), Some("main is the default branch"));
+    }
+
+    #[test]
+    fn cleanup_records_remote_failure() {
+        let mut git = FakeGit::with_refs(&["refs/heads/fix", "refs/remotes/origin/fix"]);
+        git.remote_rejects = true;
+        let cleanup = cleanup_branch(&git, &make_repo(None), "fix", &cleanup_options(true, true, true)).unwrap();
+        assert_eq!(cleanup.local, Some(BranchOutcome::Deleted));
+        assert_eq!(cleanup.remote, Some(BranchOutcome::Failed));
+        assert!(cleanup.detail.unwrap().contains("remote rejected"));
+    }
+
+    fn commit_input(paths: &[&str], all: bool) -> CommitInput {
+        CommitInput {
+            message: "Fix login".to_string(),
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+            all,
+        }
+    }
+
+    #[test]
+    fn commit_rejects_bad_input() {
+        let git = FakeGit::with_refs(&[]);
+        let ws = make_workspace(None);
+        let mut empty = commit_input(&[], false);
+        empty.message = "  ".to_string();
+        for input in [empty, commit_input(&["a.rs"], true), commit_input(&["../other/a.rs"], false), commit_input(&["/etc/passwd"], false)] {
+            assert!(matches!(commit(&git, &ws, "/ws", &input), Err(BunyanError::InvalidInput(_))));
+        }
+    }
+
+    #[test]
+    fn commit_reports_hook_failure_as_result() {
+        let mut git = FakeGit::with_refs(&[]);
+        git.commit_status = CommitStatus::HookFailed;
+        let result = commit(&git, &make_workspace(None), "/ws", &commit_input(&["src/a.rs"], false)).unwrap();
+        assert_eq!(result.status, CommitStatus::HookFailed);
+        assert_eq!(result.output, "lint failed");
+        assert!(result.commit.is_none());
+    }
+
+    #[test]
+    fn push_uses_checked_out_branch_and_repo_remote() {
+        let mut git = FakeGit::with_refs(&[]);
+        git.remote_rejects = true;
+        let result = push(&git, &make_repo(None), &make_workspace(None), "/ws").unwrap();
+        assert_eq!(result.status, crate::models::PushStatus::Rejected);
+        assert_eq!((result.remote.as_str(), result.branch.as_str()), ("origin", "fix"));
+        assert_eq!(*git.pushed.lock().unwrap(), vec!["origin/fix"]);
+    }
+
+    #[test]
+    fn prepare_worktree_runs_requested_steps_and_collects_failures() {
+        let mut git = FakeGit::with_refs(&[]);
+        git.lfs_fails = true;
+        assert!(prepare_worktree(&git, &CheckoutConfig::default(), "/ws").is_empty());
+        assert!(git.prepared.lock().unwrap().is_empty());
+
+        let checkout = CheckoutConfig {
+            submodules: true,
+            lfs: true,
+            ..Default::default()
+        };
+        let warnings = prepare_worktree(&git, &checkout, "/ws");
+        assert_eq!(*git.prepared.lock().unwrap(), vec!["submodules", "lfs"]);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, WarningKind::Lfs);
+        assert!(warnings[0].message.contains("git-lfs is not installed"));
+    }
+
+    fn sparse_input(paths: &[&str], all: bool) -> SparseInput {
+        SparseInput {
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+            all,
+        }
+    }
+
+    #[test]
+    fn widen_sparse_adds_directories_then_checks_out_everything() {
+        let git = FakeGit::with_refs(&[]);
+        let ws = make_workspace(None);
+        git.worktree_add("/repo", "/ws", &WorktreeSpec::Existing { branch: "fix".to_string() }, &["api".to_string()])
+            .unwrap();
+
+        let widened = widen_sparse(&git, &ws, "/ws", &sparse_input(&["libs/"], false)).unwrap();
+        assert!(widened.sparse);
+        assert_eq!(widened.paths, vec!["api", "libs/"]);
+
+        let full = widen_sparse(&git, &ws, "/ws", &sparse_input(&[], true)).unwrap();
+        assert!(!full.sparse);
+        assert!(full.paths.is_empty());
+        // Already full: nothing left to widen
+        assert!(!widen_sparse(&git, &ws, "/ws", &sparse_input(&["api"], false)).unwrap().sparse);
+    }
+
+    #[test]
+    fn widen_sparse_rejects_bad_input() {
+        let git = FakeGit::with_refs(&[]);
+        let ws = make_workspace(None);
+        for input in [
+            sparse_input(&[], false),
+            sparse_input(&["api"], true),
+            sparse_input(&["../other"], false),
+            sparse_input(&["src/**"], false),
+        ] {
+            let err = widen_sparse(&git, &ws, "/ws", &input).unwrap_err();
+            assert!(matches!(err, BunyanError::InvalidInput(_)), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn sync_fetches_then_rebases_onto_default_branch() {
+        let mut git = FakeGit::with_refs(&["origin/main"]);
+        git.dirty = 0;
+        let result = sync(&git, &make_repo(None), &make_workspace(None), "/ws", &SyncInput::default()).unwrap();
+        assert_eq!(result.status, SyncStatus::Synced);
+        assert_eq!(result.base, "origin/main");
+        assert!(!result.aborted);
+        assert_eq!(*git.fetched.lock().unwrap(), vec!["origin/main"]);
+        assert_eq!(*git.synced.lock().unwrap(), vec!["Rebase origin/main abort=true"]);
+    }
+
+    #[test]
+    fn sync_reports_conflicts_and_whether_it_aborted() {
+        let mut git = FakeGit::with_refs(&["origin/main"]);
+        git.dirty = 0;
+        git.sync_conflicts = vec!["src/app.rs"];
+        let (repo, ws) = (make_repo(None), make_workspace(None));
+
+        let aborted = sync(&git, &repo, &ws, "/ws", &SyncInput::default()).unwrap();
+        assert_eq!(aborted.status, SyncStatus::Conflicted);
+        assert_eq!(aborted.conflicts, vec!["src/app.rs"]);
+        assert!(aborted.aborted);
+
+        let input = SyncInput {
+            strategy: crate::models::SyncStrategy::Merge,
+            stop_on_conflict: true,
+        };
+        let stopped = sync(&git, &repo, &ws, "/ws", &input).unwrap();
+        assert!(!stopped.aborted);
+        assert_eq!(git.synced.lock().unwrap()[1], "Merge origin/main abort=false");
+    }
+
+    #[test]
+    fn sync_refuses_uncommitted_changes_and_unknown_base() {
+        let git = FakeGit::with_refs(&["origin/main"]);
+        let err = sync(&git, &make_repo(None), &make_workspace(None), "/ws", &SyncInput::default()).unwrap_err();
+        assert!(matches!(err, BunyanError::Conflict(_)));
+
+        let mut git = FakeGit::with_refs(&[]);
+        git.dirty = 0;
+        // FakeGit reports no ahead/behind for refs it doesn't know
+        let err = sync(&git, &make_repo(None), &make_workspace(None), "/ws", &SyncInput::default()).unwrap_err();
+        assert!(matches!(err, BunyanError::InvalidInput(_)));
+        assert!(git.synced.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn checkpoints_are_stored_per_workspace() {
+        let git = FakeGit::with_refs(&[]);
+        let (repo, ws) = (make_repo(None), make_workspace(None));
+
+        let first = create_checkpoint(&git, &ws, "/ws", None).unwrap();
+        assert_eq!(first.message, "Checkpoint");
+        assert_eq!(first.workspace_id, ws.id);
+        let reference = git.checkpoints.lock().unwrap()[0].reference.clone();
+        assert_eq!(reference, format!("refs/bunyan/checkpoints/{}/{}", ws.id, first.id));
+
+        let mut other = make_workspace(None);
+        other.id = "other".to_string();
+        create_checkpoint(&git, &other, "/other", Some("  Before refactor ")).unwrap();
+        let listed = list_checkpoints(&git, &repo, &ws).unwrap();
+        assert_eq!(listed, vec![first]);
+        assert_eq!(list_checkpoints(&git, &repo, &other).unwrap()[0].message, "Before refactor");
+    }
+
+    #[test]
+    fn checkpoints_taken_together_get_distinct_ids() {
+        let git = FakeGit::with_refs(&[]);
+        let ws = make_workspace(None);
+        let first = create_checkpoint(&git, &ws, "/ws", None).unwrap();
+        let second = create_checkpoint(&git, &ws, "/ws", None).unwrap();
+        assert_ne!(first.id, second.id);
+    }
+
+    #[test]
+    fn restore_checkpoint_backs_up_current_state_first() {
+        let git = FakeGit::with_refs(&[]);
+        let (repo, ws) = (make_repo(None), make_workspace(None));
+        let saved = create_checkpoint(&git, &ws, "/ws", Some("Before Claude")).unwrap();
+
+        let result = restore_checkpoint(&git, &repo, &ws, "/ws", &saved.id).unwrap();
+        assert_eq!(result.restored, saved);
+        assert_eq!(result.backup.message, format!("Before restoring {}", saved.id));
+        assert_eq!(*git.restored.lock().unwrap(), vec![saved.sha]);
+
+        let missing = restore_checkpoint(&git, &repo, &ws, "/ws", "nope");
+        assert!(matches!(missing, Err(BunyanError::NotFound(_))));
+    }
+
+    /// Forge double holding a fixed list of pull requests.
+    struct FakeForge {
+        pulls: Vec<PullRequest>,
+        created: std::sync::Mutex<Vec<NewPullRequest>>,
+    }
+
+    fn make_pull(number: u64, state: PullRequestState) -> PullRequest {
+        PullRequest {
+            number,
+            url: format!("https://github.com/org/app/pull/{}", number),
+            title: "Fix login".to_string(),
+            state,
+            head: "fix".to_string(),
+            base: "main".to_string(),
+            checks: None,
+        }
+    }
+
+    impl Forge for FakeForge {
+        fn create_pull_request(&self, pr: &NewPullRequest) -> Result<PullRequest> {
+            self.created.lock().unwrap().push(pr.clone());
+            Ok(PullRequest {
+                title: pr.title.clone(),
+                ..make_pull(99, PullRequestState::Open)
+            })
+        }
+        fn pull_request(&self, number: u64) -> Result<PullRequest> {
+            let pr = self.pulls.iter().find(|pr| pr.number == number).cloned();
+            pr.map(|pr| PullRequest {
+                checks: Some(crate::models::ChecksState::Pending),
+                ..pr
+            })
+            .ok_or_else(|| BunyanError::NotFound(format!("pull {}", number)))
+        }
+        fn pull_requests_for_branch(&self, branch: &str) -> Result<Vec<PullRequest>> {
+            Ok(self.pulls.iter().filter(|pr| pr.head == branch).cloned().collect())
+        }
+    }
+
+    fn fake_forge(pulls: Vec<PullRequest>) -> FakeForge {
+        FakeForge {
+            pulls,
+            created: std::sync::Mutex::new(Vec::new()),
+        }
+    }
+
+    #[test]
+    fn pull_request_opened_against_default_branch() {
+        let forge = fake_forge(vec![make_pull(3, PullRequestState::Closed)]);
+        let git = FakeGit::with_refs(&[]);
+        let pr = open_pull_request(&forge, &git, &make_repo(None), "/ws", &CreatePullRequestInput::default()).unwrap();
+        assert_eq!(pr.number, 99);
+        let created = forge.created.lock().unwrap();
+        assert_eq!((created[0].head.as_str(), created[0].base.as_str()), ("fix", "main"));
+        // No commit to take a title from, so the branch name is used
+        assert_eq!(created[0].title, "fix");
+    }
+
+    #[test]
+    fn existing_open_pull_request_is_returned() {
+        let forge = fake_forge(vec![make_pull(5, PullRequestState::Draft)]);
+        let git = FakeGit::with_refs(&[]);
+        let input = CreatePullRequestInput {
+            title: Some("Another".to_string()),
+            ..Default::default()
+        };
+        let pr = open_pull_request(&forge, &git, &make_repo(None), "/ws", &input).unwrap();
+        assert_eq!(pr.number, 5);
+        assert!(pr.checks.is_some());
+        assert!(forge.created.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn pull_request_base_must_differ_from_branch() {
+        let forge = fake_forge(Vec::new());
+        let git = FakeGit::with_refs(&[]);
+        let input = CreatePullRequestInput {
+            base: Some("fix".to_string()),
+            ..Default::default()
+        };
+        let result = open_pull_request(&forge, &git, &make_repo(None), "/ws", &input);
+        assert!(matches!(result, Err(BunyanError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn refresh_finds_open_pull_request_by_branch() {
+        let mut ws = make_workspace(None);
+        ws.branch = "fix".to_string();
+        let forge = fake_forge(vec![make_pull(5, PullRequestState::Merged), make_pull(7, PullRequestState::Open)]);
+        let pr = refresh_pull_request(&forge, &ws).unwrap().unwrap();
+        assert_eq!(pr.number, 7);
+
+        ws.branch = "other".to_string();
+        assert_eq!(refresh_pull_request(&forge, &ws).unwrap(), None);
+    }
+
+    #[test]
+    fn refresh_adopts_only_open_pull_requests_unless_recorded() {
+        let mut ws = make_workspace(None);
+        ws.branch = "fix".to_string();
+        let forge = fake_forge(vec![make_pull(5, PullRequestState::Merged)]);
+        assert_eq!(refresh_pull_request(&forge, &ws).unwrap(), None);
+
+        ws.pull_request = Some(make_pull(5, PullRequestState::Open));
+        let pr = refresh_pull_request(&forge, &ws).unwrap().unwrap();
+        assert_eq!(pr.state, PullRequestState::Merged);
+    }
+
+    #[test]
+    fn clean_worktree_can_be_archived() {
+        let git = FakeGit::with_refs(&[]);
+        assert!(ensure_nothing_unsaved(&git, "/ws").is_ok());
+    }
+
+    #[test]
+    fn unsaved_work_blocks_archive_and_lists_it() {
+        let mut git = FakeGit::with_refs(&[]);
+        git.unsaved = UnsavedWork {
+            dirty: vec!["src/lib.rs".to_string()],
+            untracked: (0..12).map(|i| format!("scratch{}.txt", i)).collect(),
+            unpushed: vec!["abc1234 WIP".to_string()],
+        };
+        let Err(BunyanError::Conflict(msg)) = ensure_nothing_unsaved(&git, "/ws") else {
+            panic!("expected a conflict");
+        };
+        assert!(msg.contains("1 uncommitted file(s): src/lib.rs"));
+        assert!(msg.contains("12 untracked file(s): scratch0.txt"));
+        assert!(msg.contains("and 2 more"));
+        assert!(!msg.contains("scratch11.txt"));
+        assert!(msg.contains("1 unpushed commit(s): abc1234 WIP"));
+    }
+
+    #[test]
+    fn git_status_measures_against_remote_default_branch() {
+        let git = FakeGit::with_refs(&["origin/main"]);
+        let status = git_status(&git, &make_repo(None), &make_workspace(None), "/ws").unwrap();
+        assert_eq!(status.base, "origin/main");
+        assert_eq!(status.dirty, 2);
+        assert_eq!((status.ahead, status.behind), (Some(1), Some(4)));
+    }
+
+    #[test]
+    fn git_status_without_base_ref_omits_counts() {
+        let git = FakeGit::with_refs(&[]);
+        let status = git_status(&git, &make_repo(None), &make_workspace(None), "/ws").unwrap();
+        assert_eq!((status.ahead, status.behind), (None, None));
+    }
+
+    #[test]
+    fn plan_new_branch_defaults_to_remote_default_branch() {
+        let git = FakeGit::with_refs(&["origin/main", "refs/heads/main"]);
+        let spec = plan_worktree(&git, &make_repo(None), &BranchMode::New, "fix", None).unwrap();
+        assert_eq!(
+            spec,
+            WorktreeSpec::NewBranch {
+                branch: "fix".to_string(),
+                base: Some("origin/main".to_string()),
+            }
+        );
+        assert_eq!(*git.fetched.lock().unwrap(), vec!["origin/main"]);
+    }
+
+    #[test]
+    fn plan_new_branch_fetches_a_remote_base_only() {
+        let git = FakeGit::with_refs(&["origin/release", "v1.0"]);
+        let repo = make_repo(None);
+        plan_worktree(&git, &repo, &BranchMode::New, "fix", Some("origin/release")).unwrap();
+        plan_worktree(&git, &repo, &BranchMode::New, "tag-fix", Some("v1.0")).unwrap();
+        assert_eq!(*git.fetched.lock().unwrap(), vec!["origin/release"]);
+    }
+
+    #[test]
+    fn plan_new_branch_rejects_unknown_base() {
+        let git = FakeGit::with_refs(&["origin/main"]);
+        let result = plan_worktree(&git, &make_repo(None), &BranchMode::New, "fix", Some("v9.9"));
+        assert!(matches!(result, Err(BunyanError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn plan_existing_requires_local_branch() {
+        let git = FakeGit::with_refs(&["refs/heads/feature"]);
+        let repo = make_repo(None);
+        let spec = plan_worktree(&git, &repo, &BranchMode::Existing, "feature", None).unwrap();
+        assert_eq!(spec, WorktreeSpec::Existing { branch: "feature".to_string() });
+
+        let missing = plan_worktree(&git, &repo, &BranchMode::Existing, "other", None);
+        assert!(matches!(missing, Err(BunyanError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn plan_remote_strips_prefix_and_fetches() {
+        let git = FakeGit::with_refs(&["origin/feature"]);
+        let spec =
+            plan_worktree(&git, &make_repo(None), &BranchMode::Remote, "origin/feature", None).unwrap();
+        assert_eq!(
+            spec,
+            WorktreeSpec::Track {
+                branch: "feature".to_string(),
+                upstream: "origin/feature".to_string(),
+            }
+        );
+        assert_eq!(*git.fetched.lock().unwrap(), vec!["origin/feature"]);
+    }
+
+    #[test]
+    fn plan_remote_conflicts_with_existing_local_branch() {
+        let git = FakeGit::with_refs(&["origin/feature", "refs/heads/feature"]);
+        let result = plan_worktree(&git, &make_repo(None), &BranchMode::Remote, "feature", None);
+        assert!(matches!(result, Err(BunyanError::Conflict(_))));
+    }
+
+    #[test]
+    fn plan_rejects_option_like_branch() {
+        let git = FakeGit::with_refs(&[]);
+        let result = plan_worktree(&git, &make_repo(None), &BranchMode::New, "--force", None);
+        assert!(matches!(result, Err(BunyanError::InvalidInput(_))));
+    }
 
     #[test]
     fn workspace_path_derives_from_repo_root() {
@@ -103,6 +1632,32 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn adoptable_worktrees_imports_only_workspace_dirs() {
+        let entry = |path: &str, branch: Option<&str>| WorktreeEntry {
+            path: path.to_string(),
+            branch: branch.map(str::to_string),
+            bare: false,
+        };
+        let entries = vec![
+            entry("/data/bunyan/repos/app", Some("main")),
+            entry("/data/bunyan/workspaces/app/fix", Some("fix-login")),
+            entry("/data/bunyan/workspaces/app/probe", None),
+            entry("/tmp/scratch", Some("spike")),
+        ];
+        let (adopted, skipped) = adoptable_worktrees("/data/bunyan/repos/app", "app", &entries);
+        assert_eq!(
+            adopted,
+            vec![AdoptedWorktree {
+                directory_name: "fix".to_string(),
+                branch: "fix-login".to_string(),
+            }]
+        );
+        assert_eq!(skipped.len(), 2);
+        assert!(skipped[0].contains("detached"));
+        assert!(skipped[1].starts_with("/tmp/scratch"));
+    }
+
     #[test]
     fn workspace_path_root_errors() {
         let result = workspace_path("/", "myrepo", "fix");
@@ -148,6 +1703,33 @@ mod tests {
         assert!(validate_session_id("../../etc/passwd").is_err());
     }
 
+    #[test]
+    fn parse_age_accepts_each_unit() {
+        assert_eq!(parse_age("45s").unwrap(), chrono::Duration::seconds(45));
+        assert_eq!(parse_age("90m").unwrap(), chrono::Duration::minutes(90));
+        assert_eq!(parse_age("12h").unwrap(), chrono::Duration::hours(12));
+        assert_eq!(parse_age("30d").unwrap(), chrono::Duration::days(30));
+        assert_eq!(parse_age(" 2w ").unwrap(), chrono::Duration::weeks(2));
+    }
+
+    #[test]
+    fn parse_age_rejects_malformed() {
+        assert!(parse_age("").is_err());
+        assert!(parse_age("30").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("30y").is_err());
+        assert!(parse_age("-").is_err());
+        assert!(parse_age("-5d").is_err());
+    }
+
+    #[test]
+    fn age_cutoff_rejects_ages_before_the_earliest_date() {
+        let cutoff = age_cutoff("1d").unwrap();
+        assert!(cutoff < chrono::Utc::now() - chrono::Duration::hours(23));
+        assert!(matches!(age_cutoff("4000000000d"), Err(BunyanError::InvalidInput(_))));
+        assert!(matches!(age_cutoff("4000000000w"), Err(BunyanError::InvalidInput(_))));
+    }
+
     #[test]
     fn build_claude_cmd_without_skip() {
         assert_eq!(build_claude_cmd("claude", false), "claude");
@@ -184,12 +1766,36 @@ mod tests {
             root_path: "/tmp/repos/test".to_string(),
             remote: "origin".to_string(),
             display_order: 0,
-            config,
+            config: config.map(|v| serde_json::from_value(v).unwrap()),
+            config_error: None,
             created_at: "".to_string(),
             updated_at: "".to_string(),
         }
     }
 
+    fn make_workspace(config: Option<serde_json::Value>) -> Workspace {
+        Workspace {
+            id: "ws".to_string(),
+            repository_id: "id".to_string(),
+            directory_name: "fix".to_string(),
+            branch: "fix".to_string(),
+            state: WorkspaceState::Ready,
+            state_reason: None,
+            container_mode: ContainerMode::Container,
+            container_id: None,
+            created_at: "".to_string(),
+            updated_at: "".to_string(),
+            archived_at: None,
+            description: None,
+            notes: None,
+            labels: Vec::new(),
+            config: config.map(|v| serde_json::from_value(v).unwrap()),
+            branch_cleanup: None,
+            pull_request: None,
+            warnings: Vec::new(),
+        }
+    }
+
     #[test]
     fn get_container_config_none_when_no_config() {
         let repo = make_repo(None);
@@ -198,7 +1804,7 @@ mod tests {
 
     #[test]
     fn get_container_config_none_when_no_container_key() {
-        let repo = make_repo(Some(serde_json::json!({"other": "value"})));
+        let repo = make_repo(Some(serde_json::json!({"scripts": {"setup": "make"}})));
         assert!(get_container_config(&repo).is_none());
     }
 
@@ -217,18 +1823,10 @@ mod tests {
         assert!(cfg.dangerously_skip_permissions);
     }
 
-    #[test]
-    fn get_container_config_ignores_invalid_shape() {
-        let repo = make_repo(Some(serde_json::json!({
-            "container": "not an object"
-        })));
-        assert!(get_container_config(&repo).is_none());
-    }
-
     #[test]
     fn should_skip_permissions_false_when_no_config() {
         let repo = make_repo(None);
-        assert!(!should_skip_permissions(&repo));
+        assert!(!should_skip_permissions(&repo, &make_workspace(None)));
     }
 
     #[test]
@@ -236,7 +1834,7 @@ mod tests {
         let repo = make_repo(Some(serde_json::json!({
             "container": {"enabled": true}
         })));
-        assert!(!should_skip_permissions(&repo));
+        assert!(!should_skip_permissions(&repo, &make_workspace(None)));
     }
 
     #[test]
@@ -247,66 +1845,74 @@ mod tests {
                 "dangerously_skip_permissions": true
             }
         })));
-        assert!(should_skip_permissions(&repo));
+        assert!(should_skip_permissions(&repo, &make_workspace(None)));
     }
-}
 
-/// Create a workspace container (Docker container setup for container-mode workspaces).
-/// Returns the updated workspace with container_id set.
-/// Takes Arc<AppState> to avoid holding MutexGuard across await points.
-pub async fn setup_workspace_container(
-    state: &Arc<AppState>,
-    workspace: &Workspace,
-    repo: &Repo,
-) -> std::result::Result<Workspace, String> {
-    let container_config = get_container_config(repo);
-
-    let image = container_config
-        .as_ref()
-        .and_then(|c| c.image.clone())
-        .unwrap_or_else(|| "node:22".to_string());
-    let ports = container_config
-        .as_ref()
-        .and_then(|c| c.ports.clone())
-        .unwrap_or_default();
-    let env: Vec<String> = container_config
-        .as_ref()
-        .and_then(|c| c.env.clone())
-        .map(|m| m.into_iter().map(|(k, v)| format!("{}={}", k, v)).collect())
-        .unwrap_or_default();
+    #[test]
+    fn should_skip_permissions_uses_workspace_override() {
+        let repo = make_repo(Some(serde_json::json!({
+            "container": {"enabled": true}
+        })));
+        let ws = make_workspace(Some(serde_json::json!({
+            "container": {"dangerously_skip_permissions": true}
+        })));
+        assert!(should_skip_permissions(&repo, &ws));
+    }
 
-    let wt_path = workspace_path(&repo.root_path, &repo.name, &workspace.directory_name)
-        .map_err(|e| e.to_string())?;
-    let container_name = docker::sanitize_docker_name(
-        &format!("bunyan-{}-{}", repo.name, workspace.directory_name),
-    );
+    #[test]
+    fn effective_container_config_applies_image_override() {
+        let repo = make_repo(Some(serde_json::json!({
+            "container": {"enabled": true, "image": "node:22"}
+        })));
+        let ws = make_workspace(Some(serde_json::json!({
+            "container": {"image": "node:20"}
+        })));
+        let cfg = effective_container_config(&repo, &ws).unwrap();
+        assert!(cfg.enabled);
+        assert_eq!(cfg.image.as_deref(), Some("node:20"));
+    }
 
-    let network_name = docker::sanitize_docker_name(&format!("bunyan-{}", repo.name));
-    docker::create_network(&network_name)
-        .await
-        .map_err(|e| e.to_string())?;
+    #[test]
+    fn container_spec_uses_overrides_given_at_creation() {
+        use crate::models::{BranchMode, CreateRepoInput, CreateWorkspaceInput};
 
-    let container_id = docker::create_workspace_container(
-        &image,
-        &wt_path,
-        &container_name,
-        &ports,
-        &env,
-        Some(&network_name),
-        &workspace.directory_name,
-    )
-    .await
-    .map_err(|e| e.to_string())?;
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        crate::db::schema::initialize_database(&conn).unwrap();
+        let repo = db::repos::create(
+            &conn,
+            CreateRepoInput {
+                name: "app".to_string(),
+                remote_url: "git@github.com:org/app.git".to_string(),
+                root_path: "/repos/app".to_string(),
+                default_branch: "main".to_string(),
+                remote: "origin".to_string(),
+                display_order: 0,
+                config: Some(serde_json::json!({
+                    "container": {"enabled": true, "image": "node:22", "ports": ["3000:3000"], "env": {"A": "1"}}
+                })),
+                adopt: false,
+            },
+        )
+        .unwrap();
+        let ws = db::workspaces::create_pending(
+            &conn,
+            CreateWorkspaceInput {
+                repository_id: repo.id.clone(),
+                directory_name: "api".to_string(),
+                branch: "api".to_string(),
+                container_mode: ContainerMode::Container,
+                branch_mode: BranchMode::New,
+                base_ref: None,
+                config: Some(serde_json::json!({
+                    "container": {"image": "python:3.12", "ports": ["8000:8000"], "env": {"B": "2"}}
+                })),
+            },
+        )
+        .unwrap();
 
-    // Best-effort: install claude in the container
-    if let Err(e) = docker::ensure_claude(&container_id).await {
-        eprintln!("Warning: could not install Claude in container: {}", e);
+        let spec = container_spec(&repo, &ws);
+        assert_eq!(spec.image, "python:3.12");
+        assert_eq!(spec.ports, vec!["3000:3000", "8000:8000"]);
+        assert_eq!(spec.env, vec!["A=1", "B=2"]);
     }
-
-    // Lock only for DB operations, not across await
-    let conn = state.db.lock().unwrap();
-    db::workspaces::set_container_id(&conn, &workspace.id, &container_id)
-        .map_err(|e| e.to_string())?;
-
-    db::workspaces::get(&conn, &workspace.id).map_err(|e| e.to_string())
 }
@@ -6,6 +6,7 @@ import type {
   ClaudeSessionEntry,
   WorkspacePaneInfo,
   ContainerMode,
+  UpdateRepoInput,
 } from "./api";
 import { checkForUpdates, type Update } from "./updater";
 import { AppContext } from "@/lib/context";
@@ -182,7 +183,7 @@ function App() {
         name: null,
         default_branch: null,
         display_order: null,
-        config,
+        config: config as UpdateRepoInput["config"],
       });
       setRepos((prev) => prev.map((r) => (r.id === repo.id ? repo : r)));
     },
@@ -8,6 +8,7 @@ export type WorkspacePaneInfo = components["schemas"]["WorkspacePaneInfo"];
 export type TmuxPane = components["schemas"]["TmuxPane"];
 export type ClaudeSessionEntry = components["schemas"]["ClaudeSessionEntry"];
 export type Setting = components["schemas"]["Setting"];
+export type EffectiveSetting = components["schemas"]["EffectiveSetting"];
 export type PortMapping = components["schemas"]["PortMapping"];
 export type ContainerMode = components["schemas"]["ContainerMode"];
 export type CreateRepoInput = components["schemas"]["CreateRepoInput"];
@@ -170,7 +171,7 @@ export async function getContainerPorts(workspaceId: string): Promise<PortMappin
 
 // --- Settings ---
 
-export async function getSetting(key: string): Promise<Setting> {
+export async function getSetting(key: string): Promise<EffectiveSetting> {
   return unwrap(await api.GET("/settings/{key}", { params: { path: { key } } }));
 }
 
@@ -183,7 +184,7 @@ export async function setSetting(key: string, value: string): Promise<Setting> {
   );
 }
 
-export async function getAllSettings(): Promise<Setting[]> {
+export async function getAllSettings(): Promise<EffectiveSetting[]> {
   return unwrap(await api.GET("/settings"));
 }
 
@@ -4,6 +4,38 @@
  */
 
 export interface paths {
+    "/db/migrate": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["run_migrations"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/db/migrations": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["migration_status"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/docker/status": {
         parameters: {
             query?: never;
@@ -20,6 +52,38 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/doctor": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["doctor_check"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/doctor/fix": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["doctor_fix"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/editors": {
         parameters: {
             query?: never;
@@ -36,6 +100,22 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/events": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["list_events"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/health": {
         parameters: {
             query?: never;
@@ -68,6 +148,22 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/repos/reorder": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["reorder_repos"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/repos/{id}": {
         parameters: {
             query?: never;
@@ -84,6 +180,22 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/repos/{id}/config/validate": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["validate_repo_config"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/sessions/active": {
         parameters: {
             query?: never;
@@ -116,6 +228,22 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/settings/definitions": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["list_setting_definitions"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/settings/{key}": {
         parameters: {
             query?: never;
@@ -164,6 +292,22 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/workspaces/purge": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["purge_workspaces"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/workspaces/{id}": {
         parameters: {
             query?: never;
@@ -172,9 +316,9 @@ export interface paths {
             cookie?: never;
         };
         get: operations["get_workspace"];
-        put?: never;
+        put: operations["update_workspace"];
         post?: never;
-        delete?: never;
+        delete: operations["delete_workspace"];
         options?: never;
         head?: never;
         patch?: never;
@@ -196,6 +340,38 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/workspaces/{id}/checkpoints": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["list_checkpoints"];
+        put?: never;
+        post: operations["create_checkpoint"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/workspaces/{id}/checkpoints/{checkpoint_id}/restore": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["restore_checkpoint"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/workspaces/{id}/claude": {
         parameters: {
             query?: never;
@@ -228,6 +404,38 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/workspaces/{id}/commit": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["workspace_commit"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/workspaces/{id}/config": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_workspace_config"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/workspaces/{id}/container/ports": {
         parameters: {
             query?: never;
@@ -260,6 +468,22 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/workspaces/{id}/diff": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["workspace_diff"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/workspaces/{id}/editor": {
         parameters: {
             query?: never;
@@ -276,6 +500,54 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/workspaces/{id}/git/status": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["workspace_git_status"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/workspaces/{id}/hooks": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["list_hook_runs"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/workspaces/{id}/hooks/{run}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_hook_run"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/workspaces/{id}/panes": {
         parameters: {
             query?: never;
@@ -308,23 +580,23 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
-    "/workspaces/{id}/sessions": {
+    "/workspaces/{id}/pull-request": {
         parameters: {
             query?: never;
             header?: never;
             path?: never;
             cookie?: never;
         };
-        get: operations["get_sessions"];
+        get?: never;
         put?: never;
-        post?: never;
+        post: operations["workspace_pull_request"];
         delete?: never;
         options?: never;
         head?: never;
         patch?: never;
         trace?: never;
     };
-    "/workspaces/{id}/shell": {
+    "/workspaces/{id}/push": {
         parameters: {
             query?: never;
             header?: never;
@@ -333,14 +605,14 @@ export interface paths {
         };
         get?: never;
         put?: never;
-        post: operations["open_shell"];
+        post: operations["workspace_push"];
         delete?: never;
         options?: never;
         head?: never;
         patch?: never;
         trace?: never;
     };
-    "/workspaces/{id}/view": {
+    "/workspaces/{id}/resume": {
         parameters: {
             query?: never;
             header?: never;
@@ -349,22 +621,196 @@ export interface paths {
         };
         get?: never;
         put?: never;
-        post: operations["view_workspace"];
+        post: operations["resume_workspace"];
         delete?: never;
         options?: never;
         head?: never;
         patch?: never;
         trace?: never;
     };
-}
-export type webhooks = Record<string, never>;
-export interface components {
-    schemas: {
-        ClaudeResumeInput: {
-            session_id: string;
-        };
-        /** @description A single session entry from ~/.claude/projects/<path>/sessions-index.json */
-        ClaudeSessionEntry: {
+    "/workspaces/{id}/sessions": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_sessions"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/workspaces/{id}/shell": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["open_shell"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/workspaces/{id}/sparse": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["workspace_sparse_checkout"];
+        put?: never;
+        post: operations["workspace_widen_sparse"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/workspaces/{id}/suspend": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["suspend_workspace"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/workspaces/{id}/sync": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["workspace_sync"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/workspaces/{id}/transitions": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["list_workspace_transitions"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/workspaces/{id}/view": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["view_workspace"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+}
+export type webhooks = Record<string, never>;
+export interface components {
+    schemas: {
+        ArchiveInput: {
+            /** @description Delete the local branch once the worktree is gone, if it is merged into
+             *     the default branch (or regardless, with `force`) */
+            delete_branch?: boolean;
+            /** @description Also delete the branch on the remote, under the same condition */
+            delete_remote_branch?: boolean;
+            /** @description Archive even if the worktree has uncommitted files or unpushed commits,
+             *     discarding them */
+            force?: boolean;
+        };
+        /** @description Branch cleanup recorded on a workspace when it is archived. */
+        BranchCleanup: {
+            /** @description Why a branch was kept or why deletion failed */
+            detail?: string | null;
+            local?: null | components["schemas"]["BranchOutcome"];
+            remote?: null | components["schemas"]["BranchOutcome"];
+        };
+        /**
+         * @description How a new workspace gets its branch.
+         * @enum {string}
+         */
+        BranchMode: "new" | "existing" | "remote";
+        /**
+         * @description What happened to one copy (local or remote) of an archived workspace's branch.
+         * @enum {string}
+         */
+        BranchOutcome: "deleted" | "kept" | "not_merged" | "missing" | "failed";
+        /** @description How much of a large repo is downloaded and checked out. `filter` and
+         *     `depth` apply when the repo is cloned; `sparse` to each new worktree. */
+        CheckoutConfig: {
+            /**
+             * Format: int32
+             * @description Shallow clone depth
+             */
+            depth?: number | null;
+            /** @description Partial clone filter, e.g. `blob:none` */
+            filter?: string | null;
+            /** @description Run `git lfs pull` in each new worktree */
+            lfs?: boolean;
+            /** @description Directories (sparse-checkout cone) to check out; empty checks out everything */
+            sparse?: string[];
+            /** @description Initialize and update submodules (recursively) in each new worktree */
+            submodules?: boolean;
+        };
+        /** @description A snapshot of a workspace's working tree, stored as a commit under
+         *     `refs/bunyan/checkpoints/<workspace_id>/<id>`. */
+        Checkpoint: {
+            created_at: string;
+            /** @description The commit HEAD was at when the checkpoint was taken */
+            head: string;
+            /** @description UTC timestamp name, e.g. `20260101T120000123Z` */
+            id: string;
+            message: string;
+            /** @description The checkpoint commit */
+            sha: string;
+            workspace_id: string;
+        };
+        /**
+         * @description Combined result of a commit's CI checks and statuses.
+         * @enum {string}
+         */
+        ChecksState: "pending" | "success" | "failure";
+        ClaudeResumeInput: {
+            session_id: string;
+        };
+        /** @description A single session entry from ~/.claude/projects/<path>/sessions-index.json */
+        ClaudeSessionEntry: {
             created?: string | null;
             first_prompt?: string | null;
             git_branch?: string | null;
@@ -374,6 +820,37 @@ export interface components {
             modified?: string | null;
             session_id: string;
         };
+        CommitInput: {
+            /** @description Stage every modified or deleted tracked file first, like `git commit --all` */
+            all?: boolean;
+            message: string;
+            /** @description Stage and commit only these paths (relative to the worktree) */
+            paths?: string[];
+        };
+        CommitResult: {
+            commit?: null | components["schemas"]["CommitSummary"];
+            /** @description git's (and any hook's) output */
+            output: string;
+            status: components["schemas"]["CommitStatus"];
+            workspace_id: string;
+        };
+        /** @enum {string} */
+        CommitStatus: "committed" | "nothing_to_commit" | "hook_failed";
+        CommitSummary: {
+            author: string;
+            committed_at: string;
+            sha: string;
+            summary: string;
+        };
+        /** @description A problem with one field of a repo config, e.g. `container.ports[1]`. */
+        ConfigFieldError: {
+            message: string;
+            path: string;
+        };
+        ConfigValidation: {
+            errors: components["schemas"]["ConfigFieldError"][];
+            valid: boolean;
+        };
         ContainerConfig: {
             dangerously_skip_permissions?: boolean;
             enabled: boolean;
@@ -386,33 +863,191 @@ export interface components {
         };
         /** @enum {string} */
         ContainerMode: "local" | "container";
+        /** @description Container settings a workspace can override. `image`, `shell` and
+         *     `dangerously_skip_permissions` replace the repo's values; `ports` are added
+         *     to the repo's and `env` entries are merged over the repo's. */
+        ContainerOverrides: {
+            dangerously_skip_permissions?: boolean | null;
+            env?: {
+                [key: string]: string;
+            } | null;
+            image?: string | null;
+            ports?: string[] | null;
+            shell?: string | null;
+        };
         ContainerStatusResponse: {
             status: string;
         };
+        CreateCheckpointInput: {
+            /** @description Defaults to "Checkpoint" */
+            message?: string | null;
+        };
+        CreatePullRequestInput: {
+            /** @description Target branch (default: the repo's `default_branch`) */
+            base?: string | null;
+            body?: string | null;
+            draft?: boolean;
+            /** @description Defaults to the subject of the branch's latest commit */
+            title?: string | null;
+        };
         CreateRepoInput: {
-            config?: unknown;
+            /** @description Register the clone already at `root_path` instead of cloning, taking the
+             *     remote URL and default branch from it and importing its worktrees */
+            adopt?: boolean;
+            config?: null | components["schemas"]["RepoConfig"];
             default_branch?: string;
             /** Format: int32 */
             display_order?: number;
             name: string;
             remote?: string;
-            remote_url: string;
+            /** @description Ignored when adopting; the clone's remote URL is used instead */
+            remote_url?: string;
             root_path: string;
         };
         CreateWorkspaceInput: {
+            /** @description Start point for `new`, or the remote-tracking ref for `remote` */
+            base_ref?: string | null;
             branch: string;
+            branch_mode?: components["schemas"]["BranchMode"];
+            config?: null | components["schemas"]["WorkspaceConfig"];
             container_mode?: components["schemas"]["ContainerMode"];
             directory_name: string;
             repository_id: string;
         };
+        DiffFileStat: {
+            /** Format: int32 */
+            additions: number;
+            /** @description Binary files have no line counts */
+            binary: boolean;
+            /** Format: int32 */
+            deletions: number;
+            path: string;
+        };
         DockerStatusResponse: {
             available: boolean;
         };
+        DoctorIssue: {
+            fix_error?: string | null;
+            /** @description Whether `--fix` knows how to repair this */
+            fixable: boolean;
+            /** @description Set once a fix has been applied successfully */
+            fixed: boolean;
+            kind: components["schemas"]["IssueKind"];
+            message: string;
+            repo_id?: string | null;
+            /** @description The repo name, worktree path, tmux window (`session:window`) or container ID concerned */
+            subject: string;
+            workspace_id?: string | null;
+        };
+        DoctorReport: {
+            issues: components["schemas"]["DoctorIssue"][];
+            /** @description Checks that could not run, with the reason (e.g. Docker unavailable) */
+            skipped: string[];
+        };
+        /** @description The value a setting currently resolves to: the stored value if there is
+         *     one, otherwise the registered default. */
+        EffectiveSetting: {
+            /** @description True when no value is stored and the default applies */
+            is_default: boolean;
+            key: string;
+            /** @description False for stored keys that are not in the settings registry */
+            known: boolean;
+            /** @description When the stored value was last written, or null for defaults */
+            updated_at?: string | null;
+            value: string;
+        };
+        /** @description The configuration a workspace actually runs with: repo config with the
+         *     workspace's overrides applied, and the editor resolved from settings. */
+        EffectiveWorkspaceConfig: {
+            container?: null | components["schemas"]["ContainerConfig"];
+            editor: string;
+            scripts?: null | components["schemas"]["RepoScripts"];
+        };
         ErrorResponse: {
+            /** @description Machine-readable error kind, e.g. `branch_exists` or `git_auth` */
+            code?: string;
             error: string;
         };
+        /** @description An audit log entry recording something Bunyan did. */
+        Event: {
+            created_at: string;
+            /** Format: int64 */
+            id: number;
+            kind: components["schemas"]["EventKind"];
+            message?: string | null;
+            repo_id?: string | null;
+            workspace_id?: string | null;
+        };
+        /** @enum {string} */
+        EventKind: "repo_created" | "repo_updated" | "repo_deleted" | "workspace_created" | "workspace_failed" | "workspace_archived" | "workspace_suspended" | "workspace_resumed" | "workspace_purged" | "container_created" | "container_removed" | "claude_started" | "claude_resumed" | "shell_opened" | "pane_killed" | "workspace_committed" | "workspace_pushed" | "pull_request_opened" | "workspace_synced" | "checkpoint_created" | "checkpoint_restored" | "hook_ran";
+        /** @description Git state of a workspace's worktree. */
+        GitStatus: {
+            /**
+             * Format: int32
+             * @description None when `base` does not exist locally
+             */
+            ahead?: number | null;
+            /** @description The ref ahead/behind are measured against, `<remote>/<default_branch>` */
+            base: string;
+            /** Format: int32 */
+            behind?: number | null;
+            /** @description Checked-out branch; None when HEAD is detached */
+            branch?: string | null;
+            /** Format: int32 */
+            conflicted: number;
+            /**
+             * Format: int32
+             * @description Tracked files with unstaged changes
+             */
+            dirty: number;
+            last_commit?: null | components["schemas"]["CommitSummary"];
+            /** Format: int32 */
+            staged: number;
+            /** Format: int32 */
+            untracked: number;
+            workspace_id: string;
+        };
+        /**
+         * @description A repo lifecycle script (see `RepoScripts`).
+         * @enum {string}
+         */
+        HookKind: "post_checkout" | "setup" | "teardown";
+        /** @description A finished run of a lifecycle hook in a workspace. */
+        HookRun: {
+            command: string;
+            /**
+             * Format: int32
+             * @description Exit status, or null if the script could not be started, timed out, or was killed by a signal
+             */
+            exit_code?: number | null;
+            finished_at: string;
+            hook: components["schemas"]["HookKind"];
+            /** Format: int64 */
+            id: number;
+            /** @description Combined stdout and stderr (the tail, if very long) */
+            output: string;
+            started_at: string;
+            /** @description Whether the script exited with status 0 */
+            success: boolean;
+            workspace_id: string;
+        };
+        /**
+         * @description A kind of drift between the database and the worktrees, tmux windows and
+         *     containers it describes.
+         * @enum {string}
+         */
+        IssueKind: "missing_worktree" | "untracked_worktree" | "stale_tmux_window" | "orphaned_container" | "missing_container" | "invalid_repo_config";
+        /** @description A schema migration and whether it has been applied to the database. */
+        MigrationStatus: {
+            /** @description When the migration was applied, or null if it is still pending */
+            applied_at?: string | null;
+            name: string;
+            /** Format: int64 */
+            version: number;
+        };
         OpenEditorInput: {
-            editor_id: string;
+            /** @description Editor to open; defaults to the workspace's configured editor */
+            editor_id?: string | null;
         };
         /** @description Port mapping for a running container. */
         PortMapping: {
@@ -420,8 +1055,52 @@ export interface components {
             host_ip: string;
             host_port: string;
         };
+        PullRequest: {
+            /** @description Target branch */
+            base: string;
+            checks?: null | components["schemas"]["ChecksState"];
+            /** @description Source branch */
+            head: string;
+            /** Format: int64 */
+            number: number;
+            state: components["schemas"]["PullRequestState"];
+            title: string;
+            /** @description Web URL of the pull request */
+            url: string;
+        };
+        /** @enum {string} */
+        PullRequestState: "open" | "draft" | "merged" | "closed";
+        PurgeWorkspacesInput: {
+            /** @description List what would be removed without deleting anything */
+            dry_run?: boolean;
+            /** @description Only purge workspaces archived longer ago than this age (e.g. "30d", "12h") */
+            older_than?: string | null;
+            /** @description Only purge workspaces of this repository */
+            repository_id?: string | null;
+        };
+        PurgeWorkspacesResult: {
+            dry_run: boolean;
+            /** @description Workspaces that were removed, or would be removed in a dry run */
+            workspaces: components["schemas"]["Workspace"][];
+        };
+        PushResult: {
+            branch: string;
+            /** @description git's (and any hook's) output */
+            output: string;
+            remote: string;
+            status: components["schemas"]["PushStatus"];
+            workspace_id: string;
+        };
+        /** @enum {string} */
+        PushStatus: "pushed" | "up_to_date" | "rejected" | "hook_failed";
+        ReorderReposInput: {
+            /** @description Every repository ID, in the desired display order */
+            ids: string[];
+        };
         Repo: {
-            config?: unknown;
+            config?: null | components["schemas"]["RepoConfig"];
+            /** @description Why the stored config could not be loaded; `config` is null while this is set */
+            config_error?: string | null;
             created_at: string;
             default_branch: string;
             /** Format: int32 */
@@ -433,6 +1112,28 @@ export interface components {
             root_path: string;
             updated_at: string;
         };
+        /** @description Per-repo configuration stored in `Repo.config`. */
+        RepoConfig: {
+            checkout?: null | components["schemas"]["CheckoutConfig"];
+            container?: null | components["schemas"]["ContainerConfig"];
+            /** @description How the frontend runs the `run` script */
+            runScriptMode?: string | null;
+            scripts?: null | components["schemas"]["RepoScripts"];
+        };
+        RepoScripts: {
+            /** @description Runs on the host right after a new worktree is checked out, before `setup` */
+            post_checkout?: string | null;
+            run?: string | null;
+            /** @description Runs once a new workspace is ready, inside its container in container mode */
+            setup?: string | null;
+            /** @description Runs before a workspace is archived, where `setup` ran */
+            teardown?: string | null;
+        };
+        RestoreCheckpointResult: {
+            /** @description Checkpoint of the state the restore replaced, to undo it with */
+            backup: components["schemas"]["Checkpoint"];
+            restored: components["schemas"]["Checkpoint"];
+        };
         SetSettingInput: {
             value: string;
         };
@@ -442,9 +1143,62 @@ export interface components {
             updated_at: string;
             value: string;
         };
+        /** @description A setting Bunyan knows about, with its type and default. */
+        SettingDefinition: {
+            /** @description Accepted values for `choice` settings; empty otherwise */
+            allowed_values: string[];
+            default_value?: string | null;
+            description: string;
+            key: string;
+            value_type: components["schemas"]["SettingType"];
+        };
+        /**
+         * @description The kind of value a registered setting holds.
+         * @enum {string}
+         */
+        SettingType: "string" | "boolean" | "integer" | "duration" | "choice";
+        /** @description Sparse-checkout state of a workspace's worktree. */
+        SparseCheckout: {
+            /** @description Checked-out directories (the cone); empty when not sparse */
+            paths: string[];
+            /** @description False when the whole tree is checked out */
+            sparse: boolean;
+            workspace_id: string;
+        };
+        SparseInput: {
+            /** @description Disable sparse checkout and check out the whole tree */
+            all?: boolean;
+            /** @description Directories to add to the sparse-checkout cone */
+            paths?: string[];
+        };
         StatusResponse: {
             status: string;
         };
+        SyncInput: {
+            /** @description On conflict, leave the rebase or merge in progress instead of aborting it */
+            stop_on_conflict?: boolean;
+            strategy?: components["schemas"]["SyncStrategy"];
+        };
+        SyncResult: {
+            /** @description Whether the conflicted rebase or merge was aborted, leaving the worktree as it was */
+            aborted: boolean;
+            /** @description The ref synced onto, `<remote>/<default_branch>` */
+            base: string;
+            /** @description Files with conflicts, when `status` is `conflicted` */
+            conflicts: string[];
+            /** @description git's output */
+            output: string;
+            status: components["schemas"]["SyncStatus"];
+            strategy: components["schemas"]["SyncStrategy"];
+            workspace_id: string;
+        };
+        /** @enum {string} */
+        SyncStatus: "synced" | "up_to_date" | "conflicted";
+        /**
+         * @description How `POST /workspaces/{id}/sync` brings in the default branch.
+         * @enum {string}
+         */
+        SyncStrategy: "rebase" | "merge";
         SystemInfo: {
             home_dir: string;
         };
@@ -468,23 +1222,71 @@ export interface components {
             workspace_path: string;
         };
         UpdateRepoInput: {
-            config?: unknown;
+            config?: null | components["schemas"]["RepoConfig"];
             default_branch?: string | null;
             /** Format: int32 */
             display_order?: number | null;
             id: string;
             name?: string | null;
         };
+        UpdateWorkspaceInput: {
+            config?: null | components["schemas"]["WorkspaceConfig"];
+            /** @description New description; an empty string clears it */
+            description?: string | null;
+            /** @description Replaces the workspace's full set of labels */
+            labels?: string[] | null;
+            /** @description New notes; an empty string clears them */
+            notes?: string | null;
+        };
+        ValidateConfigInput: {
+            config?: null | components["schemas"]["RepoConfig"];
+        };
+        /** @enum {string} */
+        WarningKind: "submodules" | "lfs";
         Workspace: {
+            /** @description When the workspace was archived; null unless it is archived */
+            archived_at?: string | null;
             branch: string;
+            branch_cleanup?: null | components["schemas"]["BranchCleanup"];
+            config?: null | components["schemas"]["WorkspaceConfig"];
             container_id?: string | null;
             container_mode: components["schemas"]["ContainerMode"];
             created_at: string;
+            /** @description Short free-form summary of what the workspace is for */
+            description?: string | null;
             directory_name: string;
             id: string;
+            labels: string[];
+            /** @description Longer free-form notes */
+            notes?: string | null;
+            pull_request?: null | components["schemas"]["PullRequest"];
             repository_id: string;
             state: components["schemas"]["WorkspaceState"];
+            /** @description Reason recorded with the most recent state transition */
+            state_reason?: string | null;
             updated_at: string;
+            /** @description Non-fatal problems from creating the workspace, e.g. a failed LFS pull */
+            warnings: components["schemas"]["WorkspaceWarning"][];
+        };
+        /** @description Per-workspace settings that take precedence over the repo config and
+         *     global settings. */
+        WorkspaceConfig: {
+            container?: null | components["schemas"]["ContainerOverrides"];
+            /** @description Editor ID used instead of the `preferred_editor` setting */
+            editor?: string | null;
+        };
+        /** @description Changes in a workspace's worktree (committed or not) since it diverged from `base`. */
+        WorkspaceDiff: {
+            /** @description The ref compared against */
+            base: string;
+            files: components["schemas"]["DiffFileStat"][];
+            /** @description Merge-base commit of HEAD and `base`; the diff starts here */
+            merge_base: string;
+            /** @description Unified diff; omitted in stat mode */
+            patch?: string | null;
+            /** @description Whether `patch` was cut at the size cap */
+            truncated: boolean;
+            workspace_id: string;
         };
         /** @description Info about all panes in a workspace's tmux window. */
         WorkspacePaneInfo: {
@@ -494,7 +1296,22 @@ export interface components {
             workspace_name: string;
         };
         /** @enum {string} */
-        WorkspaceState: "ready" | "archived";
+        WorkspaceState: "creating" | "ready" | "failed" | "suspended" | "archiving" | "archived";
+        /** @description A recorded change of a workspace's lifecycle state. */
+        WorkspaceTransition: {
+            created_at: string;
+            from_state?: null | components["schemas"]["WorkspaceState"];
+            /** Format: int64 */
+            id: number;
+            reason?: string | null;
+            to_state: components["schemas"]["WorkspaceState"];
+            workspace_id: string;
+        };
+        WorkspaceWarning: {
+            kind: components["schemas"]["WarningKind"];
+            /** @description What failed, with git's output */
+            message: string;
+        };
     };
     responses: never;
     parameters: never;
@@ -504,7 +1321,7 @@ export interface components {
 }
 export type $defs = Record<string, never>;
 export interface operations {
-    docker_status: {
+    run_migrations: {
         parameters: {
             query?: never;
             header?: never;
@@ -518,7 +1335,7 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["DockerStatusResponse"];
+                    "application/json": components["schemas"]["MigrationStatus"][];
                 };
             };
             500: {
@@ -531,7 +1348,7 @@ export interface operations {
             };
         };
     };
-    detect_editors: {
+    migration_status: {
         parameters: {
             query?: never;
             header?: never;
@@ -545,7 +1362,7 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": string[];
+                    "application/json": components["schemas"]["MigrationStatus"][];
                 };
             };
             500: {
@@ -558,7 +1375,7 @@ export interface operations {
             };
         };
     };
-    health_check: {
+    docker_status: {
         parameters: {
             query?: never;
             header?: never;
@@ -572,12 +1389,20 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["StatusResponse"];
+                    "application/json": components["schemas"]["DockerStatusResponse"];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
                 };
             };
         };
     };
-    list_repos: {
+    doctor_check: {
         parameters: {
             query?: never;
             header?: never;
@@ -591,7 +1416,7 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["Repo"][];
+                    "application/json": components["schemas"]["DoctorReport"];
                 };
             };
             500: {
@@ -604,25 +1429,21 @@ export interface operations {
             };
         };
     };
-    create_repo: {
+    doctor_fix: {
         parameters: {
             query?: never;
             header?: never;
             path?: never;
             cookie?: never;
         };
-        requestBody: {
-            content: {
-                "application/json": components["schemas"]["CreateRepoInput"];
-            };
-        };
+        requestBody?: never;
         responses: {
             200: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["Repo"];
+                    "application/json": components["schemas"]["DoctorReport"];
                 };
             };
             500: {
@@ -635,14 +1456,49 @@ export interface operations {
             };
         };
     };
-    get_repo: {
+    detect_editors: {
         parameters: {
             query?: never;
             header?: never;
-            path: {
-                /** @description Repository ID */
-                id: string;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": string[];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    list_events: {
+        parameters: {
+            query?: {
+                /** @description Filter by repository ID */
+                repo_id?: string;
+                /** @description Filter by workspace ID */
+                workspace_id?: string;
+                /** @description Only events at or after this RFC 3339 timestamp */
+                since?: string;
+                /** @description Filter by event kind */
+                kind?: components["schemas"]["EventKind"];
+                /** @description Maximum number of events (default 100) */
+                limit?: number;
             };
+            header?: never;
+            path?: never;
             cookie?: never;
         };
         requestBody?: never;
@@ -652,7 +1508,1192 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["Repo"];
+                    "application/json": components["schemas"]["Event"][];
+                };
+            };
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    health_check: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["StatusResponse"];
+                };
+            };
+        };
+    };
+    list_repos: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Repo"][];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    create_repo: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateRepoInput"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Repo"];
+                };
+            };
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    reorder_repos: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["ReorderReposInput"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Repo"][];
+                };
+            };
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    get_repo: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Repository ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Repo"];
+                };
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    update_repo: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Repository ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UpdateRepoInput"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Repo"];
+                };
+            };
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    delete_repo: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Repository ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    validate_repo_config: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Repository ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["ValidateConfigInput"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ConfigValidation"];
+                };
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    active_sessions: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["WorkspacePaneInfo"][];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    list_settings: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["EffectiveSetting"][];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    list_setting_definitions: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["SettingDefinition"][];
+                };
+            };
+        };
+    };
+    get_setting: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Setting key */
+                key: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["EffectiveSetting"];
+                };
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    set_setting: {
+        parameters: {
+            query?: {
+                /** @description Store unknown keys and unvalidated values */
+                force?: boolean;
+            };
+            header?: never;
+            path: {
+                /** @description Setting key */
+                key: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["SetSettingInput"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Setting"];
+                };
+            };
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    system_info: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["SystemInfo"];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    list_workspaces: {
+        parameters: {
+            query?: {
+                /** @description Filter by repository ID */
+                repo_id?: string;
+                /** @description Comma-separated labels; all must match */
+                label?: string;
+                /** @description Filter by state */
+                state?: components["schemas"]["WorkspaceState"];
+                /** @description Filter by container mode */
+                container_mode?: components["schemas"]["ContainerMode"];
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Workspace"][];
+                };
+            };
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    create_workspace: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateWorkspaceInput"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Workspace"];
+                };
+            };
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    purge_workspaces: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["PurgeWorkspacesInput"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["PurgeWorkspacesResult"];
+                };
+            };
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    get_workspace: {
+        parameters: {
+            query?: {
+                /** @description Refresh the pull request from the forge (default: false) */
+                refresh?: boolean;
+            };
+            header?: never;
+            path: {
+                /** @description Workspace ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Workspace"];
+                };
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    update_workspace: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Workspace ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UpdateWorkspaceInput"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Workspace"];
+                };
+            };
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    delete_workspace: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Workspace ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Workspace"];
+                };
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    archive_workspace: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Workspace ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: {
+            content: {
+                "application/json": null | components["schemas"]["ArchiveInput"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Workspace"];
+                };
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    list_checkpoints: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Workspace ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Checkpoint"][];
+                };
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    create_checkpoint: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Workspace ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: {
+            content: {
+                "application/json": null | components["schemas"]["CreateCheckpointInput"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Checkpoint"];
+                };
+            };
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    restore_checkpoint: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Workspace ID */
+                id: string;
+                /** @description Checkpoint ID */
+                checkpoint_id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["RestoreCheckpointResult"];
+                };
+            };
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    start_claude: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Workspace ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["StatusResponse"];
+                };
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    resume_claude: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Workspace ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["ClaudeResumeInput"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["StatusResponse"];
+                };
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    workspace_commit: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Workspace ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CommitInput"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["CommitResult"];
+                };
+            };
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    get_workspace_config: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Workspace ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["EffectiveWorkspaceConfig"];
+                };
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    container_ports: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Workspace ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["PortMapping"][];
+                };
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    container_status: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Workspace ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ContainerStatusResponse"];
+                };
+            };
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+        };
+    };
+    workspace_diff: {
+        parameters: {
+            query?: {
+                /** @description Ref to compare against (default: <remote>/<default_branch>) */
+                base?: string;
+                /** @description Comma-separated paths to limit the diff to */
+                paths?: string;
+                /** @description Only return per-file stats */
+                stat?: boolean;
+                /** @description Patch size cap in bytes (default 1 MiB) */
+                max_bytes?: number;
+            };
+            header?: never;
+            path: {
+                /** @description Workspace ID */
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["WorkspaceDiff"];
+                };
+            };
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
                 };
             };
             404: {
@@ -673,19 +2714,19 @@ export interface operations {
             };
         };
     };
-    update_repo: {
+    open_editor: {
         parameters: {
             query?: never;
             header?: never;
             path: {
-                /** @description Repository ID */
+                /** @description Workspace ID */
                 id: string;
             };
             cookie?: never;
         };
         requestBody: {
             content: {
-                "application/json": components["schemas"]["UpdateRepoInput"];
+                "application/json": components["schemas"]["OpenEditorInput"];
             };
         };
         responses: {
@@ -694,7 +2735,7 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["Repo"];
+                    "application/json": components["schemas"]["StatusResponse"];
                 };
             };
             404: {
@@ -705,22 +2746,14 @@ export interface operations {
                     "application/json": components["schemas"]["ErrorResponse"];
                 };
             };
-            500: {
-                headers: {
-                    [name: string]: unknown;
-                };
-                content: {
-                    "application/json": components["schemas"]["ErrorResponse"];
-                };
-            };
         };
     };
-    delete_repo: {
+    workspace_git_status: {
         parameters: {
             query?: never;
             header?: never;
             path: {
-                /** @description Repository ID */
+                /** @description Workspace ID */
                 id: string;
             };
             cookie?: never;
@@ -731,7 +2764,9 @@ export interface operations {
                 headers: {
                     [name: string]: unknown;
                 };
-                content?: never;
+                content: {
+                    "application/json": components["schemas"]["GitStatus"];
+                };
             };
             404: {
                 headers: {
@@ -751,11 +2786,14 @@ export interface operations {
             };
         };
     };
-    active_sessions: {
+    list_hook_runs: {
         parameters: {
             query?: never;
             header?: never;
-            path?: never;
+            path: {
+                /** @description Workspace ID */
+                id: string;
+            };
             cookie?: never;
         };
         requestBody?: never;
@@ -765,10 +2803,10 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["WorkspacePaneInfo"][];
+                    "application/json": components["schemas"]["HookRun"][];
                 };
             };
-            500: {
+            404: {
                 headers: {
                     [name: string]: unknown;
                 };
@@ -778,11 +2816,16 @@ export interface operations {
             };
         };
     };
-    list_settings: {
+    get_hook_run: {
         parameters: {
             query?: never;
             header?: never;
-            path?: never;
+            path: {
+                /** @description Workspace ID */
+                id: string;
+                /** @description Hook run ID */
+                run: number;
+            };
             cookie?: never;
         };
         requestBody?: never;
@@ -792,10 +2835,10 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["Setting"][];
+                    "application/json": components["schemas"]["HookRun"];
                 };
             };
-            500: {
+            404: {
                 headers: {
                     [name: string]: unknown;
                 };
@@ -805,13 +2848,13 @@ export interface operations {
             };
         };
     };
-    get_setting: {
+    get_panes: {
         parameters: {
             query?: never;
             header?: never;
             path: {
-                /** @description Setting key */
-                key: string;
+                /** @description Workspace ID */
+                id: string;
             };
             cookie?: never;
         };
@@ -822,7 +2865,7 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["Setting"];
+                    "application/json": components["schemas"]["TmuxPane"][];
                 };
             };
             404: {
@@ -835,31 +2878,29 @@ export interface operations {
             };
         };
     };
-    set_setting: {
+    kill_pane_handler: {
         parameters: {
             query?: never;
             header?: never;
             path: {
-                /** @description Setting key */
-                key: string;
+                /** @description Workspace ID */
+                id: string;
+                /** @description Pane index */
+                index: number;
             };
             cookie?: never;
         };
-        requestBody: {
-            content: {
-                "application/json": components["schemas"]["SetSettingInput"];
-            };
-        };
+        requestBody?: never;
         responses: {
             200: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["Setting"];
+                    "application/json": components["schemas"]["StatusResponse"];
                 };
             };
-            500: {
+            404: {
                 headers: {
                     [name: string]: unknown;
                 };
@@ -869,24 +2910,32 @@ export interface operations {
             };
         };
     };
-    system_info: {
+    workspace_pull_request: {
         parameters: {
             query?: never;
             header?: never;
-            path?: never;
+            path: {
+                /** @description Workspace ID */
+                id: string;
+            };
             cookie?: never;
         };
-        requestBody?: never;
+        /** @description Optional title, body, base and draft flag */
+        requestBody?: {
+            content: {
+                "application/json": null | components["schemas"]["CreatePullRequestInput"];
+            };
+        };
         responses: {
             200: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["SystemInfo"];
+                    "application/json": components["schemas"]["PullRequest"];
                 };
             };
-            500: {
+            400: {
                 headers: {
                     [name: string]: unknown;
                 };
@@ -894,29 +2943,23 @@ export interface operations {
                     "application/json": components["schemas"]["ErrorResponse"];
                 };
             };
-        };
-    };
-    list_workspaces: {
-        parameters: {
-            query?: {
-                /** @description Filter by repository ID */
-                repo_id?: string;
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
             };
-            header?: never;
-            path?: never;
-            cookie?: never;
-        };
-        requestBody?: never;
-        responses: {
-            200: {
+            409: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["Workspace"][];
+                    "application/json": components["schemas"]["ErrorResponse"];
                 };
             };
-            500: {
+            502: {
                 headers: {
                     [name: string]: unknown;
                 };
@@ -926,28 +2969,27 @@ export interface operations {
             };
         };
     };
-    create_workspace: {
+    workspace_push: {
         parameters: {
             query?: never;
             header?: never;
-            path?: never;
-            cookie?: never;
-        };
-        requestBody: {
-            content: {
-                "application/json": components["schemas"]["CreateWorkspaceInput"];
+            path: {
+                /** @description Workspace ID */
+                id: string;
             };
+            cookie?: never;
         };
+        requestBody?: never;
         responses: {
             200: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["Workspace"];
+                    "application/json": components["schemas"]["PushResult"];
                 };
             };
-            500: {
+            400: {
                 headers: {
                     [name: string]: unknown;
                 };
@@ -955,29 +2997,23 @@ export interface operations {
                     "application/json": components["schemas"]["ErrorResponse"];
                 };
             };
-        };
-    };
-    get_workspace: {
-        parameters: {
-            query?: never;
-            header?: never;
-            path: {
-                /** @description Workspace ID */
-                id: string;
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
             };
-            cookie?: never;
-        };
-        requestBody?: never;
-        responses: {
-            200: {
+            409: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["Workspace"];
+                    "application/json": components["schemas"]["ErrorResponse"];
                 };
             };
-            404: {
+            500: {
                 headers: {
                     [name: string]: unknown;
                 };
@@ -987,7 +3023,7 @@ export interface operations {
             };
         };
     };
-    archive_workspace: {
+    resume_workspace: {
         parameters: {
             query?: never;
             header?: never;
@@ -1015,9 +3051,17 @@ export interface operations {
                     "application/json": components["schemas"]["ErrorResponse"];
                 };
             };
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
         };
     };
-    start_claude: {
+    get_sessions: {
         parameters: {
             query?: never;
             header?: never;
@@ -1034,7 +3078,7 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["StatusResponse"];
+                    "application/json": components["schemas"]["ClaudeSessionEntry"][];
                 };
             };
             404: {
@@ -1047,7 +3091,7 @@ export interface operations {
             };
         };
     };
-    resume_claude: {
+    open_shell: {
         parameters: {
             query?: never;
             header?: never;
@@ -1057,11 +3101,7 @@ export interface operations {
             };
             cookie?: never;
         };
-        requestBody: {
-            content: {
-                "application/json": components["schemas"]["ClaudeResumeInput"];
-            };
-        };
+        requestBody?: never;
         responses: {
             200: {
                 headers: {
@@ -1081,7 +3121,7 @@ export interface operations {
             };
         };
     };
-    container_ports: {
+    workspace_sparse_checkout: {
         parameters: {
             query?: never;
             header?: never;
@@ -1098,7 +3138,7 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["PortMapping"][];
+                    "application/json": components["schemas"]["SparseCheckout"];
                 };
             };
             404: {
@@ -1109,29 +3149,15 @@ export interface operations {
                     "application/json": components["schemas"]["ErrorResponse"];
                 };
             };
-        };
-    };
-    container_status: {
-        parameters: {
-            query?: never;
-            header?: never;
-            path: {
-                /** @description Workspace ID */
-                id: string;
-            };
-            cookie?: never;
-        };
-        requestBody?: never;
-        responses: {
-            200: {
+            409: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["ContainerStatusResponse"];
+                    "application/json": components["schemas"]["ErrorResponse"];
                 };
             };
-            404: {
+            500: {
                 headers: {
                     [name: string]: unknown;
                 };
@@ -1141,7 +3167,7 @@ export interface operations {
             };
         };
     };
-    open_editor: {
+    workspace_widen_sparse: {
         parameters: {
             query?: never;
             header?: never;
@@ -1153,7 +3179,7 @@ export interface operations {
         };
         requestBody: {
             content: {
-                "application/json": components["schemas"]["OpenEditorInput"];
+                "application/json": components["schemas"]["SparseInput"];
             };
         };
         responses: {
@@ -1162,10 +3188,10 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["StatusResponse"];
+                    "application/json": components["schemas"]["SparseCheckout"];
                 };
             };
-            404: {
+            400: {
                 headers: {
                     [name: string]: unknown;
                 };
@@ -1173,29 +3199,23 @@ export interface operations {
                     "application/json": components["schemas"]["ErrorResponse"];
                 };
             };
-        };
-    };
-    get_panes: {
-        parameters: {
-            query?: never;
-            header?: never;
-            path: {
-                /** @description Workspace ID */
-                id: string;
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
             };
-            cookie?: never;
-        };
-        requestBody?: never;
-        responses: {
-            200: {
+            409: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["TmuxPane"][];
+                    "application/json": components["schemas"]["ErrorResponse"];
                 };
             };
-            404: {
+            500: {
                 headers: {
                     [name: string]: unknown;
                 };
@@ -1205,15 +3225,13 @@ export interface operations {
             };
         };
     };
-    kill_pane_handler: {
+    suspend_workspace: {
         parameters: {
             query?: never;
             header?: never;
             path: {
                 /** @description Workspace ID */
                 id: string;
-                /** @description Pane index */
-                index: number;
             };
             cookie?: never;
         };
@@ -1224,7 +3242,7 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["StatusResponse"];
+                    "application/json": components["schemas"]["Workspace"];
                 };
             };
             404: {
@@ -1235,9 +3253,17 @@ export interface operations {
                     "application/json": components["schemas"]["ErrorResponse"];
                 };
             };
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
         };
     };
-    get_sessions: {
+    workspace_sync: {
         parameters: {
             query?: never;
             header?: never;
@@ -1247,14 +3273,26 @@ export interface operations {
             };
             cookie?: never;
         };
-        requestBody?: never;
+        requestBody?: {
+            content: {
+                "application/json": null | components["schemas"]["SyncInput"];
+            };
+        };
         responses: {
             200: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["ClaudeSessionEntry"][];
+                    "application/json": components["schemas"]["SyncResult"];
+                };
+            };
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
                 };
             };
             404: {
@@ -1265,9 +3303,25 @@ export interface operations {
                     "application/json": components["schemas"]["ErrorResponse"];
                 };
             };
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ErrorResponse"];
+                };
+            };
         };
     };
-    open_shell: {
+    list_workspace_transitions: {
         parameters: {
             query?: never;
             header?: never;
@@ -1284,7 +3338,7 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["StatusResponse"];
+                    "application/json": components["schemas"]["WorkspaceTransition"][];
                 };
             };
             404: {
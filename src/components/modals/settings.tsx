@@ -134,6 +134,8 @@ function RepoSettingsItem({
   const config = asConfig(repo.config);
   const [setupScript, setSetupScript] = useState(config?.scripts?.setup ?? "");
   const [runScript, setRunScript] = useState(config?.scripts?.run ?? "");
+  const [teardownScript, setTeardownScript] = useState(config?.scripts?.teardown ?? "");
+  const [postCheckoutScript, setPostCheckoutScript] = useState(config?.scripts?.post_checkout ?? "");
   const [containerEnabled, setContainerEnabled] = useState(config?.container?.enabled ?? false);
   const [containerImage, setContainerImage] = useState(config?.container?.image ?? "node:22");
   const [skipPermissions, setSkipPermissions] = useState(
@@ -144,6 +146,8 @@ function RepoSettingsItem({
   const hasChanges =
     setupScript !== (config?.scripts?.setup ?? "") ||
     runScript !== (config?.scripts?.run ?? "") ||
+    teardownScript !== (config?.scripts?.teardown ?? "") ||
+    postCheckoutScript !== (config?.scripts?.post_checkout ?? "") ||
     containerEnabled !== (config?.container?.enabled ?? false) ||
     containerImage !== (config?.container?.image ?? "node:22") ||
     skipPermissions !== (config?.container?.dangerously_skip_permissions ?? false);
@@ -155,8 +159,11 @@ function RepoSettingsItem({
         scripts: {
           ...(setupScript ? { setup: setupScript } : {}),
           ...(runScript ? { run: runScript } : {}),
+          ...(teardownScript ? { teardown: teardownScript } : {}),
+          ...(postCheckoutScript ? { post_checkout: postCheckoutScript } : {}),
         },
         ...(config?.runScriptMode ? { runScriptMode: config.runScriptMode } : {}),
+        ...(config?.checkout ? { checkout: config.checkout } : {}),
         container: {
           enabled: containerEnabled,
           image: containerImage,
@@ -201,6 +208,22 @@ function RepoSettingsItem({
               placeholder="e.g. npm run dev"
             />
           </div>
+          <div className="grid gap-1.5">
+            <Label className="text-xs">Teardown script</Label>
+            <Input
+              value={teardownScript}
+              onChange={(e) => setTeardownScript(e.target.value)}
+              placeholder="e.g. docker compose down"
+            />
+          </div>
+          <div className="grid gap-1.5">
+            <Label className="text-xs">Post-checkout script</Label>
+            <Input
+              value={postCheckoutScript}
+              onChange={(e) => setPostCheckoutScript(e.target.value)}
+              placeholder='e.g. cp "$BUNYAN_REPO_PATH/.env" .'
+            />
+          </div>
         </div>
         {dockerAvailable && (
           <div className="pt-2 border-t space-y-3">
@@ -12,10 +12,19 @@ export interface ContainerConfig {
   dangerously_skip_permissions?: boolean;
 }
 
+export interface CheckoutConfig {
+  filter?: string;
+  depth?: number;
+  sparse?: string[];
+  submodules?: boolean;
+  lfs?: boolean;
+}
+
 export interface RepoConfig {
-  scripts?: { setup?: string; run?: string };
+  scripts?: { setup?: string; run?: string; teardown?: string; post_checkout?: string };
   runScriptMode?: string;
   container?: ContainerConfig;
+  checkout?: CheckoutConfig;
 }
 
 export type WorktreeStatus = "active" | "shell-only" | "idle" | "archived";